@@ -0,0 +1,12 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-css")
+def assert_css():
+    body = '''<html><body>
+<div class="item featured"><a href="/items/1">First</a></div>
+<div class="item"><a href="/items/2">Second</a></div>
+<p>Items: <span id="total">2</span></p>
+</body></html>'''
+    return Response(body, mimetype='text/html')
@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-json-body")
+def assert_json_body():
+    body = '''{"count":2,"users":[{"name":"Bob","id":1,"createdAt":"2020-10-01T09:12:00Z"},{"name":"Bill","id":2,"createdAt":"2020-10-02T17:45:00Z"}],"requestId":"f1c2a7"}'''
+    return Response(body, mimetype='application/json')
@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-json-matcher")
+def assert_json_matcher():
+    body = '''{"id":1234,"name":"Bob","createdAt":"2020-10-01T09:12:00Z","score":4.5,"roles":["admin","user"],"address":{"city":"Paris"}}'''
+    return Response(body, mimetype='application/json')
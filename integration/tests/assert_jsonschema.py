@@ -0,0 +1,12 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-jsonschema")
+def assert_jsonschema():
+    body = '''[
+  { "id": 1, "name": "Bob", "email": "bob@example.org" },
+  { "id": 2, "name": "Bill", "email": "bill@example.org" }
+]'''
+    return Response(body, mimetype='application/json')
+
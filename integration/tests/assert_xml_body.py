@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-xml-body")
+def assert_xml_body():
+    body = '''<?xml version="1.0" encoding="utf-8"?><ns0:order xmlns:ns0="http://example.org/order" status="paid" id="1"><ns0:item sku="A1"><![CDATA[Pen]]></ns0:item><!-- second item --><ns0:item sku="B2">Ink</ns0:item></ns0:order>'''
+    return Response(body, mimetype='application/xml')
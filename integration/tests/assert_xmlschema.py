@@ -0,0 +1,14 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-xmlschema")
+def assert_xmlschema():
+    body = '''<?xml version="1.0"?>
+<order>
+  <id>1</id>
+  <item>Coffee</item>
+  <item>Tea</item>
+  <total>7.50</total>
+</order>'''
+    return Response(body, mimetype='application/xml')
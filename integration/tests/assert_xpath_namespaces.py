@@ -0,0 +1,15 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-xpath-namespaces")
+def assert_xpath_namespaces():
+    body = '''<?xml version="1.0"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <GetStockPriceResponse xmlns="http://www.example.org/stock">
+      <Price>34.5</Price>
+    </GetStockPriceResponse>
+  </soap:Body>
+</soap:Envelope>'''
+    return Response(body, mimetype='application/xml')
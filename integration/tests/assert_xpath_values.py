@@ -0,0 +1,14 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/assert-xpath-values")
+def assert_xpath_values():
+    body = '''<html><body>
+<ul>
+  <li id="1">Apple</li>
+  <li id="2">Banana</li>
+  <li id="3">Cherry</li>
+</ul>
+</body></html>'''
+    return Response(body, mimetype='text/html')
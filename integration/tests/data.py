@@ -0,0 +1,5 @@
+from tests import app
+
+@app.route('/data/users/<int:id>')
+def data_get_user(id):
+    return '{"id": %d, "name": "%s"}' % (id, {1: 'bob', 2: 'Smith, Al'}[id])
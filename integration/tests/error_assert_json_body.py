@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/error-assert-json-body")
+def error_assert_json_body():
+    body = '''{"id":1,"name":"Bob","roles":["admin"],"updatedAt":"2020-10-01T09:12:00Z"}'''
+    return Response(body, mimetype='application/json')
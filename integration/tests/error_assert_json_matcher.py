@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/error-assert-json-matcher")
+def error_assert_json_matcher():
+    body = '''{"id":"1234","name":"Bob","createdAt":"01/10/2020","tags":[]}'''
+    return Response(body, mimetype='application/json')
@@ -0,0 +1,10 @@
+from tests import app
+from flask import Response
+
+@app.route("/error-assert-jsonschema")
+def error_assert_jsonschema():
+    body = '''[
+  { "id": 0, "name": "Bob", "email": "bob" },
+  { "id": 2, "email": "bill@example.org", "age": 42 }
+]'''
+    return Response(body, mimetype='application/json')
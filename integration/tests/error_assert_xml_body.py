@@ -0,0 +1,8 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/error-assert-xml-body")
+def error_assert_xml_body():
+    body = '''<order id="1"><item sku="A1">Pen</item><item sku="B3">Ink</item></order>'''
+    return Response(body, mimetype='application/xml')
@@ -0,0 +1,13 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/error-assert-xmlschema")
+def error_assert_xmlschema():
+    body = '''<?xml version="1.0"?>
+<order>
+  <id>one</id>
+  <item>Coffee</item>
+  <total>free</total>
+</order>'''
+    return Response(body, mimetype='application/xml')
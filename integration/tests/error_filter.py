@@ -0,0 +1,6 @@
+from tests import app
+
+
+@app.route("/error-filter")
+def error_filter():
+    return '{ "id": 123 }'
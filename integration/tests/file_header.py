@@ -0,0 +1,16 @@
+from flask import request
+from tests import app
+
+@app.route('/file-header')
+def file_header():
+    assert request.headers['Authorization'] == 'Bearer abc123'
+    assert request.headers['Accept'] == 'application/json'
+    assert request.args.get('lang') == 'en'
+    return ''
+
+@app.route('/file-header/bob')
+def file_header_override():
+    assert request.headers['Authorization'] == 'Bearer abc123'
+    assert request.headers['Accept'] == 'text/plain'
+    assert request.args.getlist('lang') == ['fr']
+    return ''
@@ -0,0 +1,17 @@
+from tests import app
+from flask import Response
+
+
+@app.route("/filters")
+def filters():
+    body = '''{
+  "books": ["Dune", "Neuromancer", "Hyperion"],
+  "ids": "1,22,333",
+  "nested": "{\\"id\\": 123}",
+  "html": "&lt;p&gt;Tom &amp; Jerry&lt;/p&gt;",
+  "price": "12.5",
+  "query": "a b&c"
+}'''
+    resp = Response(body, mimetype='application/json')
+    resp.headers['Location'] = 'http://localhost:8000/login?token=abc%20def&mode=full'
+    return resp
@@ -0,0 +1,13 @@
+from flask import request
+from tests import app
+
+@app.route('/include/login', methods=['POST'])
+def include_login():
+    assert request.form['user'] == 'bob'
+    assert request.form['password'] == 'secret'
+    return '{"token": "abc123"}'
+
+@app.route('/include/protected')
+def include_protected():
+    assert request.headers['Authorization'] == 'Bearer abc123'
+    return ''
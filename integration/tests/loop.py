@@ -0,0 +1,9 @@
+from tests import app
+
+@app.route('/loop/users')
+def loop_users():
+    return '{"users": [{"id": 1}, {"id": 2}, {"id": 3}]}'
+
+@app.route('/loop/users/<int:id>')
+def loop_get_user(id):
+    return '{"id": %d, "name": "%s"}' % (id, {1: 'bob', 2: 'alice', 3: 'eve'}[id])
@@ -0,0 +1,17 @@
+from flask import request
+from tests import app
+
+@app.route('/post-json-variables/user')
+def post_json_variables_user():
+    return '{"id": 12, "admin": true, "roles": ["read", "write"], "name": "Bob"}'
+
+@app.route('/post-json-variables', methods=['POST'])
+def post_json_variables():
+    assert request.json == {
+        'id': 12,
+        'admin': True,
+        'roles': ['read', 'write'],
+        'name': 'Bob',
+        'label': 'user Bob',
+    }
+    return ''
@@ -0,0 +1,36 @@
+from flask import request, Response
+from tests import app
+
+@app.route('/post-template/token')
+def post_template_token():
+    return Response('', headers={'X-Token': 'abc&123'})
+
+@app.route('/post-template/xml', methods=['POST'])
+def post_template_xml():
+    s = request.data.decode("utf-8")
+    assert s == '''<?xml version="1.0"?>
+<order><token>abc&amp;123</token></order>'''
+    return ''
+
+@app.route('/post-template/xml-literal', methods=['POST'])
+def post_template_xml_literal():
+    s = request.data.decode("utf-8")
+    assert s == '''<?xml version="1.0"?>
+<order><token>{{token}}</token></order>'''
+    return ''
+
+@app.route('/post-template/file', methods=['POST'])
+def post_template_file():
+    s = request.data.decode("utf-8")
+    assert s == '''{
+  "token": "abc&123",
+  "items": [1, 2, 3]
+}
+'''
+    return ''
+
+@app.route('/post-template/xml-file', methods=['POST'])
+def post_template_xml_file():
+    s = request.data.decode("utf-8")
+    assert s == '<order><token>abc&amp;123</token></order>\n'
+    return ''
@@ -0,0 +1,10 @@
+from tests import app
+
+
+@app.route("/regex-flags")
+def regex_flags():
+    return '''Hello World!
+line 2
+id=1 id=22 id=333
+/path/users
+name: John Doe'''
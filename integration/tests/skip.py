@@ -0,0 +1,14 @@
+from tests import app
+
+@app.route('/skip/users')
+def skip_users():
+    return '{"id": 1}'
+
+@app.route('/skip/users/<int:id>', methods=['DELETE'])
+def skip_delete_user(id):
+    assert id == 1
+    return ''
+
+@app.route('/skip/feature')
+def skip_feature():
+    return 'Hello'
@@ -0,0 +1,15 @@
+from flask import request, Response
+from tests import app
+
+@app.route('/tags/login', methods=['POST'])
+def tags_login():
+    return Response('', headers={'token': 'abc'})
+
+@app.route('/tags/users')
+def tags_users():
+    assert request.headers['Authorization'] == 'abc'
+    return '[]'
+
+@app.route('/tags/health')
+def tags_health():
+    return 'OK'
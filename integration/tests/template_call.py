@@ -0,0 +1,13 @@
+from flask import request
+from tests import app
+
+@app.route('/template/users', methods=['POST'])
+def template_create_user():
+    user = request.get_json()
+    assert user['role'] == {'bob': 'admin', 'alice': 'user'}[user['name']]
+    return '{"id": %d}' % {'bob': 1, 'alice': 2}[user['name']], 201
+
+@app.route('/template/users/<int:id>')
+def template_get_user(id):
+    assert id == 1
+    return ''
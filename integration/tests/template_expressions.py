@@ -0,0 +1,20 @@
+import re
+from datetime import datetime
+from flask import request
+from tests import app
+
+@app.route('/template-expressions/token')
+def template_expressions_token():
+    return '{"counter": 10, "token": "Hello"}'
+
+@app.route('/template-expressions', methods=['POST'])
+def template_expressions():
+    assert request.args.get('year') == str(datetime.utcnow().year)
+    assert re.match(r'^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$', request.headers['X-Request-Id'])
+    assert request.headers['X-Token'] == 'SGVsbG8='
+    body = request.json
+    assert body['next'] == 11
+    assert body['previous'] == '9'
+    assert body['name'] == 'ANONYMOUS'
+    assert 1 <= body['dice'] <= 6
+    return ''
@@ -22,13 +22,14 @@ use std::fs;
 use std::io::{self, Read};
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use atty::Stream;
 use chrono::{DateTime, Local};
 use clap::{AppSettings, ArgMatches};
 
 use hurl::cli;
-use hurl::core::common::FormatError;
+use hurl::core::common::{FormatError, Value};
 use hurl::html;
 use hurl::http;
 use hurl::parser;
@@ -44,13 +45,17 @@ pub struct CLIOptions {
     pub color: bool,
     pub fail_fast: bool,
     pub insecure: bool,
-    pub variables: HashMap<String, String>,
+    pub variables: HashMap<String, Value>,
+    pub namespaces: HashMap<String, String>,
     pub to_entry: Option<usize>,
+    pub seed: Option<u64>,
+    pub entry_filter: Option<EntryFilter>,
     pub redirect: http::client::Redirect,
     pub http_proxy: Option<String>,
     pub https_proxy: Option<String>,
     pub all_proxy: Option<String>,
     pub noproxy_hosts: Vec<String>,
+    pub timeout: Option<Duration>,
 }
 
 
@@ -61,9 +66,7 @@ fn execute(filename: &str,
            cookies: Vec<http::cookie::Cookie>,
            cli_options: CLIOptions,
            logger: format::logger::Logger,
-) -> HurlResult {
-    let mut cookiejar = http::cookie::CookieJar::init(cookies);
-
+) -> Vec<HurlResult> {
     match parser::parse_hurl_file(contents.as_str()) {
         Err(e) => {
             let error = hurl::format::error::Error {
@@ -81,6 +84,9 @@ fn execute(filename: &str,
         Ok(hurl_file) => {
             logger.verbose(format!("Fail fast: {}", cli_options.fail_fast).as_str());
             logger.verbose(format!("variables: {:?}", cli_options.variables).as_str());
+            if !cli_options.namespaces.is_empty() {
+                logger.verbose(format!("namespaces: {:?}", cli_options.namespaces).as_str());
+            }
             if let Some(proxy) = cli_options.http_proxy.clone() {
                 logger.verbose(format!("http_proxy: {}", proxy).as_str());
             }
@@ -93,6 +99,9 @@ fn execute(filename: &str,
             if !cli_options.noproxy_hosts.is_empty() {
                 logger.verbose(format!("noproxy: {}", cli_options.noproxy_hosts.join(", ")).as_str());
             }
+            if let Some(timeout) = cli_options.timeout {
+                logger.verbose(format!("max time: {}s", timeout.as_secs()).as_str());
+            }
 
             match cli_options.redirect {
                 http::client::Redirect::None {} => {}
@@ -108,16 +117,13 @@ fn execute(filename: &str,
                 }
             }
 
-            let noproxy_hosts = cli_options.noproxy_hosts.clone();
-            let redirect = cli_options.redirect.clone();
-            let client = http::client::Client::init(http::client::ClientOptions {
-                noproxy_hosts,
-                insecure: cli_options.insecure,
-                redirect,
-                http_proxy: cli_options.http_proxy.clone(),
-                https_proxy: cli_options.https_proxy.clone(),
-                all_proxy: cli_options.all_proxy.clone(),
-            });
+            if let Some(entry_filter) = cli_options.entry_filter.clone() {
+                logger.verbose(format!("entry filter: {:?}", entry_filter).as_str());
+                if !runner::file::is_selected(&hurl_file, &entry_filter) {
+                    logger.verbose("no entry selected");
+                    return vec![];
+                }
+            }
 
             let context_dir = match file_root {
                 None => {
@@ -134,37 +140,102 @@ fn execute(filename: &str,
                 }
             };
 
-            let options = RunnerOptions {
-                fail_fast: cli_options.fail_fast,
-                variables: cli_options.variables,
-                to_entry: cli_options.to_entry,
+            // the whole file is run once per row of its data file
+            let rows = match runner::file::dataset(&hurl_file, &cli_options.variables, context_dir.as_str()) {
+                Ok(None) => vec![None],
+                Ok(Some(rows)) => rows.into_iter().map(Some).collect(),
+                Err(e) => {
+                    let error = hurl::format::error::Error {
+                        source_info: e.source_info(),
+                        description: e.description(),
+                        fixme: e.fixme(),
+                        lines: vec![],
+                        filename: "".to_string(),
+                        warning: false,
+                        color: logger.color,
+                    };
+                    logger.error(&error);
+                    return vec![HurlResult {
+                        filename: filename.to_string(),
+                        entries: vec![EntryResult {
+                            request: None,
+                            response: None,
+                            captures: vec![],
+                            asserts: vec![],
+                            errors: vec![e],
+                            time_in_ms: 0,
+                            iteration: None,
+                            skipped: false,
+                            filename: None,
+                        }],
+                        time_in_ms: 0,
+                        success: false,
+                        cookies: vec![],
+                        iteration: None,
+                    }];
+                }
             };
-            runner::file::run(hurl_file,
-                              client,
-                              filename.to_string(),
-                              &mut cookiejar,
-                              context_dir,
-                              options,
-                              logger,
-            )
+
+            let mut hurl_results = vec![];
+            for (i, row) in rows.into_iter().enumerate() {
+                let iteration = row.as_ref().map(|_| i + 1);
+                let mut variables = cli_options.variables.clone();
+                if let Some(row) = row {
+                    logger.verbose(format!("data row {}: {:?}", i + 1, row).as_str());
+                    variables.extend(row);
+                }
+                let client = http::client::Client::init(http::client::ClientOptions {
+                    noproxy_hosts: cli_options.noproxy_hosts.clone(),
+                    insecure: cli_options.insecure,
+                    redirect: cli_options.redirect.clone(),
+                    http_proxy: cli_options.http_proxy.clone(),
+                    https_proxy: cli_options.https_proxy.clone(),
+                    all_proxy: cli_options.all_proxy.clone(),
+                    timeout: cli_options.timeout,
+                });
+                let mut cookiejar = http::cookie::CookieJar::init(cookies.clone());
+                let options = RunnerOptions {
+                    fail_fast: cli_options.fail_fast,
+                    variables,
+                    namespaces: cli_options.namespaces.clone(),
+                    to_entry: cli_options.to_entry,
+                    seed: cli_options.seed,
+                    entry_filter: cli_options.entry_filter.clone(),
+                };
+                let hurl_result = runner::file::run(hurl_file.clone(),
+                                                    client,
+                                                    filename.to_string(),
+                                                    &mut cookiejar,
+                                                    context_dir.clone(),
+                                                    options,
+                                                    logger.clone(),
+                );
+                hurl_results.push(HurlResult { iteration, ..hurl_result });
+                if cli_options.fail_fast && !hurl_results.iter().all(|r| r.success) {
+                    break;
+                }
+            }
+            hurl_results
         }
     }
 }
 
 
-fn output_color(matches: ArgMatches) -> bool {
+fn output_color(matches: ArgMatches, config_options: &cli::config::ConfigOptions) -> bool {
     if matches.is_present("color") {
         true
     } else if matches.is_present("no_color") {
         false
+    } else if let Some(color) = config_options.color {
+        color
     } else {
         atty::is(Stream::Stdout)
     }
 }
 
 
-fn noproxy_host(matches: ArgMatches) -> Vec<String> {
-    match matches.value_of("noproxy") {
+fn noproxy_host(matches: ArgMatches, config_options: &cli::config::ConfigOptions) -> Vec<String> {
+    match matches.value_of("noproxy").or(config_options.noproxy.as_deref()) {
         Some(value) => {
             value.split(',').map(|e| e.trim().to_string()).collect()
         }
@@ -191,12 +262,100 @@ fn to_entry(matches: ArgMatches, logger: format::logger::Logger) -> Option<usize
     }
 }
 
+fn entry_filter(matches: ArgMatches, config_options: &cli::config::ConfigOptions) -> Option<EntryFilter> {
+    let values = |name: &str| match matches.values_of(name) {
+        None => vec![],
+        Some(values) => values.map(|value| value.to_string()).collect(),
+    };
+    let entry_filter = EntryFilter {
+        names: values("name"),
+        tags: values("tag"),
+        exclude_tags: values("exclude_tag"),
+        with_dependencies: matches.is_present("with_dependencies") || config_options.with_dependencies.unwrap_or(false),
+    };
+    if entry_filter == EntryFilter::default() {
+        None
+    } else {
+        Some(entry_filter)
+    }
+}
+
+fn max_time(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> Option<Duration> {
+    match matches.value_of("max_time") {
+        Some(value) => {
+            match value.parse() {
+                Ok(v) => Some(Duration::from_secs(v)),
+                Err(_) => {
+                    logger.error_message("Invalid value for option --max-time - must be a positive integer!".to_string());
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => config_options.max_time.map(Duration::from_secs),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
 
-fn json_file(matches: ArgMatches, logger: format::logger::Logger) -> (Vec<HurlResult>, Option<std::path::PathBuf>) {
-    if let Some(filename) = matches.value_of("json") {
+fn config_options(matches: ArgMatches, current_dir: &Path, logger: format::logger::Logger) -> (Vec<PathBuf>, cli::config::ConfigOptions) {
+    let config_files = if matches.is_present("no_config") {
+        vec![]
+    } else {
+        cli::config::config_files(current_dir, home_dir().as_deref())
+    };
+    let environment = matches.value_of("env");
+    if config_files.is_empty() {
+        if let Some(name) = environment {
+            logger.error_message(format!("No config file {} found, environment {} can not be selected", cli::config::CONFIG_FILE, name));
+            std::process::exit(1);
+        }
+    }
+
+    let mut config = cli::config::Config::default();
+    for path in config_files.clone() {
+        match cli::config::read(path.as_path()) {
+            Ok(file_config) => config.merge(file_config),
+            Err(e) => {
+                logger.error_message(e.message);
+                std::process::exit(1);
+            }
+        }
+    }
+    match config.options(environment) {
+        Ok(options) => (config_files, options),
+        Err(e) => {
+            logger.error_message(e.message);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn seed(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> Option<u64> {
+    match matches.value_of("seed") {
+        Some(value) => {
+            match value.parse() {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    logger.error_message("Invalid value for option --seed - must be a positive integer!".to_string());
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => config_options.seed,
+    }
+}
+
+
+fn json_file(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> (Vec<HurlResult>, Option<std::path::PathBuf>) {
+    if let Some(filename) = matches.value_of("json").or(config_options.json.as_deref()) {
         let path = Path::new(filename);
+        let append = matches.is_present("append") || config_options.append.unwrap_or(false);
 
-        let results = if matches.is_present("append") && std::path::Path::new(&path).exists() {
+        let results = if append && std::path::Path::new(&path).exists() {
             logger.verbose(format!("Appending session to {}", path.display()).as_str());
 
             let data = fs::read_to_string(path).unwrap();
@@ -215,7 +374,7 @@ fn json_file(matches: ArgMatches, logger: format::logger::Logger) -> (Vec<HurlRe
                 Ok(results) => results
             }
         } else {
-            if matches.is_present("verbose") {
+            if logger.verbose {
                 logger.error_message(format!("* Writing session to {}", path.display()));
             }
             vec![]
@@ -227,8 +386,8 @@ fn json_file(matches: ArgMatches, logger: format::logger::Logger) -> (Vec<HurlRe
 }
 
 
-fn html_report(matches: ArgMatches, logger: format::logger::Logger) -> Option<std::path::PathBuf> {
-    if let Some(dir) = matches.value_of("html_report") {
+fn html_report(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> Option<std::path::PathBuf> {
+    if let Some(dir) = matches.value_of("html_report").or(config_options.html.as_deref()) {
         let path = Path::new(dir);
         if std::path::Path::new(&path).exists() {
             if !path.read_dir().map(|mut i| i.next().is_none()).unwrap_or(false) {
@@ -251,8 +410,34 @@ fn html_report(matches: ArgMatches, logger: format::logger::Logger) -> Option<st
 }
 
 
-fn variables(matches: ArgMatches, logger: format::logger::Logger) -> HashMap<String, String> {
+// Variables are defined, by increasing precedence, with the config files (and their --env environment),
+// HURL_ environment variables, --variables-file (later files override earlier ones), --variable and --variable-json.
+// Only the values of json sources are typed, the other values are strings.
+fn variables(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> HashMap<String, Value> {
     let mut variables = HashMap::new();
+    for (name, value) in config_options.variables.clone() {
+        variables.insert(name, value);
+    }
+    for (name, value) in cli::options::env_variables(env::vars().collect()) {
+        variables.insert(name, Value::String(value));
+    }
+    let variables_files: Vec<&str> = match matches.values_of("variables_file") {
+        Some(filenames) => filenames.collect(),
+        None => config_options.variables_file.iter().map(|filename| filename.as_str()).collect(),
+    };
+    for filename in variables_files {
+        let file_variables = match cli::options::variables_file(filename) {
+            Ok(file_variables) => file_variables,
+            Err(e) => {
+                logger.error_message(e.message);
+                std::process::exit(1);
+            }
+        };
+        for (name, value) in file_variables {
+            variables.insert(name, value);
+        }
+    }
+    let mut option_variables = HashMap::new();
     if matches.is_present("variable") {
         let input: Vec<_> = matches.values_of("variable").unwrap().collect();
         for s in input {
@@ -263,18 +448,61 @@ fn variables(matches: ArgMatches, logger: format::logger::Logger) -> HashMap<Str
                 }
                 Some(index) => {
                     let (name, value) = s.split_at(index);
-                    if variables.contains_key(name) {
+                    if option_variables.contains_key(name) {
+                        logger.error_message(format!("Variable {} defined twice!", name));
                         std::process::exit(1);
                     }
-                    variables.insert(name.to_string(), value[1..].to_string());
+                    option_variables.insert(name.to_string(), Value::String(value[1..].to_string()));
                 }
             };
         }
     }
+    if matches.is_present("variable_json") {
+        for s in matches.values_of("variable_json").unwrap() {
+            let (name, value) = match cli::options::json_variable(s) {
+                Ok(variable) => variable,
+                Err(e) => {
+                    logger.error_message(e.message);
+                    std::process::exit(1);
+                }
+            };
+            if option_variables.contains_key(&name) {
+                logger.error_message(format!("Variable {} defined twice!", name));
+                std::process::exit(1);
+            }
+            option_variables.insert(name, value);
+        }
+    }
+    variables.extend(option_variables);
     variables
 }
 
 
+fn namespaces(matches: ArgMatches, logger: format::logger::Logger) -> HashMap<String, String> {
+    let mut namespaces = HashMap::new();
+    if matches.is_present("namespace") {
+        let input: Vec<_> = matches.values_of("namespace").unwrap().collect();
+        for s in input {
+            match s.find('=') {
+                None => {
+                    logger.error_message(format!("Missing namespace uri for {}!", s));
+                    std::process::exit(1);
+                }
+                Some(index) => {
+                    let (prefix, uri) = s.split_at(index);
+                    if namespaces.contains_key(prefix) {
+                        logger.error_message(format!("Namespace {} is already defined!", prefix));
+                        std::process::exit(1);
+                    }
+                    namespaces.insert(prefix.to_string(), uri[1..].to_string());
+                }
+            };
+        }
+    }
+    namespaces
+}
+
+
 fn app() -> clap::App<'static, 'static> {
     clap::App::new("hurl")
 //.author(clap::crate_authors!())
@@ -313,6 +541,20 @@ fn app() -> clap::App<'static, 'static> {
                 .value_name("FILE")
                 .help("Write cookies to FILE after running the session (only for one session)")
         )
+        .arg(clap::Arg::with_name("env")
+            .long("env")
+            .value_name("NAME")
+            .help("Use the environment NAME of the config files hurl.toml")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("exclude_tag")
+            .long("exclude-tag")
+            .value_name("TAG")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Do not execute the entries with TAG, even as dependencies")
+            .takes_value(true)
+        )
         .arg(clap::Arg::with_name("fail_at_end")
             .long("fail-at-end")
             .help("Fail at end")
@@ -365,12 +607,38 @@ fn app() -> clap::App<'static, 'static> {
                 .allow_hyphen_values(true)
                 .help("Maximum number of redirects allowed"),
         )
+        .arg(
+            clap::Arg::with_name("max_time")
+                .long("max-time")
+                .value_name("SECONDS")
+                .help("Maximum time allowed for each request"),
+        )
+        .arg(clap::Arg::with_name("name")
+            .long("name")
+            .value_name("PATTERN")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Execute only the entries with a name matching PATTERN")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("namespace")
+            .long("namespace")
+            .value_name("PREFIX=URI")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Define a namespace prefix for xpath queries")
+            .takes_value(true)
+        )
         .arg(
             clap::Arg::with_name("no_color")
                 .long("no-color")
                 .conflicts_with("color")
                 .help("Do not colorize Output"),
         )
+        .arg(clap::Arg::with_name("no_config")
+            .long("no-config")
+            .help("Do not read the config files hurl.toml")
+        )
         .arg(clap::Arg::with_name("noproxy")
             .long("noproxy")
             .value_name("HOST(S)")
@@ -391,6 +659,20 @@ fn app() -> clap::App<'static, 'static> {
                 .value_name("[PROTOCOL://]HOST[:PORT]")
                 .help("Use proxy on given protocol/host/port"),
         )
+        .arg(clap::Arg::with_name("seed")
+            .long("seed")
+            .value_name("NUMBER")
+            .help("Seed the random values of templates for reproducible runs")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("tag")
+            .long("tag")
+            .value_name("TAG")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Execute only the entries with TAG")
+            .takes_value(true)
+        )
         .arg(clap::Arg::with_name("to_entry")
             .long("to-entry")
             .value_name("ENTRY_NUMBER")
@@ -405,12 +687,33 @@ fn app() -> clap::App<'static, 'static> {
             .help("Define a variable")
             .takes_value(true)
         )
+        .arg(clap::Arg::with_name("variable_json")
+            .long("variable-json")
+            .value_name("NAME=JSON")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Define a variable typed by its json value")
+            .takes_value(true)
+        )
+        .arg(clap::Arg::with_name("variables_file")
+            .long("variables-file")
+            .value_name("FILE")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Define variables from a .env/properties or json FILE")
+            .takes_value(true)
+        )
         .arg(
             clap::Arg::with_name("verbose")
                 .short("v")
                 .long("verbose")
                 .help("Turn on verbose output"),
         )
+        .arg(clap::Arg::with_name("with_dependencies")
+            .long("with-dependencies")
+            .help("Also execute the entries whose captures are used by the selected entries, except excluded ones")
+            .takes_value(false)
+        )
 }
 
 
@@ -425,30 +728,44 @@ pub fn unwrap_or_exit<T>(result: Result<T, cli::Error>, logger: format::logger::
 }
 
 
-fn parse_options(matches: ArgMatches, logger: format::logger::Logger) -> Result<CLIOptions, cli::Error> {
-    let verbose = matches.is_present("verbose");
-    let color = output_color(matches.clone());
-    let fail_fast = !matches.is_present("fail_at_end");
-    let variables = variables(matches.clone(), logger.clone());
-    let to_entry = to_entry(matches.clone(), logger);
-    let redirect = cli::options::redirect(matches.is_present("redirect"), matches.value_of("max_redirects").unwrap_or_default())?;
-    let http_proxy = cli::options::proxy(matches.value_of("proxy"), env::var("http_proxy").ok())?;
-    let https_proxy = cli::options::proxy(matches.value_of("proxy"), env::var("https_proxy").ok())?;
-    let all_proxy = cli::options::proxy(matches.value_of("proxy"), env::var("all_proxy").ok())?;
-    let noproxy_hosts = noproxy_host(matches.clone());
-    let insecure = matches.is_present("insecure");
+fn parse_options(matches: ArgMatches, config_options: &cli::config::ConfigOptions, logger: format::logger::Logger) -> Result<CLIOptions, cli::Error> {
+    let verbose = matches.is_present("verbose") || config_options.verbose.unwrap_or(false);
+    let color = output_color(matches.clone(), config_options);
+    let fail_fast = !(matches.is_present("fail_at_end") || config_options.fail_at_end.unwrap_or(false));
+    let variables = variables(matches.clone(), config_options, logger.clone());
+    let namespaces = namespaces(matches.clone(), logger.clone());
+    let to_entry = to_entry(matches.clone(), logger.clone());
+    let seed = seed(matches.clone(), config_options, logger.clone());
+    let entry_filter = entry_filter(matches.clone(), config_options);
+    let timeout = max_time(matches.clone(), config_options, logger);
+    let location = matches.is_present("redirect") || config_options.location.unwrap_or(false);
+    let max_redirs = match config_options.max_redirs {
+        Some(n) if matches.occurrences_of("max_redirects") == 0 => n.to_string(),
+        _ => matches.value_of("max_redirects").unwrap_or_default().to_string(),
+    };
+    let redirect = cli::options::redirect(location, max_redirs.as_str())?;
+    let proxy = matches.value_of("proxy").or(config_options.proxy.as_deref());
+    let http_proxy = cli::options::proxy(proxy, env::var("http_proxy").ok())?;
+    let https_proxy = cli::options::proxy(proxy, env::var("https_proxy").ok())?;
+    let all_proxy = cli::options::proxy(proxy, env::var("all_proxy").ok())?;
+    let noproxy_hosts = noproxy_host(matches.clone(), config_options);
+    let insecure = matches.is_present("insecure") || config_options.insecure.unwrap_or(false);
     Ok(CLIOptions {
         verbose,
         color,
         fail_fast,
         insecure,
         variables,
+        namespaces,
         to_entry,
+        seed,
+        entry_filter,
         redirect,
         http_proxy,
         https_proxy,
         all_proxy,
         noproxy_hosts,
+        timeout,
     })
 }
 
@@ -473,28 +790,39 @@ fn main() -> Result<(), cli::Error> {
     let current_dir_buf = std::env::current_dir().unwrap();
     let current_dir = current_dir_buf.as_path();
 
-    let file_root = match matches.value_of("file_root") {
-        Some(value) => Some(value.to_string()),
-        _ => None
-    };
-
     let logger = format::logger::Logger {
         filename: None,
         lines: vec![],
         verbose: matches.is_present("verbose"),
-        color: output_color(matches.clone()),
+        color: output_color(matches.clone(), &cli::config::ConfigOptions::default()),
+    };
+    let (config_files, config_options) = config_options(matches.clone(), current_dir, logger);
+    let logger = format::logger::Logger {
+        filename: None,
+        lines: vec![],
+        verbose: matches.is_present("verbose") || config_options.verbose.unwrap_or(false),
+        color: output_color(matches.clone(), &config_options),
+    };
+    if !config_files.is_empty() {
+        let config_files: Vec<String> = config_files.iter().map(|path| path.display().to_string()).collect();
+        logger.verbose(format!("config files: {}", config_files.join(", ")).as_str());
+    }
+
+    let file_root = match matches.value_of("file_root") {
+        Some(value) => Some(value.to_string()),
+        _ => config_options.file_root.clone()
     };
 
-    let cookies = match matches.value_of("cookies_input_file") {
+    let cookies = match matches.value_of("cookies_input_file").or(config_options.cookie.as_deref()) {
         Some(filename) => unwrap_or_exit(cli::options::cookies(filename), logger.clone()),
         None => vec![],
     };
 
 
 
-    let (mut hurl_results, json_file) = json_file(matches.clone(), logger.clone());
-    let html_report = html_report(matches.clone(), logger.clone());
-    let cookies_output_file = match matches.value_of("cookies_output_file") {
+    let (mut hurl_results, json_file) = json_file(matches.clone(), &config_options, logger.clone());
+    let html_report = html_report(matches.clone(), &config_options, logger.clone());
+    let cookies_output_file = match matches.value_of("cookies_output_file").or(config_options.cookie_jar.as_deref()) {
         None => None,
         Some(filename) => {
             let filename = unwrap_or_exit(cli::options::cookies_output_file(filename.to_string(), filenames.len()), logger.clone());
@@ -502,7 +830,7 @@ fn main() -> Result<(), cli::Error> {
         }
     };
 
-    let cli_options = unwrap_or_exit(parse_options(matches.clone(), logger.clone()), logger.clone());
+    let cli_options = unwrap_or_exit(parse_options(matches.clone(), &config_options, logger.clone()), logger.clone());
 
     for filename in filenames {
         let contents = if filename == "-" {
@@ -531,7 +859,7 @@ fn main() -> Result<(), cli::Error> {
         };
 
 
-        let hurl_result = execute(
+        let results = execute(
             filename,
             contents,
             current_dir,
@@ -540,13 +868,24 @@ fn main() -> Result<(), cli::Error> {
             cli_options.clone(),
             logger.clone(),
         );
+        let hurl_result = match results.last() {
+            None => continue,
+            Some(hurl_result) => hurl_result.clone(),
+        };
+        for result in results.iter() {
+            let entries = result.entries.iter();
+            let skipped = entries.clone().filter(|e| e.skipped).count();
+            let failed = entries.clone().filter(|e| !e.errors.is_empty()).count();
+            let succeeded = result.entries.len() - skipped - failed;
+            logger.summary(succeeded, failed, skipped);
+        }
 
         if hurl_result.errors().is_empty() {
             // default
-            // last entry + response + body
-            if let Some(entry_result) = hurl_result.entries.last() {
+            // last entry run + response + body
+            if let Some(entry_result) = hurl_result.entries.iter().rev().find(|e| !e.skipped) {
                 if let Some(response) = entry_result.response.clone() {
-                    if matches.is_present("include") {
+                    if matches.is_present("include") || config_options.include.unwrap_or(false) {
                         logger.info(format!("HTTP/{} {}", response.version.to_string(), response.status.to_string()).as_str());
                         for header in response.headers.clone() {
                             logger.info(format!("{}: {}", header.name, header.value).as_str());
@@ -554,7 +893,7 @@ fn main() -> Result<(), cli::Error> {
                         logger.info("");
                     }
 
-                    write_output(response.body, matches.value_of("output"), logger.clone());
+                    write_output(response.body, matches.value_of("output").or(config_options.output.as_deref()), logger.clone());
                 } else {
                     logger.warning_message("no response has been received".to_string());
                 }
@@ -564,7 +903,7 @@ fn main() -> Result<(), cli::Error> {
         }
 
 
-        hurl_results.push(hurl_result.clone());
+        hurl_results.extend(results);
     }
 
 
@@ -777,7 +1116,7 @@ fn create_html_table_header() -> html::ast::Element {
 fn create_html_table_body(hurl_results: Vec<HurlResult>) -> html::ast::Element {
     let children = hurl_results
         .iter()
-        .map(|result| create_html_result(result.clone()))
+        .flat_map(|result| create_html_result(result.clone()))
         .collect();
 
     html::ast::Element::NodeElement {
@@ -788,12 +1127,43 @@ fn create_html_table_body(hurl_results: Vec<HurlResult>) -> html::ast::Element {
 }
 
 
-fn create_html_result(result: HurlResult) -> html::ast::Element {
-    let status = if result.success {
+// one row for the file, followed by one row per iteration of its data-driven entries
+// and one row per skipped entry
+fn create_html_result(result: HurlResult) -> Vec<html::ast::Element> {
+    let name = match result.iteration {
+        None => result.filename.clone(),
+        Some(iteration) => format!("{} [{}]", result.filename, iteration),
+    };
+    let mut rows = vec![create_html_row(name, status(result.success), result.time_in_ms)];
+    for (index, entry_result) in result.entries.iter().enumerate() {
+        let iteration = match entry_result.iteration {
+            None => "".to_string(),
+            Some(iteration) => format!(" [{}]", iteration),
+        };
+        if entry_result.skipped {
+            let name = format!("{} entry {}{}", result.filename, index + 1, iteration);
+            rows.push(create_html_row(name, "skipped".to_string(), 0));
+        } else if entry_result.iteration.is_some() {
+            let url = match entry_result.request.clone() {
+                None => "".to_string(),
+                Some(request) => request.url(),
+            };
+            let name = format!("{} {}{}", result.filename, url, iteration);
+            rows.push(create_html_row(name, status(entry_result.errors.is_empty()), entry_result.time_in_ms));
+        }
+    }
+    rows
+}
+
+fn status(success: bool) -> String {
+    if success {
         "success".to_string()
     } else {
         "failure".to_string()
-    };
+    }
+}
+
+fn create_html_row(name: String, status: String, time_in_ms: u128) -> html::ast::Element {
     html::ast::Element::NodeElement {
         name: "tr".to_string(),
         attributes: vec![],
@@ -804,14 +1174,14 @@ fn create_html_result(result: HurlResult) -> html::ast::Element {
                     html::ast::Attribute::Class(status)
                 ],
                 children: vec![
-                    html::ast::Element::TextElement(result.filename.clone())
+                    html::ast::Element::TextElement(name)
                 ],
             },
             html::ast::Element::NodeElement {
                 name: "td".to_string(),
                 attributes: vec![],
                 children: vec![
-                    html::ast::Element::TextElement(format!("{}s", result.time_in_ms as f64 / 1000.0))
+                    html::ast::Element::TextElement(format!("{}s", time_in_ms as f64 / 1000.0))
                 ],
             }
         ],
@@ -0,0 +1,474 @@
+/*
+ * hurl (https://hurl.dev)
+ * Copyright (C) 2020 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::common::Value;
+
+use super::Error;
+
+///
+/// Project config file (hurl.toml).
+///
+/// Only a subset of toml is supported: tables, and keys with string, integer, float or boolean values.
+/// Top-level keys and the [variables] table define default options,
+/// environments override them when selected with --env.
+///
+/// ```toml
+/// color = true
+/// location = true
+///
+/// [variables]
+/// host = "http://localhost:8000"
+///
+/// [environments.staging]
+/// proxy = "proxy.example.com:3128"
+/// insecure = true
+/// max-time = 10
+///
+/// [environments.staging.variables]
+/// host = "https://staging.example.com"
+/// ```
+///
+pub const CONFIG_FILE: &str = "hurl.toml";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub defaults: ConfigOptions,
+    pub environments: HashMap<String, ConfigOptions>,
+}
+
+///
+/// Options defined in the config file.
+/// They are only used for options not given on the command line.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigOptions {
+    // values keep their type
+    pub variables: Vec<(String, Value)>,
+    pub append: Option<bool>,
+    pub color: Option<bool>,
+    pub cookie: Option<String>,
+    pub cookie_jar: Option<String>,
+    pub fail_at_end: Option<bool>,
+    pub file_root: Option<String>,
+    pub html: Option<String>,
+    pub include: Option<bool>,
+    pub insecure: Option<bool>,
+    pub json: Option<String>,
+    pub location: Option<bool>,
+    pub max_redirs: Option<i64>,
+    pub max_time: Option<u64>,
+    pub noproxy: Option<String>,
+    pub output: Option<String>,
+    pub proxy: Option<String>,
+    pub seed: Option<u64>,
+    pub variables_file: Option<String>,
+    pub verbose: Option<bool>,
+    pub with_dependencies: Option<bool>,
+}
+
+impl ConfigOptions {
+    ///
+    /// Override these options with the options defined in other.
+    ///
+    pub fn merge(&mut self, other: ConfigOptions) {
+        self.variables.extend(other.variables);
+        self.append = other.append.or(self.append);
+        self.color = other.color.or(self.color);
+        self.cookie = other.cookie.or_else(|| self.cookie.take());
+        self.cookie_jar = other.cookie_jar.or_else(|| self.cookie_jar.take());
+        self.fail_at_end = other.fail_at_end.or(self.fail_at_end);
+        self.file_root = other.file_root.or_else(|| self.file_root.take());
+        self.html = other.html.or_else(|| self.html.take());
+        self.include = other.include.or(self.include);
+        self.insecure = other.insecure.or(self.insecure);
+        self.json = other.json.or_else(|| self.json.take());
+        self.location = other.location.or(self.location);
+        self.max_redirs = other.max_redirs.or(self.max_redirs);
+        self.max_time = other.max_time.or(self.max_time);
+        self.noproxy = other.noproxy.or_else(|| self.noproxy.take());
+        self.output = other.output.or_else(|| self.output.take());
+        self.proxy = other.proxy.or_else(|| self.proxy.take());
+        self.seed = other.seed.or(self.seed);
+        self.variables_file = other.variables_file.or_else(|| self.variables_file.take());
+        self.verbose = other.verbose.or(self.verbose);
+        self.with_dependencies = other.with_dependencies.or(self.with_dependencies);
+    }
+
+    // paths are relative to the directory of the config file
+    fn resolve_paths(&mut self, dir: &Path) {
+        let paths = self.cookie.iter_mut()
+            .chain(self.cookie_jar.iter_mut())
+            .chain(self.file_root.iter_mut())
+            .chain(self.html.iter_mut())
+            .chain(self.json.iter_mut())
+            .chain(self.output.iter_mut())
+            .chain(self.variables_file.iter_mut());
+        for path in paths {
+            *path = dir.join(path.as_str()).to_string_lossy().to_string();
+        }
+    }
+}
+
+impl Config {
+    ///
+    /// Override this config with the config other (from a file nearer the working directory).
+    ///
+    pub fn merge(&mut self, other: Config) {
+        self.defaults.merge(other.defaults);
+        for (name, options) in other.environments {
+            self.environments.entry(name).or_default().merge(options);
+        }
+    }
+
+    ///
+    /// Return the default options, overridden by the environment name if any.
+    ///
+    pub fn options(&self, environment: Option<&str>) -> Result<ConfigOptions, Error> {
+        let mut options = self.defaults.clone();
+        if let Some(name) = environment {
+            match self.environments.get(name) {
+                Some(environment_options) => options.merge(environment_options.clone()),
+                None => return Err(Error {
+                    message: format!("Environment {} is not defined in config file {}", name, CONFIG_FILE)
+                })
+            }
+        }
+        Ok(options)
+    }
+}
+
+///
+/// Config files, by increasing precedence:
+/// the one of the home directory, then the ones from the root directory down to the current directory.
+///
+pub fn config_files(current_dir: &Path, home_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<&Path> = current_dir.ancestors().collect();
+    if let Some(home_dir) = home_dir {
+        if !dirs.contains(&home_dir) {
+            dirs.push(home_dir);
+        }
+    }
+    dirs.iter()
+        .rev()
+        .map(|dir| dir.join(CONFIG_FILE))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+pub fn read(path: &Path) -> Result<Config, Error> {
+    let filename = path.display().to_string();
+    match fs::read_to_string(path) {
+        Ok(s) => {
+            let mut config = parse(filename.as_str(), s.as_str())?;
+            if let Some(dir) = path.parent() {
+                config.defaults.resolve_paths(dir);
+                for options in config.environments.values_mut() {
+                    options.resolve_paths(dir);
+                }
+            }
+            Ok(config)
+        }
+        Err(_) => Err(Error {
+            message: format!("Config file {} can not be read", filename)
+        })
+    }
+}
+
+enum Table {
+    None,
+    Variables,
+    Environment(String),
+    EnvironmentVariables(String),
+}
+
+pub fn parse(filename: &str, s: &str) -> Result<Config, Error> {
+    let mut config = Config::default();
+    let mut table = Table::None;
+    for (i, line) in s.lines().enumerate() {
+        let error = |message: String| Error {
+            message: format!("Config file {}:{}: {}", filename, i + 1, message)
+        };
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            let end = match line.find(']') {
+                Some(index) if is_comment(&line[index + 1..]) => index,
+                _ => return Err(error("invalid table header".to_string()))
+            };
+            let keys: Vec<&str> = line[1..end].split('.').map(|key| key.trim()).collect();
+            table = match keys.as_slice() {
+                ["variables"] => Table::Variables,
+                ["environments", name] if is_bare_key(name) => Table::Environment(name.to_string()),
+                ["environments", name, "variables"] if is_bare_key(name) => Table::EnvironmentVariables(name.to_string()),
+                _ => return Err(error(format!("unknown table [{}]", line[1..end].trim())))
+            };
+            if let Table::Environment(name) | Table::EnvironmentVariables(name) = &table {
+                config.environments.entry(name.clone()).or_default();
+            }
+            continue;
+        }
+
+        let (key, value) = match key_value(line) {
+            Some(key_value) => key_value,
+            None => return Err(error("expecting key = value".to_string()))
+        };
+        let value = match parse_value(value) {
+            Some(value) => value,
+            None => return Err(error(format!("invalid value for key {}", key)))
+        };
+        match &table {
+            Table::None => set_option(&mut config.defaults, key.as_str(), value).map_err(error)?,
+            Table::Variables => config.defaults.variables.push((key, Value::from_json(&value))),
+            Table::Environment(name) => {
+                let options = config.environments.get_mut(name).unwrap();
+                set_option(options, key.as_str(), value).map_err(error)?;
+            }
+            Table::EnvironmentVariables(name) => {
+                let options = config.environments.get_mut(name).unwrap();
+                options.variables.push((key, Value::from_json(&value)));
+            }
+        }
+    }
+    Ok(config)
+}
+
+// options selecting what to run, or defined by a table, are only given on the command line
+const COMMAND_LINE_OPTIONS: [&str; 12] = [
+    "env", "exclude-tag", "help", "name", "namespace", "no-color", "no-config", "tag", "to-entry", "variable", "variable-json", "version",
+];
+
+// keys are the long names of the command line options
+fn set_option(options: &mut ConfigOptions, key: &str, value: serde_json::Value) -> Result<(), String> {
+    let invalid_value = || format!("invalid value for key {}", key);
+    let bool_value = || value.as_bool().ok_or_else(invalid_value);
+    let string_value = || value.as_str().map(|s| s.to_string()).ok_or_else(invalid_value);
+    match key {
+        "append" => options.append = Some(bool_value()?),
+        "color" => options.color = Some(bool_value()?),
+        "cookie" => options.cookie = Some(string_value()?),
+        "cookie-jar" => options.cookie_jar = Some(string_value()?),
+        "fail-at-end" => options.fail_at_end = Some(bool_value()?),
+        "file-root" => options.file_root = Some(string_value()?),
+        "html" => options.html = Some(string_value()?),
+        "include" => options.include = Some(bool_value()?),
+        "insecure" => options.insecure = Some(bool_value()?),
+        "json" => options.json = Some(string_value()?),
+        "location" => options.location = Some(bool_value()?),
+        "max-redirs" => match value.as_i64() {
+            Some(n) if n >= -1 => options.max_redirs = Some(n),
+            _ => return Err(invalid_value()),
+        },
+        "max-time" => options.max_time = Some(value.as_u64().ok_or_else(invalid_value)?),
+        "noproxy" => options.noproxy = Some(string_value()?),
+        "output" => options.output = Some(string_value()?),
+        "proxy" => options.proxy = Some(string_value()?),
+        "seed" => options.seed = Some(value.as_u64().ok_or_else(invalid_value)?),
+        "variables-file" => options.variables_file = Some(string_value()?),
+        "verbose" => options.verbose = Some(bool_value()?),
+        "with-dependencies" => options.with_dependencies = Some(bool_value()?),
+        _ if COMMAND_LINE_OPTIONS.contains(&key) => return Err(format!("option {} can only be given on the command line", key)),
+        _ => return Err(format!("unknown key {}", key)),
+    }
+    Ok(())
+}
+
+fn is_bare_key(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+fn key_value(line: &str) -> Option<(String, &str)> {
+    let (key, value) = if line.starts_with('"') {
+        let (key, len) = basic_string(line)?;
+        let value = line[len..].trim_start().strip_prefix('=')?;
+        (key, value)
+    } else {
+        let index = line.find('=')?;
+        let key = line[..index].trim();
+        if !is_bare_key(key) {
+            return None;
+        }
+        (key.to_string(), &line[index + 1..])
+    };
+    Some((key, value.trim()))
+}
+
+// parse a double-quoted string, returning its value and the number of bytes consumed
+fn basic_string(s: &str) -> Option<(String, usize)> {
+    let mut value = String::new();
+    let mut chars = s.char_indices().skip(1);
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Some((value, index + 1)),
+            '\\' => match chars.next()?.1 {
+                '"' => value.push('"'),
+                '\\' => value.push('\\'),
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                _ => return None,
+            },
+            _ => value.push(c),
+        }
+    }
+    None
+}
+
+fn parse_value(s: &str) -> Option<serde_json::Value> {
+    if s.starts_with('"') {
+        let (value, len) = basic_string(s)?;
+        return if is_comment(&s[len..]) {
+            Some(serde_json::Value::String(value))
+        } else {
+            None
+        };
+    }
+    let s = match s.find('#') {
+        Some(index) => s[..index].trim(),
+        None => s,
+    };
+    match s {
+        "true" => Some(serde_json::Value::Bool(true)),
+        "false" => Some(serde_json::Value::Bool(false)),
+        _ => if let Ok(n) = s.parse::<i64>() {
+            Some(serde_json::Value::from(n))
+        } else if let Ok(f) = s.parse::<f64>() {
+            serde_json::Number::from_f64(f).map(serde_json::Value::Number)
+        } else {
+            None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let s = r#"
+color = true
+max-redirs = -1
+include = true
+seed = 42
+
+[variables]
+host = "http://localhost:8000"
+
+# staging
+[environments.staging]
+proxy = "proxy.example.com:3128"  # corporate proxy
+insecure = true
+max-time = 10
+
+[environments.staging.variables]
+host = "https://staging.example.com"
+port = 8443
+"zip" = "75001"
+
+[environments.prod]
+"#;
+        let config = parse("hurl.toml", s).unwrap();
+        assert_eq!(config.options(None).unwrap(), ConfigOptions {
+            variables: vec![("host".to_string(), Value::String("http://localhost:8000".to_string()))],
+            color: Some(true),
+            include: Some(true),
+            max_redirs: Some(-1),
+            seed: Some(42),
+            ..ConfigOptions::default()
+        });
+        assert_eq!(config.options(Some("staging")).unwrap(), ConfigOptions {
+            variables: vec![
+                ("host".to_string(), Value::String("http://localhost:8000".to_string())),
+                ("host".to_string(), Value::String("https://staging.example.com".to_string())),
+                ("port".to_string(), Value::Integer(8443)),
+                ("zip".to_string(), Value::String("75001".to_string())),
+            ],
+            color: Some(true),
+            include: Some(true),
+            insecure: Some(true),
+            max_redirs: Some(-1),
+            max_time: Some(10),
+            proxy: Some("proxy.example.com:3128".to_string()),
+            seed: Some(42),
+            ..ConfigOptions::default()
+        });
+        assert_eq!(config.options(Some("prod")).unwrap(), config.options(None).unwrap());
+        assert_eq!(config.options(Some("dev")).err().unwrap().message, "Environment dev is not defined in config file hurl.toml");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse("hurl.toml", "[environments.dev]\ncookies = true").err().unwrap().message, "Config file hurl.toml:2: unknown key cookies");
+        assert_eq!(parse("hurl.toml", "[environments.dev]\ncookie = true").err().unwrap().message, "Config file hurl.toml:2: invalid value for key cookie");
+        assert_eq!(parse("hurl.toml", "tag = \"smoke\"").err().unwrap().message, "Config file hurl.toml:1: option tag can only be given on the command line");
+        assert_eq!(parse("hurl.toml", "[environments.dev]\ninsecure = \"yes\"").err().unwrap().message, "Config file hurl.toml:2: invalid value for key insecure");
+        assert_eq!(parse("hurl.toml", "max-time = -1").err().unwrap().message, "Config file hurl.toml:1: invalid value for key max-time");
+        assert_eq!(parse("hurl.toml", "max-redirs = -2").err().unwrap().message, "Config file hurl.toml:1: invalid value for key max-redirs");
+        assert_eq!(parse("hurl.toml", "[env.dev]").err().unwrap().message, "Config file hurl.toml:1: unknown table [env.dev]");
+        assert_eq!(parse("hurl.toml", "[environments.dev").err().unwrap().message, "Config file hurl.toml:1: invalid table header");
+        assert_eq!(parse("hurl.toml", "[environments.dev.variables]\nhost = localhost").err().unwrap().message, "Config file hurl.toml:2: invalid value for key host");
+        assert_eq!(parse("hurl.toml", "[environments.dev.variables]\nhost").err().unwrap().message, "Config file hurl.toml:2: expecting key = value");
+    }
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("\"a \\\"b\\\"\" # comment").unwrap(), serde_json::Value::String("a \"b\"".to_string()));
+        assert_eq!(parse_value("\"a\" b"), None);
+        assert_eq!(parse_value("1.5").unwrap(), serde_json::json!(1.5));
+        assert_eq!(parse_value("false").unwrap(), serde_json::Value::Bool(false));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut config = parse("hurl.toml", "color = true\njson = \"report.json\"\n[environments.dev]\nproxy = \"localhost:3128\"").unwrap();
+        config.merge(parse("hurl.toml", "color = false\n[environments.dev]\ninsecure = true").unwrap());
+        assert_eq!(config.options(Some("dev")).unwrap(), ConfigOptions {
+            color: Some(false),
+            insecure: Some(true),
+            json: Some("report.json".to_string()),
+            proxy: Some("localhost:3128".to_string()),
+            ..ConfigOptions::default()
+        });
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let mut options = ConfigOptions {
+            json: Some("report.json".to_string()),
+            html: Some("/tmp/report".to_string()),
+            cookie_jar: Some("cookies.txt".to_string()),
+            ..ConfigOptions::default()
+        };
+        options.resolve_paths(Path::new("/project"));
+        assert_eq!(options.json, Some("/project/report.json".to_string()));
+        assert_eq!(options.cookie_jar, Some("/project/cookies.txt".to_string()));
+        assert_eq!(options.html, Some("/tmp/report".to_string()));
+    }
+}
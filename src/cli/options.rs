@@ -17,6 +17,7 @@
  */
 use std::fs;
 
+use crate::core::common::Value;
 use crate::http;
 
 use super::Error;
@@ -120,6 +121,107 @@ pub fn proxy(option_value: Option<&str>, env_value: Option<String>) -> Result<Op
     }
 }
 
+///
+/// Read the variables of a file.
+/// A json file (.json extension) must contain an object, its values keep their json type, its names are sorted.
+/// Other files are read as .env/properties files, with one name=value per line in order of definition,
+/// the values are strings, without their surrounding quotes.
+///
+pub fn variables_file(filename: &str) -> Result<Vec<(String, Value)>, Error> {
+    let path = std::path::Path::new(filename);
+    if !path.exists() {
+        return Err(Error {
+            message: format!("Variables file {} does not exist", filename)
+        });
+    }
+    let s = match fs::read_to_string(filename) {
+        Ok(s) => s,
+        Err(_) => return Err(Error {
+            message: format!("Variables file {} can not be read", filename)
+        })
+    };
+    if path.extension().map(|e| e == "json").unwrap_or(false) {
+        json_variables(filename, &s)
+    } else {
+        properties_variables(filename, &s)
+    }
+}
+
+fn json_variables(filename: &str, s: &str) -> Result<Vec<(String, Value)>, Error> {
+    match serde_json::from_str::<serde_json::Value>(s) {
+        Ok(serde_json::Value::Object(map)) => Ok(map
+            .iter()
+            .map(|(name, value)| (name.to_string(), Value::from_json(value)))
+            .collect()),
+        Ok(_) => Err(Error {
+            message: format!("Variables file {} must contain a json object", filename)
+        }),
+        Err(e) => Err(Error {
+            message: format!("Variables file {}:{}: invalid json ({})", filename, e.line(), e)
+        }),
+    }
+}
+
+fn properties_variables(filename: &str, s: &str) -> Result<Vec<(String, Value)>, Error> {
+    let mut variables = vec![];
+    for (i, line) in s.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (name, value) = match line.find('=') {
+            Some(index) => (line[..index].trim(), line[index + 1..].trim()),
+            None => return Err(Error {
+                message: format!("Variables file {}:{}: missing variable value for {}", filename, i + 1, line)
+            })
+        };
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            return Err(Error {
+                message: format!("Variables file {}:{}: invalid variable name <{}>", filename, i + 1, name)
+            });
+        }
+        // one pair of surrounding quotes is removed
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| value.strip_prefix(*quote).and_then(|value| value.strip_suffix(*quote)))
+            .unwrap_or(value);
+        variables.push((name.to_string(), Value::String(value.to_string())));
+    }
+    Ok(variables)
+}
+
+///
+/// Parse a --variable-json option, name=value with a json value.
+///
+pub fn json_variable(s: &str) -> Result<(String, Value), Error> {
+    let (name, value) = match s.find('=') {
+        Some(index) => (&s[..index], &s[index + 1..]),
+        None => return Err(Error {
+            message: format!("Missing variable value for {}!", s)
+        })
+    };
+    match serde_json::from_str::<serde_json::Value>(value) {
+        Ok(value) => Ok((name.to_string(), Value::from_json(&value))),
+        Err(_) => Err(Error {
+            message: format!("Invalid json value for variable {}", name)
+        })
+    }
+}
+
+///
+/// Select the variables defined with HURL_ environment variables.
+/// The prefix is removed from the variable name: HURL_host defines the variable host.
+///
+pub fn env_variables(vars: Vec<(String, String)>) -> Vec<(String, String)> {
+    vars.iter()
+        .filter_map(|(name, value)| match name.strip_prefix("HURL_") {
+            Some(name) if !name.is_empty() => Some((name.to_string(), value.to_string())),
+            _ => None
+        })
+        .collect()
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -147,4 +249,52 @@ mod tests {
         assert_eq!(proxy(Some(""), Some("http://localhost:8002".to_string())).unwrap(), None);
         assert_eq!(proxy(None, Some("http://localhost:8002".to_string())).unwrap(), Some("http://localhost:8002".to_string()));
     }
+
+    #[test]
+    fn test_json_variables() {
+        assert_eq!(json_variables("vars.json", r#"{"host": "localhost", "port": 8000, "zip": "75001", "ids": [1, 2]}"#).unwrap(), vec![
+            ("host".to_string(), Value::String("localhost".to_string())),
+            ("ids".to_string(), Value::List(vec![Value::Integer(1), Value::Integer(2)])),
+            ("port".to_string(), Value::Integer(8000)),
+            ("zip".to_string(), Value::String("75001".to_string())),
+        ]);
+        assert_eq!(json_variables("vars.json", "[1]").err().unwrap().message, "Variables file vars.json must contain a json object");
+        assert!(json_variables("vars.json", "{\n\"host\":\n}").err().unwrap().message.starts_with("Variables file vars.json:3: invalid json"));
+    }
+
+    #[test]
+    fn test_properties_variables() {
+        assert_eq!(properties_variables("vars.env", "# comment\n\nhost=localhost\nexport port = 8000\nzip=\"75001\"\nempty=\n").unwrap(), vec![
+            ("host".to_string(), Value::String("localhost".to_string())),
+            ("port".to_string(), Value::String("8000".to_string())),
+            ("zip".to_string(), Value::String("75001".to_string())),
+            ("empty".to_string(), Value::String("".to_string())),
+        ]);
+        assert_eq!(properties_variables("vars.env", "a='x y'\nb=\"\"\"\nc=\"x'\nd='\n").unwrap(), vec![
+            ("a".to_string(), Value::String("x y".to_string())),
+            ("b".to_string(), Value::String("\"".to_string())),
+            ("c".to_string(), Value::String("\"x'".to_string())),
+            ("d".to_string(), Value::String("'".to_string())),
+        ]);
+        assert_eq!(properties_variables("vars.env", "host=localhost\nport\n").err().unwrap().message, "Variables file vars.env:2: missing variable value for port");
+        assert_eq!(properties_variables("vars.env", "a b=1\n").err().unwrap().message, "Variables file vars.env:1: invalid variable name <a b>");
+    }
+
+    #[test]
+    fn test_json_variable() {
+        assert_eq!(json_variable("id=12").unwrap(), ("id".to_string(), Value::Integer(12)));
+        assert_eq!(json_variable("ids=[1, 2]").unwrap(), ("ids".to_string(), Value::List(vec![Value::Integer(1), Value::Integer(2)])));
+        assert_eq!(json_variable("zip=\"75001\"").unwrap(), ("zip".to_string(), Value::String("75001".to_string())));
+        assert_eq!(json_variable("name=Bob").err().unwrap().message, "Invalid json value for variable name");
+        assert_eq!(json_variable("id").err().unwrap().message, "Missing variable value for id!");
+    }
+
+    #[test]
+    fn test_env_variables() {
+        assert_eq!(env_variables(vec![
+            ("HOME".to_string(), "/root".to_string()),
+            ("HURL_host".to_string(), "localhost".to_string()),
+            ("HURL_".to_string(), "x".to_string()),
+        ]), vec![("host".to_string(), "localhost".to_string())]);
+    }
 }
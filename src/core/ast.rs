@@ -22,16 +22,48 @@ use super::json;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HurlFile {
-    pub entries: Vec<Entry>,
+    // file header: included files, variables, headers and query params applying to every entry
+    pub sections: Vec<Section>,
+    pub templates: Vec<EntryTemplate>,
+    pub entries: Vec<FileEntry>,
     pub line_terminators: Vec<LineTerminator>,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FileEntry {
+    Entry(Box<Entry>),
+    Call(TemplateCall),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Entry {
     pub request: Request,
     pub response: Option<Response>,
 }
 
+// named entry with parameters, run by the template calls of the file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryTemplate {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub name: Variable,
+    pub params: Vec<(Whitespace, Variable)>,
+    pub line_terminator0: LineTerminator,
+    pub entry: Entry,
+}
+
+// run of a template entry, with one argument per parameter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplateCall {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub name: Variable,
+    pub line_terminator0: LineTerminator,
+    pub arguments: Vec<KeyValue>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Request {
     pub line_terminators: Vec<LineTerminator>,
@@ -46,6 +78,44 @@ pub struct Request {
     pub source_info: SourceInfo,
 }
 
+impl HurlFile {
+    pub fn includes(&self) -> Vec<Include> {
+        for section in self.sections.clone() {
+            if let SectionValue::Includes(includes) = section.value {
+                return includes;
+            }
+        }
+        vec![]
+    }
+    pub fn variables(&self) -> Vec<KeyValue> {
+        for section in self.sections.clone() {
+            if let SectionValue::Variables(variables) = section.value {
+                return variables;
+            }
+        }
+        vec![]
+    }
+    pub fn options(&self) -> Vec<KeyValue> {
+        for section in self.sections.clone() {
+            if let SectionValue::Options(options) = section.value {
+                return options;
+            }
+        }
+        vec![]
+    }
+    pub fn namespaces(&self) -> Vec<KeyValue> {
+        for section in self.sections.clone() {
+            if let SectionValue::Namespaces(namespaces) = section.value {
+                return namespaces;
+            }
+        }
+        vec![]
+    }
+    pub fn template(&self, name: &str) -> Option<EntryTemplate> {
+        self.templates.iter().find(|t| t.name.name == name).cloned()
+    }
+}
+
 impl Request {
     pub fn querystring_params(self) -> Vec<KeyValue> {
         for section in self.sections {
@@ -80,6 +150,15 @@ impl Request {
         }
         return vec![];
     }
+
+    pub fn options(self) -> Vec<KeyValue> {
+        for section in self.sections {
+            if let SectionValue::Options(options) = section.value {
+                return options;
+            }
+        }
+        return vec![];
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -113,6 +192,22 @@ impl Response {
         }
         return vec![];
     }
+    pub fn namespaces(self) -> Vec<KeyValue> {
+        for section in self.sections {
+            if let SectionValue::Namespaces(namespaces) = section.value {
+                return namespaces;
+            }
+        }
+        return vec![];
+    }
+    pub fn json_ignore(self) -> Vec<IgnoredPath> {
+        for section in self.sections {
+            if let SectionValue::JsonIgnore(paths) = section.value {
+                return paths;
+            }
+        }
+        return vec![];
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -207,7 +302,13 @@ impl Section {
             SectionValue::FormParams(_) => "FormParams",
             SectionValue::Cookies(_) => "Cookies",
             SectionValue::Captures(_) => "Captures",
-            SectionValue::MultipartFormData(_) => "MultipartFormData"
+            SectionValue::MultipartFormData(_) => "MultipartFormData",
+            SectionValue::Namespaces(_) => "Namespaces",
+            SectionValue::JsonIgnore(_) => "JsonIgnore",
+            SectionValue::Includes(_) => "Includes",
+            SectionValue::Variables(_) => "Variables",
+            SectionValue::Headers(_) => "Headers",
+            SectionValue::Options(_) => "Options",
         }
     }
 }
@@ -220,6 +321,30 @@ pub enum SectionValue {
     Cookies(Vec<Cookie>),
     Captures(Vec<Capture>),
     Asserts(Vec<Assert>),
+    Namespaces(Vec<KeyValue>),
+    JsonIgnore(Vec<IgnoredPath>),
+    Includes(Vec<Include>),
+    Variables(Vec<KeyValue>),
+    Headers(Vec<KeyValue>),
+    Options(Vec<KeyValue>),
+}
+
+// hurl file whose entries are run before the entries of the including file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Include {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub filename: Filename,
+    pub line_terminator0: LineTerminator,
+}
+
+// path excluded from the comparison of the json body
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IgnoredPath {
+    pub line_terminators: Vec<LineTerminator>,
+    pub space0: Whitespace,
+    pub path: Template,
+    pub line_terminator0: LineTerminator,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -249,6 +374,71 @@ pub struct KeyValue {
     pub line_terminator0: LineTerminator,
 }
 
+impl KeyValue {
+    // the value of an option without templates
+    pub fn text(&self) -> Option<String> {
+        match self.value.elements.as_slice() {
+            [] => Some("".to_string()),
+            [TemplateElement::String { value, .. }] => Some(value.trim().to_string()),
+            _ => None,
+        }
+    }
+
+    // the comma separated values of a tags option
+    pub fn tags(&self) -> Vec<String> {
+        match self.text() {
+            None => vec![],
+            Some(value) => value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect(),
+        }
+    }
+
+    // the item and list variables of a loop option `item in list`
+    pub fn loop_variables(&self) -> Option<(String, String)> {
+        let value = self.text()?;
+        let is_name = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+        match value.split_whitespace().collect::<Vec<&str>>().as_slice() {
+            [item, "in", list] if is_name(item) && is_name(list) => Some((item.to_string(), list.to_string())),
+            _ => None,
+        }
+    }
+
+    // the condition of a skip option `[not] variable [== value | != value]`
+    pub fn condition(&self) -> Option<Condition> {
+        let value = self.text()?;
+        let value = value.as_str();
+        let (not, value) = match value.strip_prefix("not ") {
+            Some(value) => (true, value.trim_start()),
+            None => (false, value),
+        };
+        let (variable, comparison) = match value.find(&['=', '!'][..]) {
+            None => (value, None),
+            Some(index) => {
+                let (variable, comparison) = value.split_at(index);
+                let comparison = if let Some(expected) = comparison.strip_prefix("==") {
+                    (true, expected.trim().to_string())
+                } else if let Some(expected) = comparison.strip_prefix("!=") {
+                    (false, expected.trim().to_string())
+                } else {
+                    return None;
+                };
+                (variable.trim_end(), Some(comparison))
+            }
+        };
+        if variable.is_empty() || !variable.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+        Some(Condition { not, variable: variable.to_string(), comparison })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+    pub not: bool,
+    pub variable: String,
+    // equals (true) or differs from (false) the value
+    pub comparison: Option<(bool, String)>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MultipartParam {
     Param(KeyValue),
@@ -284,8 +474,7 @@ pub struct Capture {
     pub space1: Whitespace,
     pub space2: Whitespace,
     pub query: Query,
-    pub space3: Whitespace,
-    pub subquery: Option<Subquery>,
+    pub filters: Vec<(Whitespace, Filter)>,
     pub line_terminator0: LineTerminator,
 }
 
@@ -294,6 +483,7 @@ pub struct Assert {
     pub line_terminators: Vec<LineTerminator>,
     pub space0: Whitespace,
     pub query: Query,
+    pub filters: Vec<(Whitespace, Filter)>,
     pub space1: Whitespace,
     pub predicate: Predicate,
     pub line_terminator0: LineTerminator,
@@ -322,13 +512,17 @@ pub enum QueryValue {
         space0: Whitespace,
         expr: Template,
     },
+    Css {
+        space0: Whitespace,
+        expr: Template,
+    },
     Jsonpath {
         space0: Whitespace,
         expr: Template,
     },
     Regex {
         space0: Whitespace,
-        expr: Template,
+        expr: RegexValue,
     },
     Variable {
         space0: Whitespace,
@@ -379,18 +573,69 @@ impl CookieAttributeName {
 }
 
 
+//
+// Filters
+//
+// applied successively to the value returned by a query
+// in captures and asserts
+//
+
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Subquery {
+pub struct Filter {
     pub source_info: SourceInfo,
-    pub value: SubqueryValue,
+    pub value: FilterValue,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum SubqueryValue {
-    Regex {
+pub enum FilterValue {
+    Count {},
+    Nth {
+        space0: Whitespace,
+        n: u64,
+    },
+    Split {
+        space0: Whitespace,
+        sep: Template,
+    },
+    Replace {
+        space0: Whitespace,
+        old_value: Template,
+        space1: Whitespace,
+        new_value: Template,
+    },
+    UrlDecode {},
+    UrlEncode {},
+    Base64Decode {},
+    ToInt {},
+    ToFloat {},
+    HtmlUnescape {},
+    Jsonpath {
         space0: Whitespace,
         expr: Template,
     },
+    Regex {
+        space0: Whitespace,
+        expr: RegexValue,
+    },
+}
+
+impl FilterValue {
+    pub fn name(&self) -> &str {
+        match self {
+            FilterValue::Count {} => "count",
+            FilterValue::Nth { .. } => "nth",
+            FilterValue::Split { .. } => "split",
+            FilterValue::Replace { .. } => "replace",
+            FilterValue::UrlDecode {} => "urlDecode",
+            FilterValue::UrlEncode {} => "urlEncode",
+            FilterValue::Base64Decode {} => "base64Decode",
+            FilterValue::ToInt {} => "toInt",
+            FilterValue::ToFloat {} => "toFloat",
+            FilterValue::HtmlUnescape {} => "htmlUnescape",
+            FilterValue::Jsonpath { .. } => "jsonpath",
+            FilterValue::Regex { .. } => "regex",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -475,9 +720,25 @@ pub enum PredicateFuncValue {
     },
     Match {
         space0: Whitespace,
-        value: Template,
+        value: RegexValue,
     },
     Exist {},
+    JsonSchema {
+        space0: Whitespace,
+        space1: Whitespace,
+        filename: Filename,
+        space2: Whitespace,
+    },
+    JsonEqual {
+        space0: Whitespace,
+        value: json::Value,
+    },
+    XmlSchema {
+        space0: Whitespace,
+        space1: Whitespace,
+        filename: Filename,
+        space2: Whitespace,
+    },
 }
 
 //
@@ -500,6 +761,50 @@ pub enum TemplateElement {
     Expression(Expr),
 }
 
+// Regex used by queries, filters and predicates
+// either a quoted template (compiled at runtime)
+// or a regex literal /pattern/flags (compiled at parse time)
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegexValue {
+    Template(Template),
+    Literal(RegexLiteral),
+}
+
+impl RegexValue {
+    // return all the matches instead of the first one
+    pub fn is_global(&self) -> bool {
+        match self {
+            RegexValue::Template(_) => false,
+            RegexValue::Literal(literal) => literal.flags.contains('g'),
+        }
+    }
+
+    pub fn source_info(&self) -> SourceInfo {
+        match self {
+            RegexValue::Template(template) => template.source_info.clone(),
+            RegexValue::Literal(literal) => literal.source_info.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RegexLiteral {
+    pub value: String,
+    pub flags: String,
+    pub inner: regex::Regex,
+    pub source_info: SourceInfo,
+}
+
+// the compiled regex is fully determined by its pattern and flags
+impl PartialEq for RegexLiteral {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.flags == other.flags && self.source_info == other.source_info
+    }
+}
+
+impl Eq for RegexLiteral {}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Comment {
     pub value: String,
@@ -570,6 +875,11 @@ pub enum Bytes {
     Xml {
         value: String,
     },
+    // xml body preceded by the template keyword, its variables are rendered
+    TemplateXml {
+        newline0: Whitespace,
+        value: Template,
+    },
     RawString {
         newline0: Whitespace,
         value: Template,
@@ -585,6 +895,11 @@ pub enum Bytes {
         filename: Filename,
         space1: Whitespace,
     },
+    TemplateFile {
+        space0: Whitespace,
+        filename: Filename,
+        space1: Whitespace,
+    },
 }
 
 //
@@ -597,8 +912,184 @@ pub enum Bytes {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Expr {
     pub space0: Whitespace,
-    pub variable: Variable,
+    pub value: ExprValue,
+    pub filters: Vec<ExprFilter>,
+    pub space1: Whitespace,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprValue {
+    Variable(Variable),
+    Literal(ExprLiteral),
+    Function(Box<Function>),
+    Operation(Box<Operation>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operation {
+    pub left: ExprValue,
+    pub space0: Whitespace,
+    pub operator: Operator,
+    pub space1: Whitespace,
+    pub right: ExprValue,
+    pub source_info: SourceInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprLiteral {
+    Integer(i64),
+    String(ExprString),
+}
+
+// the quote is \" inside a json string
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprString {
+    pub quote: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub value: FunctionValue,
+    pub source_info: SourceInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FunctionValue {
+    NewUuid {},
+    Now {},
+    RandomInt {
+        space0: Whitespace,
+        space1: Whitespace,
+        min: i64,
+        space2: Whitespace,
+        max: i64,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprFilter {
+    pub space0: Whitespace,
     pub space1: Whitespace,
+    pub value: ExprFilterValue,
+    pub source_info: SourceInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprFilterValue {
+    Format { space0: Whitespace, format: ExprString },
+    Default { space0: Whitespace, value: ExprLiteral },
+    Base64Encode {},
+    Base64Decode {},
+    UrlEncode {},
+    Upper {},
+    Lower {},
+}
+
+impl Expr {
+    pub fn source_info(&self) -> SourceInfo {
+        SourceInfo {
+            start: self.space0.source_info.end.clone(),
+            end: self.space1.source_info.start.clone(),
+        }
+    }
+}
+
+// the source text of the expression
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let filters: Vec<String> = self.filters.iter().map(|filter| filter.to_string()).collect();
+        write!(f, "{{{{{}{}{}{}}}}}", self.space0.value, self.value, filters.join(""), self.space1.value)
+    }
+}
+
+impl fmt::Display for ExprValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprValue::Variable(variable) => write!(f, "{}", variable.name),
+            ExprValue::Literal(value) => write!(f, "{}", value),
+            ExprValue::Function(function) => write!(f, "{}", function.value),
+            ExprValue::Operation(operation) => {
+                let Operation { left, space0, operator, space1, right, .. } = operation.as_ref();
+                write!(f, "{}{}{}{}{}", left, space0.value, operator.symbol(), space1.value, right)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ExprLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprLiteral::Integer(value) => write!(f, "{}", value),
+            ExprLiteral::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for ExprString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}{}", self.quote, self.value, self.quote)
+    }
+}
+
+impl fmt::Display for FunctionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FunctionValue::RandomInt { space0, space1, min, space2, max } =>
+                write!(f, "random{}int{}{}{}{}", space0.value, space1.value, min, space2.value, max),
+            value => write!(f, "{}", value.name()),
+        }
+    }
+}
+
+impl fmt::Display for ExprFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments = match &self.value {
+            ExprFilterValue::Format { space0, format } => format!("{}{}", space0.value, format),
+            ExprFilterValue::Default { space0, value } => format!("{}{}", space0.value, value),
+            _ => "".to_string(),
+        };
+        write!(f, "{}|{}{}{}", self.space0.value, self.space1.value, self.value.name(), arguments)
+    }
+}
+
+impl Operator {
+    pub fn symbol(&self) -> &str {
+        match self {
+            Operator::Add => "+",
+            Operator::Subtract => "-",
+        }
+    }
+}
+
+impl FunctionValue {
+    pub fn name(&self) -> &str {
+        match self {
+            FunctionValue::NewUuid {} => "newUuid",
+            FunctionValue::Now {} => "now",
+            FunctionValue::RandomInt { .. } => "random",
+        }
+    }
+}
+
+impl ExprFilterValue {
+    pub fn name(&self) -> &str {
+        match self {
+            ExprFilterValue::Format { .. } => "format",
+            ExprFilterValue::Default { .. } => "default",
+            ExprFilterValue::Base64Encode {} => "base64Encode",
+            ExprFilterValue::Base64Decode {} => "base64Decode",
+            ExprFilterValue::UrlEncode {} => "urlEncode",
+            ExprFilterValue::Upper {} => "upper",
+            ExprFilterValue::Lower {} => "lower",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -45,7 +45,8 @@ pub enum Value {
     String(String),
     List(Vec<Value>),
     Object(Vec<(String, Value)>),
-    Nodeset(usize),
+    // string values of the selected nodes
+    Nodeset(Vec<String>),
     Bytes(Vec<u8>),
     Null,
 }
@@ -104,10 +105,10 @@ impl Value {
             serde_json::Value::Null => Value::Null,
             serde_json::Value::Bool(bool) => Value::Bool(*bool),
             serde_json::Value::Number(n) =>
-                if n.is_f64() {
-                    Value::from_f64(n.as_f64().unwrap())
-                } else {
-                    Value::Integer(n.as_i64().unwrap())
+                match n.as_i64() {
+                    Some(i) => Value::Integer(i),
+                    // floats and integers out of the i64 range
+                    None => Value::from_f64(n.as_f64().unwrap()),
                 }
             ,
             serde_json::Value::String(s) => Value::String(s.to_string()),
@@ -184,8 +185,8 @@ impl Serialize for Value {
             Value::String(s) => serializer.serialize_str(s),
             Value::List(values) => serializer.collect_seq(values),
             Value::Object(values) => serializer.collect_map(values.iter().map(|(k, v)| (k, v))),
-            Value::Nodeset(size) => {
-                let size = *size as i64;
+            Value::Nodeset(values) => {
+                let size = values.len() as i64;
                 serializer.collect_map(vec![
                     ("type", serde_json::Value::String("nodeset".to_string())),
                     ("size", serde_json::Value::from(size))
@@ -231,11 +232,11 @@ impl Value {
                 serde_json::Value::from(value)
             }
             Value::String(v) => serde_json::Value::String(v),
-            Value::List(_) => serde_json::Value::Array(vec![]),
-            Value::Object(_) => todo!(),
-            Value::Nodeset(_) => todo!(),
-            Value::Bytes(_) => todo!(),
-            Value::Null => todo!(),
+            Value::List(values) => serde_json::Value::Array(values.iter().map(|v| v.to_json()).collect()),
+            Value::Object(elements) => serde_json::Value::Object(elements.iter().map(|(k, v)| (k.clone(), v.to_json())).collect()),
+            Value::Nodeset(values) => serde_json::Value::Array(values.into_iter().map(serde_json::Value::String).collect()),
+            Value::Bytes(v) => serde_json::Value::String(base64::encode(&v)),
+            Value::Null => serde_json::Value::Null,
         }
     }
 }
@@ -260,6 +261,15 @@ mod tests {
         assert_eq!(Value::from_json(&serde_json::Value::Bool(true)), Value::Bool(true));
         assert_eq!(Value::from_json(&serde_json::Value::from(1)), Value::Integer(1));
         assert_eq!(Value::from_json(&serde_json::Value::from(1.5)), Value::Float(1, 500_000_000_000_000_000));
+        assert_eq!(Value::from_json(&serde_json::json!([1, null])), Value::List(vec![Value::Integer(1), Value::Null]));
+        assert_eq!(Value::from_json(&serde_json::json!(12345678901234567890u64)), Value::from_f64(12345678901234567890.0));
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(Value::Float(1, 500_000_000_000_000_000).to_json(), serde_json::json!(1.5));
+        assert_eq!(Value::List(vec![Value::Integer(1), Value::Null]).to_json(), serde_json::json!([1, null]));
+        assert_eq!(Value::Bytes(vec![104, 105]).to_json(), serde_json::json!("aGk="));
     }
 
     #[test]
@@ -280,7 +290,7 @@ mod tests {
         assert_eq!(serde_json::to_string(&Value::Object(vec![
             ("name".to_string(), Value::String("Bob".to_string()))
         ])).unwrap(), r#"{"name":"Bob"}"#);
-        assert_eq!(serde_json::to_string(&Value::Nodeset(4)).unwrap(), r#"{"type":"nodeset","size":4}"#);
+        assert_eq!(serde_json::to_string(&Value::Nodeset(vec![String::from("a"); 4])).unwrap(), r#"{"type":"nodeset","size":4}"#);
         assert_eq!(serde_json::to_string(&Value::Bytes(vec![65])).unwrap(), r#""QQ==""#);
         assert_eq!(serde_json::to_string(&Value::Null {}).unwrap(), "null");
     }
@@ -15,7 +15,10 @@
  * limitations under the License.
  *
  */
-use super::ast::Template;
+use core::fmt;
+
+use super::ast::{Expr, Template, TemplateElement};
+use super::common::SourceInfo;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Value {
@@ -25,6 +28,8 @@ pub enum Value {
     List { space0: String, elements: Vec<ListElement> },
     Object { space0: String, elements: Vec<ObjectElement> },
     Null {},
+    Matcher(Matcher),
+    Expression(Expr),
 }
 
 impl Value {
@@ -36,6 +41,8 @@ impl Value {
             Value::List { .. } => "list".to_string(),
             Value::Object { .. } => "object".to_string(),
             Value::String(_) => "string".to_string(),
+            Value::Matcher(_) => "matcher".to_string(),
+            Value::Expression(_) => "expression".to_string(),
         }
     }
 }
@@ -57,11 +64,47 @@ pub struct ObjectElement {
     pub space3: String,
 }
 
+///
+/// A placeholder in an expected json value
+/// such as {{any integer}} or {{matches "\\d{4}-.*"}}
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Matcher {
+    pub space0: String,
+    pub value: MatcherValue,
+    pub space1: String,
+    pub source_info: SourceInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MatcherValue {
+    Any {},
+    AnyType { space0: String, name: String },
+    Matches { space0: String, value: Template },
+}
+
+impl fmt::Display for Matcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match &self.value {
+            MatcherValue::Any {} => "any".to_string(),
+            MatcherValue::AnyType { space0, name } => format!("any{}{}", space0, name),
+            MatcherValue::Matches { space0, value } => {
+                let encoded: String = value.elements.iter().map(|e| match e {
+                    TemplateElement::String { encoded, .. } => encoded.clone(),
+                    TemplateElement::Expression(expr) => expr.to_string(),
+                }).collect();
+                format!("matches{}\"{}\"", space0, encoded)
+            }
+        };
+        write!(f, "{{{{{}{}{}}}}}", self.space0, value, self.space1)
+    }
+}
+
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use super::super::ast::{Expr, TemplateElement, Variable, Whitespace};
+    use super::super::ast::{Expr, ExprValue, Variable, Whitespace};
     use super::super::common::SourceInfo;
 
     pub fn person_value() -> Value {
@@ -100,7 +143,8 @@ pub mod tests {
                 },
                 TemplateElement::Expression(Expr {
                     space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 15, 1, 15) },
-                    variable: Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 15, 1, 19) },
+                    value: ExprValue::Variable(Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 15, 1, 19) }),
+                    filters: vec![],
                     space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 19, 1, 19) },
                 }),
                 TemplateElement::String {
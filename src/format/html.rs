@@ -16,6 +16,7 @@
  *
  */
 use super::super::core::ast::*;
+use super::super::core::json;
 
 pub trait Htmlable {
     fn to_html(&self) -> String;
@@ -48,6 +49,12 @@ impl Htmlable for HurlFile {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
         buffer.push_str("<div class=\"hurl-file\">");
+        for section in self.clone().sections {
+            buffer.push_str(section.to_html().as_str());
+        }
+        for template in self.clone().templates {
+            buffer.push_str(template.to_html().as_str());
+        }
         for entry in self.clone().entries {
             buffer.push_str(entry.to_html().as_str());
         }
@@ -59,6 +66,57 @@ impl Htmlable for HurlFile {
     }
 }
 
+impl Htmlable for FileEntry {
+    fn to_html(&self) -> String {
+        match self {
+            FileEntry::Entry(entry) => entry.to_html(),
+            FileEntry::Call(call) => call.to_html(),
+        }
+    }
+}
+
+impl Htmlable for EntryTemplate {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str("<div class=\"hurl-template\">");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str("<span class=\"keyword\">TEMPLATE</span>");
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str(format!("<span class=\"string\">{}</span>", self.name.name).as_str());
+        for (space, param) in self.params.clone() {
+            buffer.push_str(space.to_html().as_str());
+            buffer.push_str(format!("<span class=\"variable\">{}</span>", param.name).as_str());
+        }
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer.push_str(self.entry.to_html().as_str());
+        buffer.push_str("</div>");
+        buffer
+    }
+}
+
+impl Htmlable for TemplateCall {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str("<div class=\"hurl-entry\">");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str("<span class=\"keyword\">CALL</span>");
+        buffer.push_str(self.space1.to_html().as_str());
+        buffer.push_str(format!("<span class=\"string\">{}</span>", self.name.name).as_str());
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        buffer.push_str("</span>");
+        for argument in self.arguments.clone() {
+            buffer.push_str(argument.to_html().as_str());
+        }
+        buffer.push_str("</div>");
+        buffer
+    }
+}
+
 impl Htmlable for Entry {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
@@ -183,11 +241,67 @@ impl Htmlable for SectionValue {
                     buffer.push_str(item.to_html().as_str())
                 }
             }
+            SectionValue::Namespaces(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::JsonIgnore(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::Includes(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::Variables(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::Headers(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
+            SectionValue::Options(items) => {
+                for item in items {
+                    buffer.push_str(item.to_html().as_str())
+                }
+            }
         }
         buffer
     }
 }
 
+impl Htmlable for IgnoredPath {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str(self.path.to_html().as_str());
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer
+    }
+}
+
+impl Htmlable for Include {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        add_line_terminators(&mut buffer, self.line_terminators.clone());
+        buffer.push_str("<span class=\"line\">");
+        buffer.push_str(self.space0.to_html().as_str());
+        buffer.push_str(format!("<span class=\"string\">{}</span>", self.filename.value).as_str());
+        buffer.push_str(self.line_terminator0.to_html().as_str());
+        buffer.push_str("</span>");
+        buffer
+    }
+}
+
 impl Htmlable for KeyValue {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
@@ -275,6 +389,10 @@ impl Htmlable for Capture {
         buffer.push_str("<span>:</span>");
         buffer.push_str(self.space2.to_html().as_str());
         buffer.push_str(self.query.to_html().as_str());
+        for (space, filter) in self.filters.clone() {
+            buffer.push_str(space.to_html().as_str());
+            buffer.push_str(filter.to_html().as_str());
+        }
         buffer.push_str(self.line_terminator0.to_html().as_str());
         buffer.push_str("</span>");
         buffer
@@ -312,6 +430,11 @@ impl Htmlable for QueryValue {
                 buffer.push_str(space0.to_html().as_str());
                 buffer.push_str(expr.to_html().as_str());
             }
+            QueryValue::Css { space0, expr } => {
+                buffer.push_str("<span class=\"query-type\">css</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
             QueryValue::Jsonpath { space0, expr } => {
                 buffer.push_str("<span class=\"query-type\">jsonpath</span>");
                 buffer.push_str(space0.to_html().as_str());
@@ -361,6 +484,10 @@ impl Htmlable for Assert {
         buffer.push_str("<span class=\"line\">");
         buffer.push_str(self.space0.to_html().as_str());
         buffer.push_str(self.query.to_html().as_str());
+        for (space, filter) in self.filters.clone() {
+            buffer.push_str(space.to_html().as_str());
+            buffer.push_str(filter.to_html().as_str());
+        }
         buffer.push_str(self.space1.to_html().as_str());
         buffer.push_str(self.predicate.to_html().as_str());
         buffer.push_str("</span>");
@@ -369,6 +496,39 @@ impl Htmlable for Assert {
     }
 }
 
+impl Htmlable for Filter {
+    fn to_html(&self) -> String {
+        let mut buffer = String::from("");
+        buffer.push_str(format!("<span class=\"filter-type\">{}</span>", self.value.name()).as_str());
+        match self.value.clone() {
+            FilterValue::Nth { space0, n } => {
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(format!("<span class=\"number\">{}</span>", n).as_str());
+            }
+            FilterValue::Split { space0, sep } => {
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(sep.to_html().as_str());
+            }
+            FilterValue::Replace { space0, old_value, space1, new_value } => {
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(old_value.to_html().as_str());
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str(new_value.to_html().as_str());
+            }
+            FilterValue::Jsonpath { space0, expr } => {
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
+            FilterValue::Regex { space0, expr } => {
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(expr.to_html().as_str());
+            }
+            _ => {}
+        }
+        buffer
+    }
+}
+
 impl Htmlable for Predicate {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
@@ -474,11 +634,52 @@ impl Htmlable for PredicateFuncValue {
             PredicateFuncValue::Exist {} => {
                 buffer.push_str("<span class=\"predicate-type\">exists</span>");
             }
+            PredicateFuncValue::JsonEqual { space0, value } => {
+                buffer.push_str("<span class=\"predicate-type\">jsonEquals</span>");
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str(value.to_html().as_str());
+            }
+            PredicateFuncValue::JsonSchema { space0, space1, filename, space2 } |
+            PredicateFuncValue::XmlSchema { space0, space1, filename, space2 } => {
+                let name = if let PredicateFuncValue::JsonSchema { .. } = self { "jsonschema" } else { "xmlschema" };
+                buffer.push_str(format!("<span class=\"predicate-type\">{}</span>", name).as_str());
+                buffer.push_str(space0.to_html().as_str());
+                buffer.push_str("<span class=\"string\">file,</span>");
+                buffer.push_str(space1.to_html().as_str());
+                buffer.push_str(format!("<span class=\"string\">{}</span>", filename.value).as_str());
+                buffer.push_str(space2.to_html().as_str());
+                buffer.push_str("<span class=\"string\">;</span>");
+            }
         }
         buffer
     }
 }
 
+impl Htmlable for json::Value {
+    fn to_html(&self) -> String {
+        match self {
+            json::Value::String(template) => format!("<span class=\"string\">\"</span>{}<span class=\"string\">\"</span>", template.to_html()),
+            json::Value::Number(value) => format!("<span class=\"number\">{}</span>", value),
+            json::Value::Boolean(value) => format!("<span class=\"boolean\">{}</span>", value),
+            json::Value::Null {} => "<span class=\"null\">null</span>".to_string(),
+            json::Value::Matcher(matcher) => format!("<span class=\"matcher\">{}</span>", matcher),
+            json::Value::Expression(expr) => expr.to_html(),
+            json::Value::List { space0, elements } => {
+                let elements = elements.iter()
+                    .map(|e| format!("{}{}{}", e.space0, e.value.to_html(), e.space1))
+                    .collect::<Vec<String>>();
+                format!("[{}{}]", space0, elements.join(","))
+            }
+            json::Value::Object { space0, elements } => {
+                let elements = elements.iter()
+                    .map(|e| format!("{}<span class=\"string\">\"{}\"</span>{}:{}{}{}", e.space0, e.name, e.space1, e.space2, e.value.to_html(), e.space3))
+                    .collect::<Vec<String>>();
+                format!("{{{}{}}}", space0, elements.join(","))
+            }
+        }
+    }
+}
+
 impl Htmlable for Whitespace {
     fn to_html(&self) -> String {
         let mut buffer = String::from("");
@@ -519,6 +720,17 @@ impl Htmlable for Template {
     }
 }
 
+impl Htmlable for RegexValue {
+    fn to_html(&self) -> String {
+        match self {
+            RegexValue::Template(template) => template.to_html(),
+            RegexValue::Literal(RegexLiteral { value, flags, .. }) => {
+                format!("<span class=\"string\">/{}/{}</span>", value, flags)
+            }
+        }
+    }
+}
+
 impl Htmlable for TemplateElement {
     fn to_html(&self) -> String {
         match self {
@@ -538,7 +750,10 @@ impl Htmlable for TemplateElement {
 
 impl Htmlable for Expr {
     fn to_html(&self) -> String {
-        format!("<span class=\"variable\">{}</span>", self.variable.name)
+        match &self.value {
+            ExprValue::Variable(variable) if self.filters.is_empty() => format!("<span class=\"variable\">{}</span>", variable.name),
+            _ => format!("<span class=\"variable\">{}</span>", self),
+        }
     }
 }
 
@@ -52,6 +52,29 @@ impl Logger {
         }
     }
 
+    // skipped entries are reported outside of the verbose mode too
+    pub fn summary(&self, succeeded: usize, failed: usize, skipped: usize) {
+        if !self.verbose && skipped == 0 {
+            return;
+        }
+        let failed = if self.color && failed > 0 {
+            TerminalColor::Red.format(format!("{} failed", failed))
+        } else {
+            format!("{} failed", failed)
+        };
+        let skipped = if self.color && skipped > 0 {
+            TerminalColor::Yellow.format(format!("{} skipped", skipped))
+        } else {
+            format!("{} skipped", skipped)
+        };
+        let s = format!("entries: {} succeeded, {}, {}", succeeded, failed, skipped);
+        if self.verbose {
+            eprintln!("* {}", s);
+        } else {
+            eprintln!("{}", s);
+        }
+    }
+
     pub fn error_message(&self, s: String) {
         let error_type = if !self.color {
             "error".to_string()
@@ -139,13 +162,22 @@ impl Logger {
                     tab_shift += 1;
                 }
             }
+            let mut fixme_lines = err.fixme.lines();
             eprintln!(
                 "{} | {}{} {fixme}",
                 " ".repeat(line_number_size).as_str(),
                 " ".repeat(err.source_info.start.column - 1 + tab_shift * 3),
                 "^".repeat(if width > 1 { width } else { 1 }),
-                fixme = err.fixme.as_str(),
+                fixme = fixme_lines.next().unwrap_or_default(),
             );
+            // following lines of the fixme (details)
+            for line in fixme_lines {
+                eprintln!(
+                    "{} |   {}",
+                    " ".repeat(line_number_size).as_str(),
+                    line,
+                );
+            }
         }
 
         eprintln!("{} |\n", " ".repeat(line_number_size));
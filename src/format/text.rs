@@ -55,7 +55,7 @@ fn format_token(token: Token, color: bool) -> String {
         }
         Token::Value(value) => value,
         Token::Colon(value) => value,
-        Token::QueryType(value) => {
+        Token::QueryType(value) | Token::FilterType(value) => {
             if color {
                 TerminalColor::LightCyan.format(value)
             } else {
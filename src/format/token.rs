@@ -26,6 +26,7 @@ pub enum Token {
     Status(String),
     SectionHeader(String),
     QueryType(String),
+    FilterType(String),
     PredicateType(String),
     Not(String),
     Keyword(String),
@@ -56,6 +57,14 @@ fn add_tokens(tokens1: &mut Vec<Token>, tokens2: Vec<Token>) {
 impl Tokenizable for HurlFile {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.sections.iter().flat_map(|e| e.tokenize()).collect(),
+        );
+        add_tokens(
+            &mut tokens,
+            self.templates.iter().flat_map(|e| e.tokenize()).collect(),
+        );
         add_tokens(
             &mut tokens,
             self.entries.iter().flat_map(|e| e.tokenize()).collect(),
@@ -71,6 +80,62 @@ impl Tokenizable for HurlFile {
     }
 }
 
+impl Tokenizable for FileEntry {
+    fn tokenize(&self) -> Vec<Token> {
+        match self {
+            FileEntry::Entry(entry) => entry.tokenize(),
+            FileEntry::Call(call) => call.tokenize(),
+        }
+    }
+}
+
+impl Tokenizable for EntryTemplate {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::Keyword("TEMPLATE".to_string()));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        tokens.push(Token::String(self.name.name.clone()));
+        for (space, param) in self.params.iter() {
+            add_tokens(&mut tokens, space.tokenize());
+            tokens.push(Token::CodeVariable(param.name.clone()));
+        }
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        add_tokens(&mut tokens, self.entry.tokenize());
+        tokens
+    }
+}
+
+impl Tokenizable for TemplateCall {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::Keyword("CALL".to_string()));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        tokens.push(Token::String(self.name.name.clone()));
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        add_tokens(
+            &mut tokens,
+            self.arguments.iter().flat_map(|e| e.tokenize()).collect(),
+        );
+        tokens
+    }
+}
+
 impl Tokenizable for Entry {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
@@ -177,6 +242,11 @@ impl Tokenizable for Bytes {
             Bytes::Xml { value } => {
                 tokens.push(Token::String(value.to_string()));
             }
+            Bytes::TemplateXml { newline0, value } => {
+                tokens.push(Token::Keyword(String::from("template")));
+                add_tokens(&mut tokens, newline0.tokenize());
+                tokens.append(&mut value.tokenize());
+            }
 //            Bytes::MultilineString { value: _ } => {}
             Bytes::RawString { newline0, value } => {
                 tokens.push(Token::Keyword(String::from("```")));
@@ -208,6 +278,17 @@ impl Tokenizable for Bytes {
                 add_tokens(&mut tokens, space1.tokenize());
                 tokens.push(Token::Keyword(String::from(";")));
             }
+            Bytes::TemplateFile {
+                space0,
+                filename,
+                space1,
+            } => {
+                tokens.push(Token::Keyword(String::from("template,")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, filename.tokenize());
+                add_tokens(&mut tokens, space1.tokenize());
+                tokens.push(Token::Keyword(String::from(";")));
+            }
         }
         tokens
     }
@@ -273,11 +354,81 @@ impl Tokenizable for SectionValue {
                     items.iter().flat_map(|e| e.tokenize()).collect(),
                 );
             }
+            SectionValue::Namespaces(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::JsonIgnore(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::Includes(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::Variables(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::Headers(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
+            SectionValue::Options(items) => {
+                add_tokens(
+                    &mut tokens,
+                    items.iter().flat_map(|e| e.tokenize()).collect(),
+                );
+            }
         }
         tokens
     }
 }
 
+impl Tokenizable for IgnoredPath {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        add_tokens(&mut tokens, self.path.tokenize());
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        tokens
+    }
+}
+
+impl Tokenizable for Include {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(
+            &mut tokens,
+            self.line_terminators
+                .iter()
+                .flat_map(|e| e.tokenize())
+                .collect(),
+        );
+        add_tokens(&mut tokens, self.space0.tokenize());
+        add_tokens(&mut tokens, self.filename.tokenize());
+        add_tokens(&mut tokens, self.line_terminator0.tokenize());
+        tokens
+    }
+}
+
 impl Tokenizable for KeyValue {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
@@ -383,8 +534,10 @@ impl Tokenizable for Capture {
         tokens.push(Token::Colon(String::from(":")));
         add_tokens(&mut tokens, self.space2.tokenize());
         add_tokens(&mut tokens, self.query.tokenize());
-        add_tokens(&mut tokens, self.space3.tokenize());
-        if let Some(subquery) = self.clone().subquery { add_tokens(&mut tokens, subquery.tokenize()) }
+        for (space, filter) in self.filters.clone() {
+            add_tokens(&mut tokens, space.tokenize());
+            add_tokens(&mut tokens, filter.tokenize());
+        }
         add_tokens(&mut tokens, self.line_terminator0.tokenize());
         tokens
     }
@@ -402,6 +555,10 @@ impl Tokenizable for Assert {
         );
         add_tokens(&mut tokens, self.space0.tokenize());
         add_tokens(&mut tokens, self.query.tokenize());
+        for (space, filter) in self.filters.clone() {
+            add_tokens(&mut tokens, space.tokenize());
+            add_tokens(&mut tokens, filter.tokenize());
+        }
         add_tokens(&mut tokens, self.space1.tokenize());
         // TODO reconvert back your first predicate for jsonpath
         // so that you can use your firstX predicate for other query
@@ -434,6 +591,11 @@ impl Tokenizable for Query {
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, expr.tokenize());
             }
+            QueryValue::Css { space0, expr } => {
+                tokens.push(Token::QueryType(String::from("css")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, expr.tokenize());
+            }
             QueryValue::Jsonpath { space0, expr } => {
                 tokens.push(Token::QueryType(String::from("jsonpath")));
                 add_tokens(&mut tokens, space0.tokenize());
@@ -477,15 +639,34 @@ impl Tokenizable for CookieAttribute {
     }
 }
 
-impl Tokenizable for Subquery {
+impl Tokenizable for Filter {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
+        tokens.push(Token::FilterType(self.value.name().to_string()));
         match self.value.clone() {
-            SubqueryValue::Regex { space0, expr } => {
-                tokens.push(Token::QueryType(String::from("regex")));
+            FilterValue::Nth { space0, n } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Number(n.to_string()));
+            }
+            FilterValue::Split { space0, sep } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, sep.tokenize());
+            }
+            FilterValue::Replace { space0, old_value, space1, new_value } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, old_value.tokenize());
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, new_value.tokenize());
+            }
+            FilterValue::Jsonpath { space0, expr } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, expr.tokenize());
+            }
+            FilterValue::Regex { space0, expr } => {
                 add_tokens(&mut tokens, space0.tokenize());
                 add_tokens(&mut tokens, expr.tokenize());
             }
+            _ => {}
         }
         tokens
     }
@@ -596,6 +777,22 @@ impl Tokenizable for PredicateFuncValue {
             PredicateFuncValue::Exist {} => {
                 tokens.push(Token::PredicateType(String::from("exists")));
             }
+            PredicateFuncValue::JsonEqual { space0, value } => {
+                tokens.push(Token::PredicateType(String::from("jsonEquals")));
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            PredicateFuncValue::JsonSchema { space0, space1, filename, space2 } |
+            PredicateFuncValue::XmlSchema { space0, space1, filename, space2 } => {
+                let name = if let PredicateFuncValue::JsonSchema { .. } = self { "jsonschema" } else { "xmlschema" };
+                tokens.push(Token::PredicateType(String::from(name)));
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Keyword(String::from("file,")));
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, filename.tokenize());
+                add_tokens(&mut tokens, space2.tokenize());
+                tokens.push(Token::Keyword(String::from(";")));
+            }
         }
         tokens
     }
@@ -633,6 +830,17 @@ impl Tokenizable for Template {
     }
 }
 
+impl Tokenizable for RegexValue {
+    fn tokenize(&self) -> Vec<Token> {
+        match self {
+            RegexValue::Template(template) => template.tokenize(),
+            RegexValue::Literal(RegexLiteral { value, flags, .. }) => {
+                vec![Token::String(format!("/{}/{}", value, flags))]
+            }
+        }
+    }
+}
+
 impl Tokenizable for TemplateElement {
     fn tokenize(&self) -> Vec<Token> {
         match self {
@@ -655,13 +863,78 @@ impl Tokenizable for Expr {
         let mut tokens: Vec<Token> = vec![];
         tokens.push(Token::CodeDelimiter(String::from("{{")));
         add_tokens(&mut tokens, self.space0.tokenize());
-        tokens.push(Token::CodeVariable(self.variable.name.clone()));
+        add_tokens(&mut tokens, self.value.tokenize());
+        for filter in self.filters.iter() {
+            add_tokens(&mut tokens, filter.tokenize());
+        }
         add_tokens(&mut tokens, self.space1.tokenize());
         tokens.push(Token::CodeDelimiter(String::from("}}")));
         tokens
     }
 }
 
+impl Tokenizable for ExprValue {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        match self {
+            ExprValue::Variable(variable) => tokens.push(Token::CodeVariable(variable.name.clone())),
+            ExprValue::Literal(value) => add_tokens(&mut tokens, value.tokenize()),
+            ExprValue::Function(function) => match &function.value {
+                FunctionValue::RandomInt { space0, space1, min, space2, max } => {
+                    tokens.push(Token::Keyword(String::from("random")));
+                    add_tokens(&mut tokens, space0.tokenize());
+                    tokens.push(Token::Keyword(String::from("int")));
+                    add_tokens(&mut tokens, space1.tokenize());
+                    tokens.push(Token::Number(min.to_string()));
+                    add_tokens(&mut tokens, space2.tokenize());
+                    tokens.push(Token::Number(max.to_string()));
+                }
+                value => tokens.push(Token::Keyword(value.name().to_string())),
+            },
+            ExprValue::Operation(operation) => {
+                let Operation { left, space0, operator, space1, right, .. } = operation.as_ref();
+                add_tokens(&mut tokens, left.tokenize());
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::Keyword(operator.symbol().to_string()));
+                add_tokens(&mut tokens, space1.tokenize());
+                add_tokens(&mut tokens, right.tokenize());
+            }
+        }
+        tokens
+    }
+}
+
+impl Tokenizable for ExprLiteral {
+    fn tokenize(&self) -> Vec<Token> {
+        match self {
+            ExprLiteral::Integer(value) => vec![Token::Number(value.to_string())],
+            ExprLiteral::String(value) => vec![Token::String(value.to_string())],
+        }
+    }
+}
+
+impl Tokenizable for ExprFilter {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![];
+        add_tokens(&mut tokens, self.space0.tokenize());
+        tokens.push(Token::Keyword(String::from("|")));
+        add_tokens(&mut tokens, self.space1.tokenize());
+        tokens.push(Token::FilterType(self.value.name().to_string()));
+        match &self.value {
+            ExprFilterValue::Format { space0, format } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                tokens.push(Token::String(format.to_string()));
+            }
+            ExprFilterValue::Default { space0, value } => {
+                add_tokens(&mut tokens, space0.tokenize());
+                add_tokens(&mut tokens, value.tokenize());
+            }
+            _ => {}
+        }
+        tokens
+    }
+}
+
 impl Tokenizable for LineTerminator {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![];
@@ -738,6 +1011,26 @@ impl Tokenizable for json::Value {
             json::Value::Null {} => {
                 tokens.push(Token::Keyword("null".to_string()));
             }
+            json::Value::Matcher(matcher) => {
+                tokens.push(Token::CodeDelimiter("{{".to_string()));
+                tokens.push(Token::Whitespace(matcher.space0.clone()));
+                match &matcher.value {
+                    json::MatcherValue::Any {} => tokens.push(Token::Keyword("any".to_string())),
+                    json::MatcherValue::AnyType { space0, name } => {
+                        tokens.push(Token::Keyword("any".to_string()));
+                        tokens.push(Token::Whitespace(space0.clone()));
+                        tokens.push(Token::Keyword(name.clone()));
+                    }
+                    json::MatcherValue::Matches { space0, value } => {
+                        tokens.push(Token::Keyword("matches".to_string()));
+                        tokens.push(Token::Whitespace(space0.clone()));
+                        tokens.append(&mut value.tokenize());
+                    }
+                }
+                tokens.push(Token::Whitespace(matcher.space1.clone()));
+                tokens.push(Token::CodeDelimiter("}}".to_string()));
+            }
+            json::Value::Expression(expr) => tokens.append(&mut expr.tokenize()),
         }
         tokens
     }
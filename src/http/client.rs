@@ -18,6 +18,7 @@
 
 
 use std::path::Path;
+use std::time::Duration;
 
 use super::core::*;
 use super::request::*;
@@ -36,6 +37,7 @@ pub struct ClientOptions {
     pub http_proxy: Option<String>,
     pub https_proxy: Option<String>,
     pub all_proxy: Option<String>,
+    pub timeout: Option<Duration>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -82,6 +84,10 @@ impl Client {
             .danger_accept_invalid_hostnames(options.insecure)
             .danger_accept_invalid_certs(options.insecure)
             .cookie_store(false);
+        let client_builder = match options.timeout {
+            Some(timeout) => client_builder.timeout(timeout),
+            None => client_builder,
+        };
         Client {
             _inner_client: client_builder.build().unwrap(),
             options,
@@ -27,28 +27,78 @@ pub struct Query {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Selector {
+    Wildcard,
     NameChild(String),
-    ArrayIndex(usize),
+    ArrayIndex(i64),
+    ArraySlice(Slice),
+    Union(Vec<Selector>),
     Filter(Predicate),
     RecursiveKey(String),
+    RecursiveWildcard,
+    // ..[0], ..[1:2], ..['key'], ..[?(...)], the selector applied to the element and all its descendants
+    RecursiveDescent(Box<Selector>),
 }
 
+// [start:end:step], negative bounds count from the end of the array
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Predicate {
-    pub key: String,
-    pub func: PredicateFunc,
+pub struct Slice {
+    pub start: Option<i64>,
+    pub end: Option<i64>,
+    pub step: Option<i64>,
 }
 
+// filter expression
+// ?(@.price < 10 && @.category == 'fiction')
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Predicate {
+    Exist(Vec<Selector>),
+    Compare { left: Operand, op: CompareOp, right: Operand },
+    In { value: Operand, values: Vec<Literal> },
+    Match { value: Operand, regex: Pattern },
+    Not(Box<Predicate>),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operand {
+    // path relative to the current element (@)
+    Current(Vec<Selector>),
+    Literal(Literal),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+}
+
+// regex of a match predicate, compiled when parsing
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    pub value: String,
+    pub inner: regex::Regex,
+}
+
+// the compiled regex is fully determined by its pattern
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Pattern {}
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum PredicateFunc {
-    KeyExist {},
-    EqualString(String),
-    Equal(Number),
-    GreaterThan(Number),
-    GreaterThanOrEqual(Number),
-    LessThan(Number),
-    LessThanOrEqual(Number),
+pub enum CompareOp {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual,
 }
 
 
@@ -59,11 +109,14 @@ pub enum PredicateFunc {
 pub struct Number {
     pub int: i64,
     pub decimal: u64,
+    // the integer part of -0.5 is 0, the sign applies to both parts
+    pub negative: bool,
 }
 
 impl Number {
     pub fn to_f64(&self) -> f64 {
-        self.int as f64 + self.decimal as f64 / 1_000_000_000_000_000_000.0
+        let value = self.int.unsigned_abs() as f64 + self.decimal as f64 / 1_000_000_000_000_000_000.0;
+        if self.negative { -value } else { value }
     }
 }
 
@@ -73,6 +126,8 @@ mod tests {
 
     #[test]
     pub fn test_number() {
-        assert_eq!(Number { int: 1, decimal: 0 }.to_f64(), 1.0);
+        assert_eq!(Number { int: 1, decimal: 0, negative: false }.to_f64(), 1.0);
+        assert_eq!(Number { int: -1, decimal: 500_000_000_000_000_000, negative: true }.to_f64(), -1.5);
+        assert_eq!(Number { int: 0, decimal: 500_000_000_000_000_000, negative: true }.to_f64(), -0.5);
     }
 }
@@ -17,6 +17,8 @@
  */
 extern crate serde_json;
 
+use std::cmp::Ordering;
+
 use super::ast::*;
 
 pub type JsonpathResult = Vec<serde_json::Value>;
@@ -35,6 +37,12 @@ impl Query {
 impl Selector {
     pub fn eval(self, root: serde_json::Value) -> JsonpathResult {
         match self {
+            Selector::Wildcard =>
+                match root {
+                    serde_json::Value::Array(values) => values,
+                    serde_json::Value::Object(obj) => obj.values().cloned().collect(),
+                    _ => vec![],
+                }
             Selector::NameChild(field) =>
                 match root.get(field) {
                     None => vec![],
@@ -42,10 +50,28 @@ impl Selector {
                 }
             ,
             Selector::ArrayIndex(index) =>
-                match root.get(index) {
-                    None => vec![],
-                    Some(value) => vec![value.clone()],
+                match root {
+                    serde_json::Value::Array(values) => match array_index(index, values.len()) {
+                        None => vec![],
+                        Some(i) => vec![values[i].clone()],
+                    },
+                    _ => vec![],
                 }
+            Selector::ArraySlice(slice) =>
+                match root {
+                    serde_json::Value::Array(values) => slice
+                        .indices(values.len())
+                        .iter()
+                        .map(|i| values[*i].clone())
+                        .collect(),
+                    _ => vec![],
+                }
+            Selector::Union(selectors) => {
+                selectors
+                    .into_iter()
+                    .flat_map(|selector| selector.eval(root.clone()))
+                    .collect()
+            }
             Selector::Filter(predicate) => {
                 match root {
                     serde_json::Value::Array(elements) => {
@@ -66,7 +92,6 @@ impl Selector {
                             elements.push(elem.clone());
                         }
                         for value in obj.values() {
-                            eprintln!(">> value = {:?}", value);
                             for element in Selector::RecursiveKey(key.clone()).eval(value.clone()) {
                                 elements.push(element);
                             };
@@ -83,29 +108,154 @@ impl Selector {
                 }
                 elements
             }
+            Selector::RecursiveWildcard => {
+                let mut elements = vec![];
+                for value in Selector::Wildcard.eval(root) {
+                    elements.push(value.clone());
+                    for element in Selector::RecursiveWildcard.eval(value) {
+                        elements.push(element);
+                    }
+                }
+                elements
+            }
+            Selector::RecursiveDescent(selector) => {
+                let mut elements = selector.clone().eval(root.clone());
+                for value in Selector::Wildcard.eval(root) {
+                    elements.extend(Selector::RecursiveDescent(selector.clone()).eval(value));
+                }
+                elements
+            }
         }
     }
 }
 
+// negative indices count from the end of the array
+fn array_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { len as i64 + index } else { index };
+    if index >= 0 && index < len as i64 {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+impl Slice {
+    // same semantic as python slices
+    pub fn indices(&self, len: usize) -> Vec<usize> {
+        let len = len as i64;
+        let step = self.step.unwrap_or(1);
+        let normalize = |i: i64| if i < 0 { len + i } else { i };
+        let mut indices = vec![];
+        if step > 0 {
+            let start = self.start.map(normalize).unwrap_or(0).max(0).min(len);
+            let end = self.end.map(normalize).unwrap_or(len).max(0).min(len);
+            let mut i = start;
+            while i < end {
+                indices.push(i as usize);
+                i = match i.checked_add(step) {
+                    Some(i) => i,
+                    None => break,
+                };
+            }
+        } else if step < 0 {
+            let start = self.start.map(normalize).unwrap_or(len - 1).max(-1).min(len - 1);
+            let end = self.end.map(normalize).unwrap_or(-1).max(-1).min(len - 1);
+            let mut i = start;
+            while i > end {
+                indices.push(i as usize);
+                i = match i.checked_add(step) {
+                    Some(i) => i,
+                    None => break,
+                };
+            }
+        }
+        indices
+    }
+}
+
 impl Predicate {
     pub fn eval(&self, elem: serde_json::Value) -> bool {
-        //eprintln!("eval elem {:?}", elem);
-        match elem {
-            serde_json::Value::Object(ref obj) => match (obj.get(self.key.as_str()), self.func.clone()) {
-                (Some(serde_json::Value::Number(v)), PredicateFunc::Equal(ref num))
-                => approx_eq!(f64, v.as_f64().unwrap(), num.to_f64(), ulps = 2), //v.as_f64().unwrap() == num.to_f64(),
-                (Some(serde_json::Value::Number(v)), PredicateFunc::GreaterThan(ref num)) => v.as_f64().unwrap() > num.to_f64(),
-                (Some(serde_json::Value::Number(v)), PredicateFunc::GreaterThanOrEqual(ref num)) => v.as_f64().unwrap() >= num.to_f64(),
-                (Some(serde_json::Value::Number(v)), PredicateFunc::LessThan(ref num)) => v.as_f64().unwrap() < num.to_f64(),
-                (Some(serde_json::Value::Number(v)), PredicateFunc::LessThanOrEqual(ref num)) => v.as_f64().unwrap() <= num.to_f64(),
-                (Some(serde_json::Value::String(v)), PredicateFunc::EqualString(ref s)) => v == s,
+        match self {
+            Predicate::Exist(selectors) => !Query { selectors: selectors.clone() }.eval(elem).is_empty(),
+            Predicate::Compare { left, op, right } => match (left.eval(&elem), right.eval(&elem)) {
+                (Some(left), Some(right)) => op.eval(&left, &right),
                 _ => false,
             },
-            _ => false,
+            Predicate::In { value, values } => match value.eval(&elem) {
+                Some(value) => values.iter().any(|v| json_equal(&value, &v.to_json())),
+                None => false,
+            },
+            Predicate::Match { value, regex } => match value.eval(&elem) {
+                Some(serde_json::Value::String(s)) => regex.inner.is_match(s.as_str()),
+                _ => false,
+            },
+            Predicate::Not(predicate) => !predicate.eval(elem),
+            Predicate::And(left, right) => left.eval(elem.clone()) && right.eval(elem),
+            Predicate::Or(left, right) => left.eval(elem.clone()) || right.eval(elem),
+        }
+    }
+}
+
+impl Operand {
+    // a path must select exactly one value to be compared
+    pub fn eval(&self, elem: &serde_json::Value) -> Option<serde_json::Value> {
+        match self {
+            Operand::Current(selectors) => {
+                let mut results = Query { selectors: selectors.clone() }.eval(elem.clone());
+                if results.len() == 1 {
+                    results.pop()
+                } else {
+                    None
+                }
+            }
+            Operand::Literal(value) => Some(value.to_json()),
+        }
+    }
+}
+
+impl Literal {
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Literal::Null => serde_json::Value::Null,
+            Literal::Bool(value) => serde_json::Value::Bool(*value),
+            Literal::Number(num) => match serde_json::Number::from_f64(num.to_f64()) {
+                Some(n) => serde_json::Value::Number(n),
+                None => serde_json::Value::Null,
+            },
+            Literal::String(s) => serde_json::Value::String(s.clone()),
         }
     }
 }
 
+impl CompareOp {
+    // ordering is only defined between two numbers or two strings
+    pub fn eval(&self, left: &serde_json::Value, right: &serde_json::Value) -> bool {
+        let ordering = match (left, right) {
+            (serde_json::Value::Number(a), serde_json::Value::Number(b)) => a.as_f64().partial_cmp(&b.as_f64()),
+            (serde_json::Value::String(a), serde_json::Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+        match self {
+            CompareOp::Equal => json_equal(left, right),
+            CompareOp::NotEqual => !json_equal(left, right),
+            CompareOp::GreaterThan => ordering == Some(Ordering::Greater),
+            CompareOp::GreaterThanOrEqual => ordering == Some(Ordering::Greater) || ordering == Some(Ordering::Equal),
+            CompareOp::LessThan => ordering == Some(Ordering::Less),
+            CompareOp::LessThanOrEqual => ordering == Some(Ordering::Less) || ordering == Some(Ordering::Equal),
+        }
+    }
+}
+
+fn json_equal(left: &serde_json::Value, right: &serde_json::Value) -> bool {
+    match (left, right) {
+        (serde_json::Value::Number(a), serde_json::Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+            (Some(a), Some(b)) => approx_eq!(f64, a, b, ulps = 2),
+            _ => false,
+        },
+        _ => left == right,
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -194,10 +344,7 @@ mod tests {
             selectors: vec![
                 Selector::NameChild("store".to_string()),
                 Selector::NameChild("book".to_string()),
-                Selector::Filter(Predicate {
-                    key: "price".to_string(),
-                    func: PredicateFunc::LessThan(Number { int: 10, decimal: 0 }),
-                }),
+                Selector::Filter(compare(key("price"), CompareOp::LessThan, number(10))),
                 Selector::NameChild("title".to_string()),
             ]
         };
@@ -262,36 +409,223 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_wildcard() {
+        let value = json!(["string", 42, {"key": "value"}, [0, 1]]);
+        assert_eq!(Selector::Wildcard.eval(value.clone()),
+                   vec![json!("string"), json!(42), json!({"key": "value"}), json!([0, 1])]
+        );
+        assert_eq!(Selector::Wildcard.eval(json!({"some": "string", "int": 42})),
+                   vec![json!(42), json!("string")]
+        );
+        assert_eq!(Selector::Wildcard.eval(json!("string")), Vec::<serde_json::Value>::new());
+
+        // $[*].key
+        let query = Query { selectors: vec![Selector::Wildcard, Selector::NameChild("key".to_string())] };
+        assert_eq!(query.eval(value), vec![json!("value")]);
+    }
+
+    #[test]
+    pub fn test_negative_array_index() {
+        let value = json!(["first", "second", "third"]);
+        assert_eq!(Selector::ArrayIndex(-1).eval(value.clone()), vec![json!("third")]);
+        assert_eq!(Selector::ArrayIndex(-3).eval(value.clone()), vec![json!("first")]);
+        assert_eq!(Selector::ArrayIndex(-4).eval(value.clone()), Vec::<serde_json::Value>::new());
+        assert_eq!(Selector::ArrayIndex(3).eval(value), Vec::<serde_json::Value>::new());
+        assert_eq!(Selector::ArrayIndex(0).eval(json!({"0": "value"})), Vec::<serde_json::Value>::new());
+    }
+
+    #[test]
+    pub fn test_array_slice() {
+        let value = json!(["first", "second", "third", "forth", "fifth"]);
+        let slice = |start, end, step| Selector::ArraySlice(Slice { start, end, step });
+
+        // $[1:3]
+        assert_eq!(slice(Some(1), Some(3), None).eval(value.clone()), vec![json!("second"), json!("third")]);
+        // $[:2]
+        assert_eq!(slice(None, Some(2), None).eval(value.clone()), vec![json!("first"), json!("second")]);
+        // $[-2:]
+        assert_eq!(slice(Some(-2), None, None).eval(value.clone()), vec![json!("forth"), json!("fifth")]);
+        // $[1:10]
+        assert_eq!(slice(Some(1), Some(10), None).eval(value.clone()).len(), 4);
+        // $[0:5:2]
+        assert_eq!(slice(Some(0), Some(5), Some(2)).eval(value.clone()), vec![json!("first"), json!("third"), json!("fifth")]);
+        // $[::-1]
+        assert_eq!(slice(None, None, Some(-1)).eval(value.clone()),
+                   vec![json!("fifth"), json!("forth"), json!("third"), json!("second"), json!("first")]);
+        // $[3:0:-2]
+        assert_eq!(slice(Some(3), Some(0), Some(-2)).eval(value.clone()), vec![json!("forth"), json!("second")]);
+        // $[2:1]
+        assert_eq!(slice(Some(2), Some(1), None).eval(value.clone()), Vec::<serde_json::Value>::new());
+        // $[0:3:0]
+        assert_eq!(slice(Some(0), Some(3), Some(0)).eval(value.clone()), Vec::<serde_json::Value>::new());
+        // $[1:5:9223372036854775807]
+        assert_eq!(slice(Some(1), Some(5), Some(i64::MAX)).eval(value.clone()), vec![json!("second")]);
+        // $[3::-9223372036854775808]
+        assert_eq!(slice(Some(3), None, Some(i64::MIN)).eval(value), vec![json!("forth")]);
+        assert_eq!(slice(None, None, None).eval(json!({":": 42})), Vec::<serde_json::Value>::new());
+    }
+
+    #[test]
+    pub fn test_union() {
+        // $['key','another']
+        let selector = Selector::Union(vec![
+            Selector::NameChild("key".to_string()),
+            Selector::NameChild("another".to_string()),
+        ]);
+        assert_eq!(selector.eval(json!({"key": "value", "another": "entry"})),
+                   vec![json!("value"), json!("entry")]
+        );
+
+        // $[0,0]
+        let selector = Selector::Union(vec![Selector::ArrayIndex(0), Selector::ArrayIndex(0)]);
+        assert_eq!(selector.eval(json!(["a"])), vec![json!("a"), json!("a")]);
+    }
+
+    #[test]
+    pub fn test_recursive_wildcard() {
+        // $..*
+        let value = json!({"key": "value", "another key": {"complex": ["a", 1]}});
+        assert_eq!(Selector::RecursiveWildcard.eval(value),
+                   vec![
+                       json!({"complex": ["a", 1]}),
+                       json!(["a", 1]),
+                       json!("a"),
+                       json!(1),
+                       json!("value"),
+                   ]
+        );
+        assert_eq!(Selector::RecursiveWildcard.eval(json!(42)), Vec::<serde_json::Value>::new());
+    }
+
+    #[test]
+    pub fn test_recursive_descent() {
+        // $..[0]
+        let selector = Selector::RecursiveDescent(Box::new(Selector::ArrayIndex(0)));
+        assert_eq!(selector.eval(json!(["first", {"key": ["first nested", ["deepest"]]}])),
+                   vec![json!("first"), json!("first nested"), json!("deepest")]
+        );
+        // $..['key']
+        let selector = Selector::RecursiveDescent(Box::new(Selector::NameChild("key".to_string())));
+        assert_eq!(selector.eval(json!({"key": 1, "other": [{"key": 2}]})), vec![json!(1), json!(2)]);
+        // $..[?(@.id==2)], filters only apply to arrays
+        let selector = Selector::RecursiveDescent(Box::new(Selector::Filter(
+            compare(key("id"), CompareOp::Equal, number(2))
+        )));
+        assert_eq!(selector.eval(json!({"id": 2, "more": [{"id": 2}, {"id": 3}, [{"id": 2}]]})), vec![json!({"id": 2}), json!({"id": 2})]);
+    }
+
+    fn key(name: &str) -> Operand {
+        Operand::Current(vec![Selector::NameChild(name.to_string())])
+    }
+
+    fn compare(left: Operand, op: CompareOp, right: Operand) -> Predicate {
+        Predicate::Compare { left, op, right }
+    }
+
+    fn number(int: i64) -> Operand {
+        Operand::Literal(Literal::Number(Number { int, decimal: 0, negative: int < 0 }))
+    }
+
+    fn string(s: &str) -> Operand {
+        Operand::Literal(Literal::String(s.to_string()))
+    }
+
     #[test]
     pub fn test_predicate() {
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::EqualString("value".to_string()),
-        }.eval(json!({"key": "value"})), true);
-
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::EqualString("value".to_string()),
-        }.eval(json!({"key": "some"})), false);
-
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::Equal(Number { int: 1, decimal: 0 }),
-        }.eval(json!({"key": 1})), true);
-
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::Equal(Number { int: 1, decimal: 0 }),
-        }.eval(json!({"key": 2})), false);
-
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::Equal(Number { int: 1, decimal: 0 }),
-        }.eval(json!({"key": "1"})), false);
-
-        assert_eq!(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::LessThan(Number { int: 10, decimal: 0 }),
-        }.eval(json!({"key": 1})), true);
+        assert_eq!(compare(key("key"), CompareOp::Equal, string("value")).eval(json!({"key": "value"})), true);
+        assert_eq!(compare(key("key"), CompareOp::Equal, string("value")).eval(json!({"key": "some"})), false);
+        assert_eq!(compare(key("key"), CompareOp::Equal, number(1)).eval(json!({"key": 1})), true);
+        assert_eq!(compare(key("key"), CompareOp::Equal, number(1)).eval(json!({"key": 2})), false);
+        assert_eq!(compare(key("key"), CompareOp::Equal, number(1)).eval(json!({"key": "1"})), false);
+        assert_eq!(compare(key("key"), CompareOp::LessThan, number(10)).eval(json!({"key": 1})), true);
+        assert_eq!(compare(key("key"), CompareOp::NotEqual, number(1)).eval(json!({"key": "1"})), true);
+        assert_eq!(compare(key("key"), CompareOp::NotEqual, number(1)).eval(json!({})), false);
+        assert_eq!(compare(key("key"), CompareOp::GreaterThan, string("a")).eval(json!({"key": "b"})), true);
+        assert_eq!(compare(key("key"), CompareOp::GreaterThan, string("a")).eval(json!({"key": 1})), false);
+        assert_eq!(compare(key("key"), CompareOp::Equal, Operand::Literal(Literal::Null)).eval(json!({"key": null})), true);
+        assert_eq!(compare(key("key"), CompareOp::Equal, Operand::Literal(Literal::Bool(false))).eval(json!({"key": false})), true);
+        assert_eq!(compare(Operand::Current(vec![]), CompareOp::GreaterThanOrEqual, number(2)).eval(json!(2)), true);
+
+        // nested path and comparison between two fields
+        let nested = Operand::Current(vec![Selector::NameChild("author".to_string()), Selector::NameChild("name".to_string())]);
+        assert_eq!(compare(nested.clone(), CompareOp::Equal, string("Bob")).eval(json!({"author": {"name": "Bob"}})), true);
+        assert_eq!(compare(nested, CompareOp::Equal, key("editor")).eval(json!({"author": {"name": "Bob"}, "editor": "Bob"})), true);
+        assert_eq!(compare(key("min"), CompareOp::LessThan, key("max")).eval(json!({"min": 3, "max": 2})), false);
+    }
+
+    #[test]
+    pub fn test_predicate_boolean() {
+        let exist = Predicate::Exist(vec![Selector::NameChild("isbn".to_string())]);
+        assert_eq!(exist.eval(json_third_book()), true);
+        assert_eq!(exist.eval(json_first_book()), false);
+        assert_eq!(Predicate::Not(Box::new(exist)).eval(json_first_book()), true);
+
+        let cheap_fiction = Predicate::And(
+            Box::new(compare(key("price"), CompareOp::LessThan, number(10))),
+            Box::new(compare(key("category"), CompareOp::Equal, string("fiction"))),
+        );
+        assert_eq!(cheap_fiction.eval(json_third_book()), true);
+        assert_eq!(cheap_fiction.eval(json_first_book()), false);
+        assert_eq!(cheap_fiction.eval(json_second_book()), false);
+
+        let predicate = Predicate::Or(
+            Box::new(compare(key("price"), CompareOp::GreaterThan, number(20))),
+            Box::new(compare(key("category"), CompareOp::Equal, string("reference"))),
+        );
+        assert_eq!(predicate.eval(json_first_book()), true);
+        assert_eq!(predicate.eval(json_second_book()), false);
+        assert_eq!(predicate.eval(json_fourth_book()), true);
+    }
+
+    #[test]
+    pub fn test_predicate_in_match() {
+        let predicate = Predicate::In {
+            value: key("price"),
+            values: vec![Literal::Number(Number { int: 8, decimal: 950_000_000_000_000_000, negative: false }), Literal::String("free".to_string())],
+        };
+        assert_eq!(predicate.eval(json_first_book()), true);
+        assert_eq!(predicate.eval(json_second_book()), false);
+        assert_eq!(predicate.eval(json!({"price": "free"})), true);
+
+        let regex = Pattern { value: "(?i)^j\\. r".to_string(), inner: regex::Regex::new("(?i)^j\\. r").unwrap() };
+        let predicate = Predicate::Match { value: key("author"), regex };
+        assert_eq!(predicate.eval(json_fourth_book()), true);
+        assert_eq!(predicate.eval(json_first_book()), false);
+        assert_eq!(predicate.eval(json!({"author": 1})), false);
+    }
+
+    #[test]
+    pub fn test_filter_expression() {
+        // $.store.book[?(@.price < 10 && @.category == 'fiction')].title
+        let query = Query {
+            selectors: vec![
+                Selector::NameChild("store".to_string()),
+                Selector::NameChild("book".to_string()),
+                Selector::Filter(Predicate::And(
+                    Box::new(compare(key("price"), CompareOp::LessThan, number(10))),
+                    Box::new(compare(key("category"), CompareOp::Equal, string("fiction"))),
+                )),
+                Selector::NameChild("title".to_string()),
+            ]
+        };
+        assert_eq!(query.eval(json_root()), vec![json!("Moby Dick")]);
+    }
+
+    #[test]
+    pub fn test_filter_negative_decimal() {
+        let values = json!([{"x": -2}, {"x": -1.5}, {"x": -0.5}, {"x": 0.5}]);
+
+        // $[?(@.x < -1.5)]
+        let minus_one_and_half = Operand::Literal(Literal::Number(Number { int: -1, decimal: 500_000_000_000_000_000, negative: true }));
+        let selector = Selector::Filter(compare(key("x"), CompareOp::LessThan, minus_one_and_half));
+        assert_eq!(selector.eval(values.clone()), vec![json!({"x": -2})]);
+
+        // $[?(@.x in [-0.5])]
+        let selector = Selector::Filter(Predicate::In {
+            value: key("x"),
+            values: vec![Literal::Number(Number { int: 0, decimal: 500_000_000_000_000_000, negative: true })],
+        });
+        assert_eq!(selector.eval(values), vec![json!({"x": -0.5})]);
     }
 }
@@ -27,7 +27,7 @@ pub fn parse(s: &str) -> Result<Query, Error> {
     query(&mut reader)
 }
 
-fn query(reader: &mut Reader) -> ParseResult<Query> {
+fn query(reader: &mut Reader) -> ParseResult<'_, Query> {
     literal("$", reader)?;
 
 
@@ -44,28 +44,76 @@ fn query(reader: &mut Reader) -> ParseResult<Query> {
     })
 }
 
-fn selector(reader: &mut Reader) -> ParseResult<Selector> {
+fn selector(reader: &mut Reader) -> ParseResult<'_, Selector> {
     choice(vec![
+        selector_recursive_wildcard,
+        selector_recursive_bracket,
         selector_recursive_key,
-        selector_array_index,
-        selector_object_key_bracket,
+        selector_wildcard,
+        selector_array_slice,
+        selector_union,
         selector_object_key,
         selector_filter,
     ], reader)
 }
 
-fn selector_array_index(reader: &mut Reader) -> Result<Selector, Error> {
-    try_literal("[", reader)?;
-    let i = match natural(reader) {
-        Err(e) => return Err(Error {
-            pos: e.pos,
+fn selector_wildcard(reader: &mut Reader) -> Result<Selector, Error> {
+    if reader.try_literal(".*") || reader.try_literal("[*]") {
+        whitespace(reader);
+        Ok(Selector::Wildcard)
+    } else {
+        Err(Error {
+            pos: reader.state.pos.clone(),
             recoverable: true,
-            inner: e.inner,
-        }),
-        Ok(v) => v
+            inner: ParseError::Expecting { value: ".* or [*]".to_string() },
+        })
+    }
+}
+
+fn selector_array_slice(reader: &mut Reader) -> Result<Selector, Error> {
+    try_literal("[", reader)?;
+    let start = optional(slice_bound, reader)?;
+    try_literal(":", reader)?;
+    let end = optional(slice_bound, reader)?;
+    let step = if reader.try_literal(":") {
+        whitespace(reader);
+        optional(slice_bound, reader)?
+    } else {
+        None
     };
     literal("]", reader)?;
-    Ok(Selector::ArrayIndex(i))
+    Ok(Selector::ArraySlice(Slice { start, end, step }))
+}
+
+fn slice_bound(reader: &mut Reader) -> ParseResult<'_, i64> {
+    let i = integer(reader)?;
+    whitespace(reader);
+    Ok(i)
+}
+
+// an index, a key or a comma-separated list of them
+// ['key'], [0], ['key1','key2'], [0,-1]
+fn selector_union(reader: &mut Reader) -> Result<Selector, Error> {
+    try_literal("[", reader)?;
+    let mut selectors = vec![union_element(reader)?];
+    while reader.try_literal(",") {
+        whitespace(reader);
+        selectors.push(nonrecover(union_element, reader)?);
+    }
+    literal("]", reader)?;
+    if selectors.len() == 1 {
+        Ok(selectors.pop().unwrap())
+    } else {
+        Ok(Selector::Union(selectors))
+    }
+}
+
+fn union_element(reader: &mut Reader) -> ParseResult<'_, Selector> {
+    if let Some(i) = optional(slice_bound, reader)? {
+        return Ok(Selector::ArrayIndex(i));
+    }
+    let s = string_value(reader)?;
+    Ok(Selector::NameChild(s))
 }
 
 fn selector_filter(reader: &mut Reader) -> Result<Selector, Error> {
@@ -75,125 +123,242 @@ fn selector_filter(reader: &mut Reader) -> Result<Selector, Error> {
     Ok(Selector::Filter(pred))
 }
 
-fn selector_object_key_bracket(reader: &mut Reader) -> Result<Selector, Error> {
-    try_literal("[", reader)?;
-    match string_value(reader) {
-        Err(_) => Err(Error {
-            pos: reader.state.pos.clone(),
-            recoverable: true,
-            inner: ParseError::Expecting { value: "value string".to_string() },
-        }),
-        Ok(v) => {
-            literal("]", reader)?;
-            Ok(Selector::NameChild(v))
-        }
-    }
-}
-
 fn selector_object_key(reader: &mut Reader) -> Result<Selector, Error> {
-    let end_delim = if reader.try_literal("['") {
-        "']".to_string()
-    } else if reader.try_literal(".") {
-        "".to_string()
-    } else {
+    try_literal(".", reader)?;
+    let s = key_name(reader)?;
+    if s.is_empty() {
         return Err(Error {
             pos: reader.state.pos.clone(),
-            recoverable: true,
-            inner: ParseError::Expecting { value: "[ or .".to_string() },
+            recoverable: false,
+            inner: ParseError::Expecting { value: "empty value".to_string() },
         });
-    };
+    }
+    Ok(Selector::NameChild(s))
+}
 
-    let s = reader.read_while(|c| c.is_alphanumeric());
-    if s.is_empty() {
+fn selector_recursive_wildcard(reader: &mut Reader) -> Result<Selector, Error> {
+    try_literal("..*", reader)?;
+    Ok(Selector::RecursiveWildcard)
+}
+
+fn selector_recursive_bracket(reader: &mut Reader) -> Result<Selector, Error> {
+    try_literal("..", reader)?;
+    if reader.peek() != Some('[') {
         return Err(Error {
             pos: reader.state.pos.clone(),
-            recoverable: false,
-            inner: ParseError::Expecting { value: "empty value".to_string() },
+            recoverable: true,
+            inner: ParseError::Expecting { value: "[".to_string() },
         });
     }
-    if !end_delim.is_empty() {
-        literal(end_delim.as_str(), reader)?;
+    let selector = nonrecover(|p| choice(vec![
+        selector_wildcard,
+        selector_array_slice,
+        selector_union,
+        selector_filter,
+    ], p), reader)?;
+    if selector == Selector::Wildcard {
+        return Ok(Selector::RecursiveWildcard);
     }
-
-    Ok(Selector::NameChild(s))
+    Ok(Selector::RecursiveDescent(Box::new(selector)))
 }
 
 fn selector_recursive_key(reader: &mut Reader) -> Result<Selector, Error> {
     try_literal("..", reader)?;
     let k = key_name(reader)?;
+    if k.is_empty() {
+        return Err(Error {
+            pos: reader.state.pos.clone(),
+            recoverable: false,
+            inner: ParseError::Expecting { value: "key name".to_string() },
+        });
+    }
     Ok(Selector::RecursiveKey(k))
 }
 
-fn predicate(reader: &mut Reader) -> ParseResult<Predicate> {
-    // predicate always on key?
-    // TODO parsing key-value
-    // ?(@.key=='value')
-    // @<3 => assume number => plan it in your ast => ValueEqualInt should be used for that
-    // KeyValueEqualInt
-
-    // @.key          Exist(Key)
-    // @.key==value   Equal(Key,Value)
-    // @.key>=value   GreaterThanOrEqual(Key, Value)
-    literal("@.", reader)?;  // assume key value for the time being
-    let key = key_name(reader)?;
-    let func = predicate_func(reader)?;
-    Ok(Predicate { key, func })
+// predicate = and-predicate ("||" and-predicate)*
+fn predicate(reader: &mut Reader) -> ParseResult<'_, Predicate> {
+    whitespace(reader);
+    let mut left = and_predicate(reader)?;
+    while reader.try_literal("||") {
+        whitespace(reader);
+        let right = nonrecover(and_predicate, reader)?;
+        left = Predicate::Or(Box::new(left), Box::new(right));
+    }
+    Ok(left)
 }
 
-fn predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    choice(
-        vec![
-            equal_number_predicate_func,
-            greater_than_predicate_func,
-            greater_than_or_equal_predicate_func,
-            less_than_predicate_func,
-            less_than_or_equal_predicate_func,
-            equal_string_predicate_func
-        ],
-        reader,
-    )
+// and-predicate = unary-predicate ("&&" unary-predicate)*
+fn and_predicate(reader: &mut Reader) -> ParseResult<'_, Predicate> {
+    let mut left = unary_predicate(reader)?;
+    while reader.try_literal("&&") {
+        whitespace(reader);
+        let right = nonrecover(unary_predicate, reader)?;
+        left = Predicate::And(Box::new(left), Box::new(right));
+    }
+    Ok(left)
 }
 
-fn equal_number_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal("==", reader)?;
-    whitespace(reader);
-    let num = number(reader)?;
-    Ok(PredicateFunc::Equal(num))
+fn unary_predicate(reader: &mut Reader) -> ParseResult<'_, Predicate> {
+    if reader.try_literal("!") {
+        whitespace(reader);
+        let pred = nonrecover(unary_predicate, reader)?;
+        return Ok(Predicate::Not(Box::new(pred)));
+    }
+    if reader.try_literal("(") {
+        let pred = predicate(reader)?;
+        literal(")", reader)?;
+        return Ok(pred);
+    }
+    comparison(reader)
 }
 
-fn greater_than_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal(">", reader)?;
-    whitespace(reader);
-    let num = number(reader)?;
-    Ok(PredicateFunc::GreaterThan(num))
+// @.key                  Exist
+// @.key == value         Compare
+// @.key in [v1, v2]      In
+// @.key =~ /regex/i      Match
+fn comparison(reader: &mut Reader) -> ParseResult<'_, Predicate> {
+    let left = operand(reader)?;
+    if let Some(op) = optional(compare_op, reader)? {
+        let right = nonrecover(operand, reader)?;
+        return Ok(Predicate::Compare { left, op, right });
+    }
+    if reader.try_literal("=~") {
+        whitespace(reader);
+        let regex = filter_regex(reader)?;
+        return Ok(Predicate::Match { value: left, regex });
+    }
+    if reader.try_literal("in") {
+        whitespace(reader);
+        let values = literal_list(reader)?;
+        return Ok(Predicate::In { value: left, values });
+    }
+    match left {
+        Operand::Current(selectors) => Ok(Predicate::Exist(selectors)),
+        Operand::Literal(_) => Err(Error {
+            pos: reader.state.pos.clone(),
+            recoverable: false,
+            inner: ParseError::Expecting { value: "operator".to_string() },
+        }),
+    }
 }
 
-fn greater_than_or_equal_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal(">=", reader)?;
+fn compare_op(reader: &mut Reader) -> ParseResult<'_, CompareOp> {
+    let op = if reader.try_literal("==") {
+        CompareOp::Equal
+    } else if reader.try_literal("!=") {
+        CompareOp::NotEqual
+    } else if reader.try_literal(">=") {
+        CompareOp::GreaterThanOrEqual
+    } else if reader.try_literal(">") {
+        CompareOp::GreaterThan
+    } else if reader.try_literal("<=") {
+        CompareOp::LessThanOrEqual
+    } else if reader.try_literal("<") {
+        CompareOp::LessThan
+    } else {
+        return Err(Error {
+            pos: reader.state.pos.clone(),
+            recoverable: true,
+            inner: ParseError::Expecting { value: "operator".to_string() },
+        });
+    };
     whitespace(reader);
-    let num = number(reader)?;
-    Ok(PredicateFunc::GreaterThanOrEqual(num))
+    Ok(op)
 }
 
-fn less_than_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal("<", reader)?;
-    whitespace(reader);
-    let num = number(reader)?;
-    Ok(PredicateFunc::LessThan(num))
+fn operand(reader: &mut Reader) -> ParseResult<'_, Operand> {
+    if reader.try_literal("@") {
+        let selectors = zero_or_more(selector, reader)?;
+        whitespace(reader);
+        return Ok(Operand::Current(selectors));
+    }
+    let value = filter_literal(reader)?;
+    Ok(Operand::Literal(value))
 }
 
-fn less_than_or_equal_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal("<=", reader)?;
+fn filter_literal(reader: &mut Reader) -> ParseResult<'_, Literal> {
+    let value = if reader.try_literal("null") {
+        Literal::Null
+    } else if reader.try_literal("true") {
+        Literal::Bool(true)
+    } else if reader.try_literal("false") {
+        Literal::Bool(false)
+    } else if let Some(s) = optional(string_value, reader)? {
+        Literal::String(s)
+    } else {
+        match number(reader) {
+            Ok(num) => Literal::Number(num),
+            Err(e) => return Err(Error {
+                pos: e.pos,
+                recoverable: e.recoverable,
+                inner: ParseError::Expecting { value: "value".to_string() },
+            }),
+        }
+    };
     whitespace(reader);
-    let num = number(reader)?;
-    Ok(PredicateFunc::LessThanOrEqual(num))
+    Ok(value)
+}
+
+fn literal_list(reader: &mut Reader) -> ParseResult<'_, Vec<Literal>> {
+    literal("[", reader)?;
+    let mut values = vec![];
+    if !reader.try_literal("]") {
+        values.push(nonrecover(filter_literal, reader)?);
+        while reader.try_literal(",") {
+            whitespace(reader);
+            values.push(nonrecover(filter_literal, reader)?);
+        }
+        literal("]", reader)?;
+    } else {
+        whitespace(reader);
+    }
+    Ok(values)
 }
 
-fn equal_string_predicate_func(reader: &mut Reader) -> ParseResult<PredicateFunc> {
-    try_literal("==", reader)?;
+// /pattern/flags
+// the flags are converted to an inline group (?flags)
+fn filter_regex(reader: &mut Reader) -> ParseResult<'_, Pattern> {
+    let start = reader.state.pos.clone();
+    if !reader.try_literal("/") {
+        return Err(Error {
+            pos: start,
+            recoverable: false,
+            inner: ParseError::Expecting { value: "/".to_string() },
+        });
+    }
+    let mut pattern = String::from("");
+    loop {
+        match reader.read() {
+            None => return Err(Error {
+                pos: reader.state.pos.clone(),
+                recoverable: false,
+                inner: ParseError::Expecting { value: "/".to_string() },
+            }),
+            Some('/') => break,
+            Some('\\') => match reader.read() {
+                Some('/') => pattern.push('/'),
+                Some(c) => {
+                    pattern.push('\\');
+                    pattern.push(c);
+                }
+                None => {}
+            },
+            Some(c) => pattern.push(c),
+        }
+    }
+    let flags = reader.read_while(|c| c.is_alphabetic());
     whitespace(reader);
-    let s = string_value(reader)?;
-    Ok(PredicateFunc::EqualString(s))
+    if !flags.is_empty() {
+        pattern = format!("(?{}){}", flags, pattern);
+    }
+    match regex::Regex::new(pattern.as_str()) {
+        Ok(inner) => Ok(Pattern { value: pattern, inner }),
+        Err(_) => Err(Error {
+            pos: start,
+            recoverable: false,
+            inner: ParseError::Expecting { value: "valid regex".to_string() },
+        }),
+    }
 }
 
 
@@ -279,9 +444,10 @@ mod tests {
 
         // Filter equal on string with single quotes
         let mut reader = Reader::init("[?(@.key=='value')]");
-        assert_eq!(selector(&mut reader).unwrap(), Selector::Filter(Predicate {
-            key: "key".to_string(),
-            func: PredicateFunc::EqualString("value".to_string()),
+        assert_eq!(selector(&mut reader).unwrap(), Selector::Filter(Predicate::Compare {
+            left: Operand::Current(vec![Selector::NameChild("key".to_string())]),
+            op: CompareOp::Equal,
+            right: Operand::Literal(Literal::String("value".to_string())),
         }));
         assert_eq!(reader.state.cursor, 19);
 
@@ -290,56 +456,214 @@ mod tests {
         assert_eq!(reader.state.cursor, 6);
     }
 
+    #[test]
+    pub fn test_selector_wildcard_slice_union() {
+        let mut reader = Reader::init(".*");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::Wildcard);
+        assert_eq!(reader.state.cursor, 2);
+
+        let mut reader = Reader::init("[*]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::Wildcard);
+        assert_eq!(reader.state.cursor, 3);
+
+        let mut reader = Reader::init("..*");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::RecursiveWildcard);
+        assert_eq!(reader.state.cursor, 3);
+
+        let mut reader = Reader::init("..[*]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::RecursiveWildcard);
+        assert_eq!(reader.state.cursor, 5);
+
+        let mut reader = Reader::init("..[0]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::RecursiveDescent(Box::new(Selector::ArrayIndex(0))));
+        assert_eq!(reader.state.cursor, 5);
+
+        let mut reader = Reader::init("..[?(@.id)]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::RecursiveDescent(Box::new(Selector::Filter(
+            Predicate::Exist(vec![Selector::NameChild("id".to_string())])
+        ))));
+        assert_eq!(reader.state.cursor, 11);
+
+        let mut reader = Reader::init("..[");
+        assert!(!selector(&mut reader).err().unwrap().recoverable);
+
+        let mut reader = Reader::init("[-1]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::ArrayIndex(-1));
+        assert_eq!(reader.state.cursor, 4);
+
+        let mut reader = Reader::init("[0:3]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::ArraySlice(Slice { start: Some(0), end: Some(3), step: None }));
+        assert_eq!(reader.state.cursor, 5);
+
+        let mut reader = Reader::init("[-2:]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::ArraySlice(Slice { start: Some(-2), end: None, step: None }));
+
+        let mut reader = Reader::init("[::2]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::ArraySlice(Slice { start: None, end: None, step: Some(2) }));
+        assert_eq!(reader.state.cursor, 5);
+
+        let mut reader = Reader::init("['a','b']");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::Union(vec![
+            Selector::NameChild("a".to_string()),
+            Selector::NameChild("b".to_string()),
+        ]));
+        assert_eq!(reader.state.cursor, 9);
+
+        let mut reader = Reader::init("[0, -1]");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::Union(vec![
+            Selector::ArrayIndex(0),
+            Selector::ArrayIndex(-1),
+        ]));
+        assert_eq!(reader.state.cursor, 7);
+
+        let mut reader = Reader::init("['a.b c']");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::NameChild("a.b c".to_string()));
+
+        let mut reader = Reader::init(r#"["it's"]"#);
+        assert_eq!(selector(&mut reader).unwrap(), Selector::NameChild("it's".to_string()));
+
+        let mut reader = Reader::init(".key-with_dash1");
+        assert_eq!(selector(&mut reader).unwrap(), Selector::NameChild("key-with_dash1".to_string()));
+    }
+
+    #[test]
+    pub fn test_selector_error() {
+        let error = selector(&mut Reader::init("['a',]")).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 6 });
+        assert_eq!(error.recoverable, false);
+
+        let error = selector(&mut Reader::init("[1:2")).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 5 });
+        assert_eq!(error.inner, ParseError::Expecting { value: "]".to_string() });
+
+        let error = selector(&mut Reader::init("..")).err().unwrap();
+        assert_eq!(error.inner, ParseError::Expecting { value: "key name".to_string() });
+    }
+
+    fn key(name: &str) -> Operand {
+        Operand::Current(vec![Selector::NameChild(name.to_string())])
+    }
+
+    fn compare(left: Operand, op: CompareOp, right: Operand) -> Predicate {
+        Predicate::Compare { left, op, right }
+    }
+
+    fn number(int: i64) -> Operand {
+        Operand::Literal(Literal::Number(Number { int, decimal: 0, negative: int < 0 }))
+    }
+
     #[test]
     pub fn test_predicate() {
 
         // Filter equal on string with single quotes
         assert_eq!(predicate(&mut Reader::init("@.key=='value'")).unwrap(),
-                   Predicate {
-                       key: "key".to_string(),
-                       func: PredicateFunc::EqualString("value".to_string()),
-                   });
+                   compare(key("key"), CompareOp::Equal, Operand::Literal(Literal::String("value".to_string())))
+        );
 
         // Filter equal on int
         assert_eq!(predicate(&mut Reader::init("@.key==1")).unwrap(),
-                   Predicate {
-                       key: "key".to_string(),
-                       func: PredicateFunc::Equal(Number { int: 1, decimal: 0 }),
-                   });
+                   compare(key("key"), CompareOp::Equal, number(1))
+        );
+
+        // Key exists
+        assert_eq!(predicate(&mut Reader::init("@.isbn")).unwrap(),
+                   Predicate::Exist(vec![Selector::NameChild("isbn".to_string())])
+        );
+
+        // Nested path compared with another field
+        assert_eq!(predicate(&mut Reader::init("@.author.name != @['editor']")).unwrap(),
+                   compare(
+                       Operand::Current(vec![
+                           Selector::NameChild("author".to_string()),
+                           Selector::NameChild("name".to_string()),
+                       ]),
+                       CompareOp::NotEqual,
+                       key("editor"),
+                   )
+        );
+
+        // Current element
+        assert_eq!(predicate(&mut Reader::init("@ >= 2.1")).unwrap(),
+                   compare(
+                       Operand::Current(vec![]),
+                       CompareOp::GreaterThanOrEqual,
+                       Operand::Literal(Literal::Number(Number { int: 2, decimal: 100_000_000_000_000_000, negative: false })),
+                   )
+        );
+
+        // Literals
+        assert_eq!(predicate(&mut Reader::init("@.a == true || @.a == null")).unwrap(),
+                   Predicate::Or(
+                       Box::new(compare(key("a"), CompareOp::Equal, Operand::Literal(Literal::Bool(true)))),
+                       Box::new(compare(key("a"), CompareOp::Equal, Operand::Literal(Literal::Null))),
+                   )
+        );
     }
 
     #[test]
-    pub fn test_predicate_func() {
-        let mut reader = Reader::init("==2");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::Equal(Number { int: 2, decimal: 0 }));
-        assert_eq!(reader.state.cursor, 3);
-
-        let mut reader = Reader::init("==2.1");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::Equal(Number { int: 2, decimal: 100_000_000_000_000_000 }));
-        assert_eq!(reader.state.cursor, 5);
-
-        let mut reader = Reader::init("== 2.1 ");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::Equal(Number { int: 2, decimal: 100_000_000_000_000_000 }));
-        assert_eq!(reader.state.cursor, 7);
+    pub fn test_predicate_boolean() {
+        // && binds tighter than ||
+        assert_eq!(predicate(&mut Reader::init("@.a<1 || @.b>2 && !@.c")).unwrap(),
+                   Predicate::Or(
+                       Box::new(compare(key("a"), CompareOp::LessThan, number(1))),
+                       Box::new(Predicate::And(
+                           Box::new(compare(key("b"), CompareOp::GreaterThan, number(2))),
+                           Box::new(Predicate::Not(Box::new(Predicate::Exist(vec![Selector::NameChild("c".to_string())])))),
+                       )),
+                   )
+        );
+
+        let mut reader = Reader::init("!(@.a<=1 || @.b) && @.c)]");
+        assert_eq!(predicate(&mut reader).unwrap(),
+                   Predicate::And(
+                       Box::new(Predicate::Not(Box::new(Predicate::Or(
+                           Box::new(compare(key("a"), CompareOp::LessThanOrEqual, number(1))),
+                           Box::new(Predicate::Exist(vec![Selector::NameChild("b".to_string())])),
+                       )))),
+                       Box::new(Predicate::Exist(vec![Selector::NameChild("c".to_string())])),
+                   )
+        );
+        assert_eq!(reader.state.cursor, 23);
+    }
 
-        let mut reader = Reader::init("=='hello'");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::EqualString("hello".to_string()));
-        assert_eq!(reader.state.cursor, 9);
+    #[test]
+    pub fn test_predicate_in_match() {
+        assert_eq!(predicate(&mut Reader::init("@.category in ['fiction', 'poetry']")).unwrap(),
+                   Predicate::In {
+                       value: key("category"),
+                       values: vec![
+                           Literal::String("fiction".to_string()),
+                           Literal::String("poetry".to_string()),
+                       ],
+                   }
+        );
+        assert_eq!(predicate(&mut Reader::init("@.a in []")).unwrap(),
+                   Predicate::In { value: key("a"), values: vec![] }
+        );
+
+        assert_eq!(predicate(&mut Reader::init("@.author =~ /^J\\. R\\/R/i")).unwrap(),
+                   Predicate::Match {
+                       value: key("author"),
+                       regex: Pattern { value: "(?i)^J\\. R/R".to_string(), inner: regex::Regex::new("(?i)^J\\. R/R").unwrap() },
+                   }
+        );
+    }
 
-        let mut reader = Reader::init(">5");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::GreaterThan(Number { int: 5, decimal: 0 }));
-        assert_eq!(reader.state.cursor, 2);
+    #[test]
+    pub fn test_predicate_error() {
+        let error = predicate(&mut Reader::init("@.a == ")).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 8 });
+        assert_eq!(error.inner, ParseError::Expecting { value: "value".to_string() });
+        assert_eq!(error.recoverable, false);
 
-        let mut reader = Reader::init(">=5");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::GreaterThanOrEqual(Number { int: 5, decimal: 0 }));
-        assert_eq!(reader.state.cursor, 3);
+        let error = predicate(&mut Reader::init("1")).err().unwrap();
+        assert_eq!(error.inner, ParseError::Expecting { value: "operator".to_string() });
 
-        let mut reader = Reader::init("<5");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::LessThan(Number { int: 5, decimal: 0 }));
-        assert_eq!(reader.state.cursor, 2);
+        let error = predicate(&mut Reader::init("@.a =~ /[a/")).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 8 });
+        assert_eq!(error.inner, ParseError::Expecting { value: "valid regex".to_string() });
 
-        let mut reader = Reader::init("<=5");
-        assert_eq!(predicate_func(&mut reader).unwrap(), PredicateFunc::LessThanOrEqual(Number { int: 5, decimal: 0 }));
-        assert_eq!(reader.state.cursor, 3);
+        let error = predicate(&mut Reader::init("@.a && ")).err().unwrap();
+        assert_eq!(error.recoverable, false);
     }
 }
@@ -60,7 +60,9 @@ pub fn integer(reader: &mut Reader) -> ParseResult<'static, i64> {
 }
 
 pub fn number(reader: &mut Reader) -> ParseResult<'static, Number> {
-    let int = integer(reader)?;
+    let negative = reader.try_literal("-");
+    let nat = natural(reader)?;
+    let int = if negative { -(nat as i64) } else { nat as i64 };
 
     let decimal = if reader.try_literal(".") {
         if reader.is_eof() {
@@ -84,21 +86,53 @@ pub fn number(reader: &mut Reader) -> ParseResult<'static, Number> {
         0
     };
     whitespace(reader);
-    Ok(Number { int, decimal })
+    Ok(Number { int, decimal, negative })
 }
 
+// single or double quoted string
+// the delimiter and the backslash can be escaped
 pub fn string_value(reader: &mut Reader) -> Result<String, Error> {
-    try_literal("'", reader)?;
-    let s = reader.read_while(|c| *c != '\'');
-    literal("'", reader)?;
+    let delimiter = match reader.peek() {
+        Some('\'') => '\'',
+        Some('"') => '"',
+        _ => return Err(Error {
+            pos: reader.state.pos.clone(),
+            recoverable: true,
+            inner: ParseError::Expecting { value: String::from("'") },
+        })
+    };
+    reader.read();
+    let mut s = String::from("");
+    loop {
+        let pos = reader.state.pos.clone();
+        match reader.read() {
+            None => return Err(Error {
+                pos,
+                recoverable: false,
+                inner: ParseError::Expecting { value: delimiter.to_string() },
+            }),
+            Some('\\') => match reader.read() {
+                Some('n') => s.push('\n'),
+                Some('r') => s.push('\r'),
+                Some('t') => s.push('\t'),
+                Some(c) => s.push(c),
+                None => return Err(Error {
+                    pos: reader.state.pos.clone(),
+                    recoverable: false,
+                    inner: ParseError::Expecting { value: delimiter.to_string() },
+                }),
+            },
+            Some(c) if c == delimiter => break,
+            Some(c) => s.push(c),
+        }
+    }
     whitespace(reader);
     Ok(s)
 }
 
 pub fn key_name(reader: &mut Reader) -> Result<String, Error> {
-    // // test python or javascript
-    //// subset that can used for dot notation
-    let s = reader.read_while(|c| c.is_alphabetic()
+    // subset that can used for dot notation
+    let s = reader.read_while(|c| c.is_alphanumeric()
         || *c == '-'
         || *c == '_'
     );
@@ -226,35 +260,35 @@ mod tests {
     #[test]
     fn test_number() {
         let mut reader = Reader::init("1");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 0 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 0, negative: false });
         assert_eq!(reader.state.cursor, 1);
 
         let mut reader = Reader::init("1.0");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 0 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 0, negative: false });
         assert_eq!(reader.state.cursor, 3);
 
         let mut reader = Reader::init("-1.0");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: -1, decimal: 0 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: -1, decimal: 0, negative: true });
         assert_eq!(reader.state.cursor, 4);
 
         let mut reader = Reader::init("1.1");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 100_000_000_000_000_000 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 100_000_000_000_000_000, negative: false });
         assert_eq!(reader.state.cursor, 3);
 
         let mut reader = Reader::init("1.100");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 100_000_000_000_000_000 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 100_000_000_000_000_000, negative: false });
         assert_eq!(reader.state.cursor, 5);
 
         let mut reader = Reader::init("1.01");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 10_000_000_000_000_000 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 10_000_000_000_000_000, negative: false });
         assert_eq!(reader.state.cursor, 4);
 
         let mut reader = Reader::init("1.010");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 10_000_000_000_000_000 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 1, decimal: 10_000_000_000_000_000, negative: false });
         assert_eq!(reader.state.cursor, 5);
 
         let mut reader = Reader::init("-0.333333333333333333");
-        assert_eq!(number(&mut reader).unwrap(), Number { int: 0, decimal: 333_333_333_333_333_333 });
+        assert_eq!(number(&mut reader).unwrap(), Number { int: 0, decimal: 333_333_333_333_333_333, negative: true });
         assert_eq!(reader.state.cursor, 21);
     }
 
@@ -304,6 +338,22 @@ mod tests {
         assert_eq!(error.recoverable, false);
     }
 
+    #[test]
+    fn test_string_value_escape() {
+        let mut reader = Reader::init(r#"'it\'s'"#);
+        assert_eq!(string_value(&mut reader).unwrap(), "it's".to_string());
+
+        let mut reader = Reader::init(r#""a\\b" "#);
+        assert_eq!(string_value(&mut reader).unwrap(), "a\\b".to_string());
+        assert_eq!(reader.state.cursor, 7);
+
+        let mut reader = Reader::init(r#""hi'"#);
+        let error = string_value(&mut reader).err().unwrap();
+        assert_eq!(error.inner, ParseError::Expecting { value: String::from("\"") });
+        assert_eq!(error.pos, Pos { line: 1, column: 5 });
+        assert_eq!(error.recoverable, false);
+    }
+
     #[test]
     fn test_key_name() {
         let mut reader = Reader::init("id'");
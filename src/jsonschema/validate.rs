@@ -0,0 +1,455 @@
+/*
+ * hurl (https://hurl.dev)
+ * Copyright (C) 2020 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::cell::RefCell;
+
+use regex::Regex;
+use serde_json::{Map, Value};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    // JSON pointer of the invalid value ("" for the root value)
+    pub pointer: String,
+    pub message: String,
+}
+
+///
+/// validate a json instance against a json schema
+/// returns all the violations (empty if the instance is valid)
+///
+pub fn validate(schema: &Value, instance: &Value) -> Vec<Violation> {
+    let mut violations = vec![];
+    Validator { root: schema, references: RefCell::new(vec![]) }.validate(schema, instance, "", &mut violations);
+    violations
+}
+
+// keyword, violation message and check of a numeric bound
+type Bound = (&'static str, &'static str, fn(f64, f64) -> bool);
+
+struct Validator<'a> {
+    root: &'a Value,
+    // references being validated, with the address of their instance, to detect cycles
+    references: RefCell<Vec<(String, usize)>>,
+}
+
+impl<'a> Validator<'a> {
+    fn validate(&self, schema: &Value, instance: &Value, pointer: &str, violations: &mut Vec<Violation>) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => return violations.push(violation(pointer, "no value is allowed")),
+            Value::Object(schema) => schema,
+            _ => return violations.push(violation(pointer, "invalid schema, expecting an object or a boolean")),
+        };
+
+        // as in draft-07, a reference overrides all its sibling keywords
+        if let Some(reference) = schema.get("$ref") {
+            let reference = reference.as_str().unwrap_or_default();
+            let target = match self.resolve(reference) {
+                Some(target) => target,
+                None => return violations.push(violation(pointer, format!("can not resolve reference <{}>", reference).as_str())),
+            };
+            // validating the same instance against the same reference again never ends
+            let key = (reference.to_string(), instance as *const Value as usize);
+            if self.references.borrow().contains(&key) {
+                return violations.push(violation(pointer, format!("circular reference <{}>", reference).as_str()));
+            }
+            self.references.borrow_mut().push(key);
+            self.validate(target, instance, pointer, violations);
+            self.references.borrow_mut().pop();
+            return;
+        }
+
+        self.validate_generic(schema, instance, pointer, violations);
+        match instance {
+            Value::String(s) => self.validate_string(schema, s, pointer, violations),
+            Value::Number(_) => self.validate_number(schema, instance, pointer, violations),
+            Value::Array(items) => self.validate_array(schema, items, pointer, violations),
+            Value::Object(object) => self.validate_object(schema, object, pointer, violations),
+            _ => {}
+        }
+        self.validate_combinators(schema, instance, pointer, violations);
+    }
+
+    fn is_valid(&self, schema: &Value, instance: &Value) -> bool {
+        let mut violations = vec![];
+        self.validate(schema, instance, "", &mut violations);
+        violations.is_empty()
+    }
+
+    // only local references are supported
+    fn resolve(&self, reference: &str) -> Option<&'a Value> {
+        if reference == "#" {
+            Some(self.root)
+        } else if reference.starts_with("#/") {
+            self.root.pointer(&reference[1..])
+        } else {
+            None
+        }
+    }
+
+    fn validate_generic(&self, schema: &Map<String, Value>, instance: &Value, pointer: &str, violations: &mut Vec<Violation>) {
+        if let Some(expected) = schema.get("type") {
+            let types = match expected {
+                Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+                _ => vec![expected.as_str().unwrap_or_default()],
+            };
+            if !types.iter().any(|t| has_type(instance, t)) {
+                let message = format!("expected type {} but found {}", types.join(" or "), type_name(instance));
+                violations.push(violation(pointer, message.as_str()));
+            }
+        }
+        if let Some(Value::Array(values)) = schema.get("enum") {
+            if !values.iter().any(|v| json_equal(v, instance)) {
+                let message = format!("value {} is not one of {}", instance, Value::Array(values.clone()));
+                violations.push(violation(pointer, message.as_str()));
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if !json_equal(expected, instance) {
+                let message = format!("expected value {} but found {}", expected, instance);
+                violations.push(violation(pointer, message.as_str()));
+            }
+        }
+    }
+
+    fn validate_string(&self, schema: &Map<String, Value>, s: &str, pointer: &str, violations: &mut Vec<Violation>) {
+        let length = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64()) {
+            if length < min {
+                violations.push(violation(pointer, format!("string length {} is less than minimum {}", length, min).as_str()));
+            }
+        }
+        if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+            if length > max {
+                violations.push(violation(pointer, format!("string length {} is greater than maximum {}", length, max).as_str()));
+            }
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+            match Regex::new(pattern) {
+                Err(_) => violations.push(violation(pointer, format!("invalid pattern <{}> in schema", pattern).as_str())),
+                Ok(regex) => if !regex.is_match(s) {
+                    violations.push(violation(pointer, format!("string \"{}\" does not match pattern <{}>", s, pattern).as_str()));
+                }
+            }
+        }
+    }
+
+    fn validate_number(&self, schema: &Map<String, Value>, instance: &Value, pointer: &str, violations: &mut Vec<Violation>) {
+        let x = instance.as_f64().unwrap_or_default();
+        let bounds: [Bound; 4] = [
+            ("minimum", "is less than minimum", |x, bound| x >= bound),
+            ("maximum", "is greater than maximum", |x, bound| x <= bound),
+            ("exclusiveMinimum", "is not greater than exclusive minimum", |x, bound| x > bound),
+            ("exclusiveMaximum", "is not less than exclusive maximum", |x, bound| x < bound),
+        ];
+        for (keyword, message, check) in bounds.iter() {
+            if let Some(bound) = schema.get(*keyword).filter(|v| v.is_number()) {
+                if !check(x, bound.as_f64().unwrap_or_default()) {
+                    violations.push(violation(pointer, format!("{} {} {}", instance, message, bound).as_str()));
+                }
+            }
+        }
+        if let Some(divisor) = schema.get("multipleOf").filter(|v| v.is_number()) {
+            let quotient = x / divisor.as_f64().unwrap_or_default();
+            if !quotient.is_finite() || (quotient - quotient.round()).abs() > 1e-9 {
+                violations.push(violation(pointer, format!("{} is not a multiple of {}", instance, divisor).as_str()));
+            }
+        }
+    }
+
+    fn validate_array(&self, schema: &Map<String, Value>, items: &[Value], pointer: &str, violations: &mut Vec<Violation>) {
+        let count = items.len() as u64;
+        if let Some(min) = schema.get("minItems").and_then(|v| v.as_u64()) {
+            if count < min {
+                violations.push(violation(pointer, format!("array has {} items, less than minimum {}", count, min).as_str()));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(|v| v.as_u64()) {
+            if count > max {
+                violations.push(violation(pointer, format!("array has {} items, more than maximum {}", count, max).as_str()));
+            }
+        }
+        if schema.get("uniqueItems") == Some(&Value::Bool(true)) {
+            for (i, item) in items.iter().enumerate() {
+                if let Some(j) = items[i + 1..].iter().position(|other| json_equal(item, other)) {
+                    violations.push(violation(pointer, format!("items at index {} and {} are equal", i, i + 1 + j).as_str()));
+                    break;
+                }
+            }
+        }
+        match schema.get("items") {
+            Some(Value::Array(schemas)) => {
+                for (i, item) in items.iter().enumerate() {
+                    let item_pointer = format!("{}/{}", pointer, i);
+                    match schemas.get(i) {
+                        Some(item_schema) => self.validate(item_schema, item, item_pointer.as_str(), violations),
+                        None => if let Some(additional) = schema.get("additionalItems") {
+                            self.validate(additional, item, item_pointer.as_str(), violations)
+                        },
+                    }
+                }
+            }
+            Some(item_schema) => {
+                for (i, item) in items.iter().enumerate() {
+                    self.validate(item_schema, item, format!("{}/{}", pointer, i).as_str(), violations);
+                }
+            }
+            None => {}
+        }
+        if let Some(contains) = schema.get("contains") {
+            if !items.iter().any(|item| self.is_valid(contains, item)) {
+                violations.push(violation(pointer, "no item matches the 'contains' schema"));
+            }
+        }
+    }
+
+    fn validate_object(&self, schema: &Map<String, Value>, object: &Map<String, Value>, pointer: &str, violations: &mut Vec<Violation>) {
+        let count = object.len() as u64;
+        if let Some(min) = schema.get("minProperties").and_then(|v| v.as_u64()) {
+            if count < min {
+                violations.push(violation(pointer, format!("object has {} properties, less than minimum {}", count, min).as_str()));
+            }
+        }
+        if let Some(max) = schema.get("maxProperties").and_then(|v| v.as_u64()) {
+            if count > max {
+                violations.push(violation(pointer, format!("object has {} properties, more than maximum {}", count, max).as_str()));
+            }
+        }
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(|v| v.as_str()) {
+                if !object.contains_key(name) {
+                    violations.push(violation(pointer, format!("missing required property <{}>", name).as_str()));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(|v| v.as_object());
+        let mut patterns = vec![];
+        if let Some(Value::Object(pattern_properties)) = schema.get("patternProperties") {
+            for (pattern, property_schema) in pattern_properties {
+                match Regex::new(pattern) {
+                    Ok(regex) => patterns.push((regex, property_schema)),
+                    Err(_) => violations.push(violation(pointer, format!("invalid pattern <{}> in schema", pattern).as_str())),
+                }
+            }
+        }
+        for (name, value) in object {
+            let property_pointer = format!("{}/{}", pointer, escape(name));
+            let mut matched = false;
+            if let Some(property_schema) = properties.and_then(|p| p.get(name)) {
+                matched = true;
+                self.validate(property_schema, value, property_pointer.as_str(), violations);
+            }
+            for (regex, property_schema) in patterns.iter() {
+                if regex.is_match(name) {
+                    matched = true;
+                    self.validate(property_schema, value, property_pointer.as_str(), violations);
+                }
+            }
+            if !matched {
+                match schema.get("additionalProperties") {
+                    Some(Value::Bool(false)) => violations.push(violation(property_pointer.as_str(), format!("additional property <{}> is not allowed", name).as_str())),
+                    Some(additional) => self.validate(additional, value, property_pointer.as_str(), violations),
+                    None => {}
+                }
+            }
+            if let Some(names_schema) = schema.get("propertyNames") {
+                self.validate(names_schema, &Value::String(name.clone()), property_pointer.as_str(), violations);
+            }
+        }
+    }
+
+    fn validate_combinators(&self, schema: &Map<String, Value>, instance: &Value, pointer: &str, violations: &mut Vec<Violation>) {
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            for sub_schema in schemas {
+                self.validate(sub_schema, instance, pointer, violations);
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            if !schemas.iter().any(|s| self.is_valid(s, instance)) {
+                violations.push(violation(pointer, "value does not match any schema of 'anyOf'"));
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            let matches = schemas.iter().filter(|s| self.is_valid(s, instance)).count();
+            if matches == 0 {
+                violations.push(violation(pointer, "value does not match any schema of 'oneOf'"));
+            } else if matches > 1 {
+                violations.push(violation(pointer, format!("value matches {} schemas of 'oneOf', expecting exactly one", matches).as_str()));
+            }
+        }
+        if let Some(not_schema) = schema.get("not") {
+            if self.is_valid(not_schema, instance) {
+                violations.push(violation(pointer, "value must not match the 'not' schema"));
+            }
+        }
+        if let Some(if_schema) = schema.get("if") {
+            let branch = if self.is_valid(if_schema, instance) { "then" } else { "else" };
+            if let Some(branch_schema) = schema.get(branch) {
+                self.validate(branch_schema, instance, pointer, violations);
+            }
+        }
+    }
+}
+
+fn violation(pointer: &str, message: &str) -> Violation {
+    Violation { pointer: pointer.to_string(), message: message.to_string() }
+}
+
+// escape a property name as a JSON pointer reference token
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+fn is_integer(value: &Value) -> bool {
+    match value.as_f64() {
+        _ if value.is_i64() || value.is_u64() => true,
+        Some(x) => x.fract() == 0.0,
+        None => false,
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_number() && is_integer(value),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) if is_integer(value) => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// numbers are equal regardless of their representation (1 == 1.0)
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(_), Value::Number(_)) => left.as_f64() == right.as_f64(),
+        (Value::Array(left), Value::Array(right)) => left.len() == right.len() && left.iter().zip(right).all(|(l, r)| json_equal(l, r)),
+        (Value::Object(left), Value::Object(right)) => left.len() == right.len() && left.iter().all(|(k, l)| match right.get(k) {
+            Some(r) => json_equal(l, r),
+            None => false,
+        }),
+        _ => left == right,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "required": ["id", "name", "email"],
+            "properties": {
+                "id": { "type": "integer", "minimum": 1 },
+                "name": { "type": "string", "minLength": 1 },
+                "email": { "type": "string", "pattern": "^[^@]+@[^@]+$" },
+                "roles": { "type": "array", "items": { "enum": ["admin", "user"] }, "uniqueItems": true }
+            },
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn test_valid() {
+        let instance = serde_json::json!({"id": 1, "name": "Bob", "email": "bob@example.org", "roles": ["admin"]});
+        assert_eq!(validate(&user_schema(), &instance), vec![]);
+        assert_eq!(validate(&Value::Bool(true), &instance), vec![]);
+    }
+
+    #[test]
+    fn test_violations() {
+        let instance = serde_json::json!({"id": 0, "name": 42, "roles": ["admin", "guest", "admin"], "a/b": true});
+        assert_eq!(validate(&user_schema(), &instance), vec![
+            violation("", "missing required property <email>"),
+            violation("/a~1b", "additional property <a/b> is not allowed"),
+            violation("/id", "0 is less than minimum 1"),
+            violation("/name", "expected type string but found integer"),
+            violation("/roles", "items at index 0 and 2 are equal"),
+            violation("/roles/1", "value \"guest\" is not one of [\"admin\",\"user\"]"),
+        ]);
+    }
+
+    #[test]
+    fn test_reference() {
+        let schema = serde_json::json!({
+            "definitions": { "positive": { "type": "number", "exclusiveMinimum": 0 } },
+            "type": "array",
+            "items": { "$ref": "#/definitions/positive" }
+        });
+        assert_eq!(validate(&schema, &serde_json::json!([1, 2.5])), vec![]);
+        assert_eq!(validate(&schema, &serde_json::json!([1, 0])), vec![violation("/1", "0 is not greater than exclusive minimum 0")]);
+
+        let schema = serde_json::json!({ "$ref": "#/definitions/unknown" });
+        assert_eq!(validate(&schema, &Value::Null), vec![violation("", "can not resolve reference <#/definitions/unknown>")]);
+    }
+
+    #[test]
+    fn test_circular_reference() {
+        let schema = serde_json::json!({ "$ref": "#" });
+        assert_eq!(validate(&schema, &Value::Null), vec![violation("", "circular reference <#>")]);
+
+        let schema = serde_json::json!({
+            "definitions": { "a": { "$ref": "#/definitions/b" }, "b": { "anyOf": [{ "$ref": "#/definitions/a" }] } },
+            "$ref": "#/definitions/a"
+        });
+        assert_eq!(validate(&schema, &Value::Null), vec![violation("", "value does not match any schema of 'anyOf'")]);
+
+        // a recursive schema is valid as long as the instance gets smaller
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "children": { "type": "array", "items": { "$ref": "#" } } }
+        });
+        let instance = serde_json::json!({ "children": [{ "children": [] }, { "children": [1] }] });
+        assert_eq!(validate(&schema, &instance), vec![violation("/children/1/children/0", "expected type object but found integer")]);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let schema = serde_json::json!({ "oneOf": [{ "type": "integer" }, { "multipleOf": 2 }] });
+        assert_eq!(validate(&schema, &serde_json::json!(3)), vec![]);
+        assert_eq!(validate(&schema, &serde_json::json!(4)), vec![violation("", "value matches 2 schemas of 'oneOf', expecting exactly one")]);
+        assert_eq!(validate(&schema, &serde_json::json!(1.5)), vec![violation("", "value does not match any schema of 'oneOf'")]);
+
+        let schema = serde_json::json!({ "anyOf": [{ "type": "string" }, { "type": "null" }], "not": { "const": "" } });
+        assert_eq!(validate(&schema, &Value::Null), vec![]);
+        assert_eq!(validate(&schema, &serde_json::json!("")), vec![violation("", "value must not match the 'not' schema")]);
+        assert_eq!(validate(&schema, &serde_json::json!(true)), vec![violation("", "value does not match any schema of 'anyOf'")]);
+    }
+
+    #[test]
+    fn test_json_equal() {
+        assert!(json_equal(&serde_json::json!([1, {"a": 2.0}]), &serde_json::json!([1.0, {"a": 2}])));
+        assert!(!json_equal(&serde_json::json!({"a": 1}), &serde_json::json!({"a": 1, "b": 2})));
+    }
+}
@@ -27,5 +27,6 @@ pub mod parser;
 pub mod runner;
 pub mod http;
 pub mod jsonpath;
+pub mod jsonschema;
 pub mod html;
 pub mod cli;
@@ -23,6 +23,9 @@ use super::core::{Error, Lintable, LinterError};
 impl Lintable<HurlFile> for HurlFile {
     fn errors(&self) -> Vec<Error> {
         let mut errors = vec![];
+        for template in self.templates.clone() {
+            errors.append(&mut (template.errors()));
+        }
         for entry in self.entries.clone() {
             errors.append(&mut (entry.errors()));
         }
@@ -31,12 +34,68 @@ impl Lintable<HurlFile> for HurlFile {
 
     fn lint(&self) -> HurlFile {
         HurlFile {
+            sections: self.sections.iter().map(|e| e.lint()).collect(),
+            templates: self.templates.iter().map(|e| e.lint()).collect(),
             entries: self.entries.iter().map(|e| e.lint()).collect(),
             line_terminators: self.line_terminators.clone(),
         }
     }
 }
 
+impl Lintable<FileEntry> for FileEntry {
+    fn errors(&self) -> Vec<Error> {
+        match self {
+            FileEntry::Entry(entry) => entry.errors(),
+            FileEntry::Call(call) => call.errors(),
+        }
+    }
+
+    fn lint(&self) -> FileEntry {
+        match self {
+            FileEntry::Entry(entry) => FileEntry::Entry(Box::new(entry.lint())),
+            FileEntry::Call(call) => FileEntry::Call(call.lint()),
+        }
+    }
+}
+
+impl Lintable<EntryTemplate> for EntryTemplate {
+    fn errors(&self) -> Vec<Error> {
+        let mut errors = vec![];
+        errors.append(&mut (self.entry.errors()));
+        errors
+    }
+
+    fn lint(&self) -> EntryTemplate {
+        EntryTemplate {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            space1: one_whitespace(),
+            name: self.clone().name,
+            params: self.params.iter().map(|(_, param)| (one_whitespace(), param.clone())).collect(),
+            line_terminator0: self.clone().line_terminator0,
+            entry: self.entry.lint(),
+        }
+    }
+}
+
+impl Lintable<TemplateCall> for TemplateCall {
+    fn errors(&self) -> Vec<Error> {
+        let errors = vec![];
+        errors
+    }
+
+    fn lint(&self) -> TemplateCall {
+        TemplateCall {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            space1: one_whitespace(),
+            name: self.clone().name,
+            line_terminator0: self.clone().line_terminator0,
+            arguments: self.arguments.iter().map(|e| e.lint()).collect(),
+        }
+    }
+}
+
 impl Lintable<Entry> for Entry {
     fn errors(&self) -> Vec<Error> {
         let mut errors = vec![];
@@ -191,6 +250,24 @@ impl Lintable<SectionValue> for SectionValue {
             SectionValue::Cookies(cookies) => {
                 SectionValue::Cookies(cookies.iter().map(|e| e.lint()).collect())
             }
+            SectionValue::Namespaces(namespaces) => {
+                SectionValue::Namespaces(namespaces.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::JsonIgnore(paths) => {
+                SectionValue::JsonIgnore(paths.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::Includes(includes) => {
+                SectionValue::Includes(includes.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::Variables(variables) => {
+                SectionValue::Variables(variables.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::Headers(headers) => {
+                SectionValue::Headers(headers.iter().map(|e| e.lint()).collect())
+            }
+            SectionValue::Options(options) => {
+                SectionValue::Options(options.iter().map(|e| e.lint()).collect())
+            }
         }
     }
 }
@@ -202,8 +279,14 @@ impl SectionValue {
             SectionValue::FormParams(_) => 1,
             SectionValue::MultipartFormData(_) => 2,
             SectionValue::Cookies(_) => 3,
-            SectionValue::Captures(_) => 0,
-            SectionValue::Asserts(_) => 1,
+            SectionValue::Namespaces(_) => 0,
+            SectionValue::Captures(_) => 1,
+            SectionValue::Asserts(_) => 2,
+            SectionValue::JsonIgnore(_) => 3,
+            SectionValue::Includes(_) => 0,
+            SectionValue::Variables(_) => 1,
+            SectionValue::Headers(_) => 2,
+            SectionValue::Options(_) => 4,
         }
     }
 }
@@ -219,6 +302,7 @@ impl Lintable<Assert> for Assert {
             line_terminators: self.line_terminators.clone(),
             space0: empty_whitespace(),
             query: self.query.lint(),
+            filters: self.filters.iter().map(|(_, f)| (one_whitespace(), f.lint())).collect(),
             space1: one_whitespace(),
             predicate: self.predicate.lint(),
             line_terminator0: self.line_terminator0.clone(),
@@ -240,8 +324,7 @@ impl Lintable<Capture> for Capture {
             space1: empty_whitespace(),
             space2: one_whitespace(),
             query: self.query.lint(),
-            space3: self.space3.clone(),
-            subquery: self.subquery.clone(),
+            filters: self.filters.iter().map(|(_, f)| (one_whitespace(), f.lint())).collect(),
             line_terminator0: self.line_terminator0.lint(),
         }
     }
@@ -284,6 +367,7 @@ impl Lintable<QueryValue> for QueryValue {
             }
             QueryValue::Body {} => QueryValue::Body {},
             QueryValue::Xpath { expr, .. } => QueryValue::Xpath { expr: expr.clone(), space0: one_whitespace() },
+            QueryValue::Css { expr, .. } => QueryValue::Css { expr: expr.clone(), space0: one_whitespace() },
             QueryValue::Jsonpath { expr, .. } => QueryValue::Jsonpath { expr: expr.clone(), space0: one_whitespace() },
             QueryValue::Regex { expr, .. } => QueryValue::Regex { expr: expr.clone(), space0: one_whitespace() },
             QueryValue::Variable { name, .. } => QueryValue::Variable { name: name.clone(), space0: one_whitespace() },
@@ -291,6 +375,33 @@ impl Lintable<QueryValue> for QueryValue {
     }
 }
 
+impl Lintable<Filter> for Filter {
+    fn errors(&self) -> Vec<Error> {
+        let errors = vec![];
+        errors
+    }
+
+    fn lint(&self) -> Filter {
+        let value = match self.value.clone() {
+            FilterValue::Nth { n, .. } => FilterValue::Nth { space0: one_whitespace(), n },
+            FilterValue::Split { sep, .. } => FilterValue::Split { space0: one_whitespace(), sep },
+            FilterValue::Replace { old_value, new_value, .. } => FilterValue::Replace {
+                space0: one_whitespace(),
+                old_value,
+                space1: one_whitespace(),
+                new_value,
+            },
+            FilterValue::Jsonpath { expr, .. } => FilterValue::Jsonpath { space0: one_whitespace(), expr },
+            FilterValue::Regex { expr, .. } => FilterValue::Regex { space0: one_whitespace(), expr },
+            value => value,
+        };
+        Filter {
+            source_info: self.source_info.clone(),
+            value,
+        }
+    }
+}
+
 impl Lintable<CookieAttribute> for CookieAttribute {
     fn errors(&self) -> Vec<Error> {
         let errors = vec![];
@@ -381,7 +492,26 @@ impl Lintable<PredicateFuncValue> for PredicateFuncValue {
             PredicateFuncValue::Match { value, .. } => PredicateFuncValue::Match { space0: one_whitespace(), value: value.clone().lint() },
             PredicateFuncValue::StartWith { value, .. } => PredicateFuncValue::StartWith { space0: one_whitespace(), value: value.clone().lint() },
             PredicateFuncValue::CountEqual { value, .. } => PredicateFuncValue::CountEqual { space0: one_whitespace(), value: value.clone() },
-            PredicateFuncValue::Exist {} => PredicateFuncValue::Exist {}
+            PredicateFuncValue::Exist {} => PredicateFuncValue::Exist {},
+            PredicateFuncValue::JsonEqual { value, .. } => PredicateFuncValue::JsonEqual { space0: one_whitespace(), value: value.clone() },
+            PredicateFuncValue::JsonSchema { filename, .. } => PredicateFuncValue::JsonSchema {
+                space0: one_whitespace(),
+                space1: one_whitespace(),
+                filename: Filename {
+                    value: filename.clone().value,
+                    source_info: SourceInfo::init(0, 0, 0, 0),
+                },
+                space2: empty_whitespace(),
+            },
+            PredicateFuncValue::XmlSchema { filename, .. } => PredicateFuncValue::XmlSchema {
+                space0: one_whitespace(),
+                space1: one_whitespace(),
+                filename: Filename {
+                    value: filename.clone().value,
+                    source_info: SourceInfo::init(0, 0, 0, 0),
+                },
+                space2: empty_whitespace(),
+            },
         }
     }
 }
@@ -434,6 +564,14 @@ impl Lintable<Bytes> for Bytes {
                 },
                 space1: empty_whitespace(),
             },
+            Bytes::TemplateFile { filename, .. } => Bytes::TemplateFile {
+                space0: one_whitespace(),
+                filename: Filename {
+                    value: filename.clone().value,
+                    source_info: SourceInfo::init(0, 0, 0, 0),
+                },
+                space1: empty_whitespace(),
+            },
             Bytes::Base64 { encoded, value, .. } => Bytes::Base64 {
                 space0: one_whitespace(),
                 value: value.clone(),
@@ -450,6 +588,10 @@ impl Lintable<Bytes> for Bytes {
             Bytes::Xml { value } => Bytes::Xml {
                 value: value.clone(),
             },
+            Bytes::TemplateXml { newline0, value } => Bytes::TemplateXml {
+                newline0: newline0.clone(),
+                value: value.clone(),
+            },
 //            Bytes::MultilineString { value } => Bytes::MultilineString {
 //                value: value.clone(),
 //            },
@@ -476,6 +618,38 @@ impl Lintable<KeyValue> for KeyValue {
     }
 }
 
+impl Lintable<IgnoredPath> for IgnoredPath {
+    fn errors(&self) -> Vec<Error> {
+        let errors = vec![];
+        errors
+    }
+
+    fn lint(&self) -> IgnoredPath {
+        IgnoredPath {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            path: self.clone().path,
+            line_terminator0: self.clone().line_terminator0,
+        }
+    }
+}
+
+impl Lintable<Include> for Include {
+    fn errors(&self) -> Vec<Error> {
+        let errors = vec![];
+        errors
+    }
+
+    fn lint(&self) -> Include {
+        Include {
+            line_terminators: self.clone().line_terminators,
+            space0: empty_whitespace(),
+            filename: self.clone().filename,
+            line_terminator0: self.clone().line_terminator0,
+        }
+    }
+}
+
 impl Lintable<MultipartParam> for MultipartParam {
     fn errors(&self) -> Vec<Error> {
         let errors = vec![];
@@ -618,6 +792,17 @@ impl Lintable<Template> for Template {
     }
 }
 
+impl Lintable<RegexValue> for RegexValue {
+    fn errors(&self) -> Vec<Error> {
+        let errors = vec![];
+        errors
+    }
+
+    fn lint(&self) -> RegexValue {
+        self.clone()
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -626,10 +811,14 @@ mod tests {
     #[test]
     fn test_hurl_file() {
         let hurl_file = HurlFile {
+            sections: vec![],
+            templates: vec![],
             entries: vec![],
             line_terminators: vec![],
         };
         let hurl_file_linted = HurlFile {
+            sections: vec![],
+            templates: vec![],
             entries: vec![],
             line_terminators: vec![],
         };
@@ -640,10 +829,14 @@ mod tests {
     #[test]
     fn test_entry() {
         let entry = HurlFile {
+            sections: vec![],
+            templates: vec![],
             entries: vec![],
             line_terminators: vec![],
         };
         let entry_linted = HurlFile {
+            sections: vec![],
+            templates: vec![],
             entries: vec![],
             line_terminators: vec![],
         };
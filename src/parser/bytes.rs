@@ -19,6 +19,7 @@ use crate::core::ast::*;
 
 use super::base64;
 use super::combinators::*;
+use super::error::Error;
 use super::json::parse as parse_json;
 use super::ParseResult;
 use super::primitives::*;
@@ -27,7 +28,7 @@ use super::xml;
 
 pub fn bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
     //let start = p.state.clone();
-    choice(vec![raw_string, json_bytes, xml_bytes, base64_bytes, file_bytes], reader)
+    choice(vec![raw_string, json_bytes, xml_bytes, base64_bytes, template_xml_bytes, template_file_bytes, file_bytes], reader)
 }
 
 fn xml_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
@@ -37,6 +38,22 @@ fn xml_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
     }
 }
 
+fn template_xml_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
+    try_literal("template", reader)?;
+    // template, is a template file
+    let newline0 = match newline(reader) {
+        Ok(newline0) => newline0,
+        Err(e) => return Err(Error { recoverable: true, ..e }),
+    };
+    // the xml is read again as a template, so that it can include variables
+    let save_state = reader.state.clone();
+    xml::parse(reader)?;
+    let count = reader.state.cursor - save_state.cursor;
+    reader.state = save_state;
+    let value = template_chars(reader, count)?;
+    Ok(Bytes::TemplateXml { newline0, value })
+}
+
 fn json_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
     match parse_json(reader) {
         Err(e) => Err(e),
@@ -59,6 +76,20 @@ fn file_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
     })
 }
 
+fn template_file_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
+    try_literal("template", reader)?;
+    literal(",", reader)?;
+    let space0 = zero_or_more_spaces(reader)?;
+    let f = filename(reader)?;
+    let space1 = zero_or_more_spaces(reader)?;
+    literal(";", reader)?;
+    Ok(Bytes::TemplateFile {
+        space0,
+        filename: f,
+        space1,
+    })
+}
+
 fn base64_bytes(reader: &mut Reader) -> ParseResult<'static, Bytes> {
     // base64 => can have whitespace
     // support pqrser position
@@ -152,6 +183,69 @@ mod tests {
                 value: String::from("<a/>")
             }
         );
+
+        // variables are not rendered in an xml body
+        let mut reader = Reader::init("<a>{{id}}</a>");
+        assert_eq!(
+            bytes(&mut reader).unwrap(),
+            Bytes::Xml {
+                value: String::from("<a>{{id}}</a>")
+            }
+        );
+    }
+
+    #[test]
+    fn test_bytes_xml_template() {
+        let mut reader = Reader::init("template\n<a>{{id}}</a>");
+        assert_eq!(
+            bytes(&mut reader).unwrap(),
+            Bytes::TemplateXml {
+                newline0: Whitespace { value: String::from("\n"), source_info: SourceInfo::init(1, 9, 2, 1) },
+                value: Template {
+                    quotes: false,
+                    elements: vec![
+                        TemplateElement::String {
+                            value: String::from("<a>"),
+                            encoded: String::from("<a>"),
+                        },
+                        TemplateElement::Expression(Expr {
+                            space0: Whitespace { value: String::from(""), source_info: SourceInfo::init(2, 6, 2, 6) },
+                            value: ExprValue::Variable(Variable { name: String::from("id"), source_info: SourceInfo::init(2, 6, 2, 8) }),
+                            filters: vec![],
+                            space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(2, 8, 2, 8) },
+                        }),
+                        TemplateElement::String {
+                            value: String::from("</a>"),
+                            encoded: String::from("</a>"),
+                        },
+                    ],
+                    source_info: SourceInfo::init(2, 1, 2, 14),
+                }
+            }
+        );
+        assert_eq!(reader.state.cursor, 22);
+    }
+
+    #[test]
+    fn test_bytes_template_file() {
+        let mut reader = Reader::init("template,payload.json;");
+        assert_eq!(
+            bytes(&mut reader).unwrap(),
+            Bytes::TemplateFile {
+                space0: Whitespace {
+                    value: String::from(""),
+                    source_info: SourceInfo::init(1, 10, 1, 10),
+                },
+                filename: Filename {
+                    value: String::from("payload.json"),
+                    source_info: SourceInfo::init(1, 10, 1, 22),
+                },
+                space1: Whitespace {
+                    value: String::from(""),
+                    source_info: SourceInfo::init(1, 22, 1, 22),
+                },
+            }
+        );
     }
 
     #[test]
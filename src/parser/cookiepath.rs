@@ -125,10 +125,11 @@ mod tests {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 3, 1, 3),
                         },
-                        variable: Variable {
+                        value: ExprValue::Variable(Variable {
                             name: "name".to_string(),
                             source_info: SourceInfo::init(1, 3, 1, 7),
-                        },
+                        }),
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 7, 1, 7),
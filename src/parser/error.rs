@@ -50,8 +50,19 @@ pub enum ParseError {
 
     DuplicateSection,
     RequestSection,
+    FileSection,
     ResponseSection,
 
+    OptionName { name: String },
+    LoopOption,
+    SkipOption,
+    OptionValue { name: String },
+
+    DuplicateTemplate,
+    UndefinedTemplate { name: String },
+    TemplateArgument { name: String },
+    MissingTemplateArgument { name: String },
+
     HexDigit,
     Unicode,
     EscapeChar,
@@ -87,7 +98,16 @@ impl FormatError for Error {
             ParseError::RegexExpr { .. } => "Parsing regex".to_string(),
             ParseError::DuplicateSection { .. } => "Parsing section".to_string(),
             ParseError::RequestSection { .. } => "Parsing section".to_string(),
+            ParseError::FileSection { .. } => "Parsing section".to_string(),
             ParseError::ResponseSection { .. } => "Parsing section".to_string(),
+            ParseError::OptionName { .. } => "Parsing option".to_string(),
+            ParseError::LoopOption { .. } => "Parsing option".to_string(),
+            ParseError::SkipOption { .. } => "Parsing option".to_string(),
+            ParseError::OptionValue { .. } => "Parsing option".to_string(),
+            ParseError::DuplicateTemplate { .. } => "Parsing template".to_string(),
+            ParseError::UndefinedTemplate { .. } => "Parsing template call".to_string(),
+            ParseError::TemplateArgument { .. } => "Parsing template call".to_string(),
+            ParseError::MissingTemplateArgument { .. } => "Parsing template call".to_string(),
             ParseError::EscapeChar { .. } => "Parsing escape character".to_string(),
             ParseError::InvalidCookieAttribute { .. } => "Parsing cookie attribute".to_string(),
             _ => format!("{:?}", self),
@@ -112,7 +132,16 @@ impl FormatError for Error {
             ParseError::RegexExpr { .. } => "Invalid Regex expression".to_string(),
             ParseError::DuplicateSection { .. } => "The section is already defined".to_string(),
             ParseError::RequestSection { .. } => "This is not a valid section for a request".to_string(),
+            ParseError::FileSection { .. } => "This is not a valid section for the file header".to_string(),
             ParseError::ResponseSection { .. } => "This is not a valid section for a response".to_string(),
+            ParseError::OptionName { name } => format!("The option {} is not valid", name),
+            ParseError::LoopOption { .. } => "The loop must be given as <item> in <list>".to_string(),
+            ParseError::SkipOption { .. } => "The skip condition must be given as [not] <variable> [== <value> | != <value>]".to_string(),
+            ParseError::OptionValue { name } => format!("The option {} must be a text without templates", name),
+            ParseError::DuplicateTemplate { .. } => "The template is already defined".to_string(),
+            ParseError::UndefinedTemplate { name } => format!("The template {} is not defined", name),
+            ParseError::TemplateArgument { name } => format!("{} is not a parameter of the template", name),
+            ParseError::MissingTemplateArgument { name } => format!("missing argument {}", name),
             ParseError::EscapeChar { .. } => "The escaping sequence is not valid".to_string(),
             ParseError::InvalidCookieAttribute { .. } => "The cookie attribute is not valid".to_string(),
             _ => format!("{:?}", self),
@@ -15,6 +15,8 @@
  * limitations under the License.
  *
  */
+use chrono::format::{Item, StrftimeItems};
+
 use crate::core::ast::*;
 use crate::core::common::SourceInfo;
 
@@ -27,20 +29,17 @@ pub fn parse(reader: &mut Reader) -> ParseResult<'static, Expr> {
     // let start = p.state.clone();
 
     try_literal("{{", reader)?;
-    let space0 = zero_or_more_spaces(reader)?;
-    let variable = variable_name(reader)?;
-    let space1 = zero_or_more_spaces(reader)?;
+    let expr = match parse2(reader) {
+        Ok(expr) => expr,
+        Err(e) => return Err(Error { recoverable: false, ..e }),
+    };
 
     //literal("}}", p)?;
     if try_literal("}}}", reader).is_err() {
         literal("}}", reader)?
     }
 
-    Ok(Expr {
-        space0,
-        variable,
-        space1,
-    })
+    Ok(expr)
 }
 
 
@@ -48,17 +47,169 @@ pub fn parse2(reader: &mut Reader) -> ParseResult<'static, Expr> {
     // let start = p.state.clone();
 
     let space0 = zero_or_more_spaces(reader)?;
-    let variable = variable_name(reader)?;
+    let value = expr_value(reader)?;
+    let mut filters = vec![];
+    loop {
+        let save = reader.state.clone();
+        let space0 = zero_or_more_spaces(reader)?;
+        if try_literal("|", reader).is_err() {
+            reader.state = save;
+            break;
+        }
+        filters.push(expr_filter(space0, reader)?);
+    }
     let space1 = zero_or_more_spaces(reader)?;
 
     Ok(Expr {
         space0,
-        variable,
+        value,
+        filters,
         space1,
     })
 }
 
-fn variable_name(reader: &mut Reader) -> ParseResult<'static, Variable> {
+// operations are evaluated from left to right
+fn expr_value(reader: &mut Reader) -> ParseResult<'static, ExprValue> {
+    let start = reader.state.pos.clone();
+    let mut value = term(reader)?;
+    loop {
+        let save = reader.state.clone();
+        let space0 = zero_or_more_spaces(reader)?;
+        let operator = if try_literal("+", reader).is_ok() {
+            Operator::Add
+        } else if try_literal("-", reader).is_ok() {
+            Operator::Subtract
+        } else {
+            reader.state = save;
+            break;
+        };
+        let space1 = zero_or_more_spaces(reader)?;
+        let right = term(reader)?;
+        value = ExprValue::Operation(Box::new(Operation {
+            left: value,
+            space0,
+            operator,
+            space1,
+            right,
+            source_info: SourceInfo { start: start.clone(), end: reader.state.pos.clone() },
+        }));
+    }
+    Ok(value)
+}
+
+fn term(reader: &mut Reader) -> ParseResult<'static, ExprValue> {
+    let start = reader.state.clone();
+    match expr_literal(reader) {
+        Ok(value) => return Ok(ExprValue::Literal(value)),
+        Err(e) => if e.recoverable {
+            reader.state = start.clone();
+        } else {
+            return Err(e);
+        }
+    }
+    let name = reader.read_while(|c| c.is_alphanumeric() || *c == '_');
+    let value = match name.as_str() {
+        "newUuid" => FunctionValue::NewUuid {},
+        "now" => FunctionValue::Now {},
+        "random" => {
+            let space0 = one_or_more_spaces(reader)?;
+            literal("int", reader)?;
+            let space1 = one_or_more_spaces(reader)?;
+            let min = integer(reader)?;
+            let space2 = one_or_more_spaces(reader)?;
+            let save = reader.state.clone();
+            let max = integer(reader)?;
+            if max < min {
+                return Err(Error {
+                    pos: save.pos,
+                    recoverable: false,
+                    inner: ParseError::Expecting { value: format!("integer greater than {}", min) },
+                });
+            }
+            FunctionValue::RandomInt { space0, space1, min, space2, max }
+        }
+        _ => {
+            reader.state = start;
+            return Ok(ExprValue::Variable(variable_name(reader)?));
+        }
+    };
+    let source_info = SourceInfo { start: start.pos, end: reader.state.pos.clone() };
+    Ok(ExprValue::Function(Box::new(Function { value, source_info })))
+}
+
+fn expr_literal(reader: &mut Reader) -> ParseResult<'static, ExprLiteral> {
+    let save = reader.state.clone();
+    match integer(reader) {
+        Ok(value) => return Ok(ExprLiteral::Integer(value)),
+        Err(e) => if e.recoverable {
+            reader.state = save;
+        } else {
+            return Err(e);
+        }
+    }
+    let value = expr_string(reader)?;
+    Ok(ExprLiteral::String(value))
+}
+
+// a string can not be escaped
+// its quotes are themselves escaped inside a json string
+fn expr_string(reader: &mut Reader) -> ParseResult<'static, ExprString> {
+    let quote = if try_literal("\\\"", reader).is_ok() {
+        "\\\""
+    } else {
+        try_literal("\"", reader)?;
+        "\""
+    };
+    let mut value = String::from("");
+    while !reader.remaining().starts_with(quote) {
+        match reader.read() {
+            None => break,
+            Some(c) => value.push(c),
+        }
+    }
+    literal(quote, reader)?;
+    Ok(ExprString { quote: quote.to_string(), value })
+}
+
+fn expr_filter(space0: Whitespace, reader: &mut Reader) -> ParseResult<'static, ExprFilter> {
+    let space1 = zero_or_more_spaces(reader)?;
+    let start = reader.state.clone();
+    let name = reader.read_while(|c| c.is_alphanumeric());
+    let value = match name.as_str() {
+        "format" => {
+            let space0 = one_or_more_spaces(reader)?;
+            let save = reader.state.clone();
+            let format = expr_string(reader)?;
+            if StrftimeItems::new(format.value.as_str()).any(|item| item == Item::Error) {
+                return Err(Error {
+                    pos: save.pos,
+                    recoverable: false,
+                    inner: ParseError::Expecting { value: "date format".to_string() },
+                });
+            }
+            ExprFilterValue::Format { space0, format }
+        }
+        "default" => {
+            let space0 = one_or_more_spaces(reader)?;
+            let value = expr_literal(reader)?;
+            ExprFilterValue::Default { space0, value }
+        }
+        "base64Encode" => ExprFilterValue::Base64Encode {},
+        "base64Decode" => ExprFilterValue::Base64Decode {},
+        "urlEncode" => ExprFilterValue::UrlEncode {},
+        "upper" => ExprFilterValue::Upper {},
+        "lower" => ExprFilterValue::Lower {},
+        _ => return Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::Expecting { value: "filter".to_string() },
+        }),
+    };
+    let source_info = SourceInfo { start: start.pos, end: reader.state.pos.clone() };
+    Ok(ExprFilter { space0, space1, value, source_info })
+}
+
+pub fn variable_name(reader: &mut Reader) -> ParseResult<'static, Variable> {
     let start = reader.state.clone();
     let name = reader.read_while(|c| c.is_alphanumeric() || *c == '_');
     if name == "" {
@@ -96,10 +247,11 @@ mod tests {
                     value: String::from(" "),
                     source_info: SourceInfo::init(1, 3, 1, 4),
                 },
-                variable: Variable {
+                value: ExprValue::Variable(Variable {
                     name: String::from("name"),
                     source_info: SourceInfo::init(1, 4, 1, 8),
-                },
+                }),
+                filters: vec![],
                 space1: Whitespace {
                     value: String::from(""),
                     source_info: SourceInfo::init(1, 8, 1, 8),
@@ -108,6 +260,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expr_function() {
+        let mut reader = Reader::init("{{newUuid}}");
+        assert_eq!(
+            parse(&mut reader).unwrap().value,
+            ExprValue::Function(Box::new(Function {
+                value: FunctionValue::NewUuid {},
+                source_info: SourceInfo::init(1, 3, 1, 10),
+            }))
+        );
+
+        let mut reader = Reader::init("{{random int 1 100}}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(expr.to_string(), "{{random int 1 100}}".to_string());
+        match expr.value {
+            ExprValue::Function(function) => match function.value {
+                FunctionValue::RandomInt { min, max, .. } => assert_eq!((min, max), (1, 100)),
+                _ => panic!("expecting random int"),
+            },
+            _ => panic!("expecting a function"),
+        }
+        assert_eq!(reader.state.cursor, 20);
+
+        // a variable can start with a function name
+        let mut reader = Reader::init("{{nowDate}}");
+        assert_eq!(parse(&mut reader).unwrap().value, ExprValue::Variable(Variable {
+            name: String::from("nowDate"),
+            source_info: SourceInfo::init(1, 3, 1, 10),
+        }));
+    }
+
+    #[test]
+    fn test_expr_operation() {
+        let mut reader = Reader::init("{{counter + 1}}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(
+            expr.value,
+            ExprValue::Operation(Box::new(Operation {
+                left: ExprValue::Variable(Variable {
+                    name: String::from("counter"),
+                    source_info: SourceInfo::init(1, 3, 1, 10),
+                }),
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 10, 1, 11) },
+                operator: Operator::Add,
+                space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 12, 1, 13) },
+                right: ExprValue::Literal(ExprLiteral::Integer(1)),
+                source_info: SourceInfo::init(1, 3, 1, 14),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_expr_filters() {
+        let mut reader = Reader::init("{{now | format \"%Y\"}}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(expr.filters, vec![ExprFilter {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 6, 1, 7) },
+            space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 8, 1, 9) },
+            value: ExprFilterValue::Format {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 15, 1, 16) },
+                format: ExprString { quote: String::from("\""), value: String::from("%Y") },
+            },
+            source_info: SourceInfo::init(1, 9, 1, 20),
+        }]);
+
+        let mut reader = Reader::init("{{name | default \"x\" | upper}}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(expr.filters.len(), 2);
+        assert_eq!(expr.to_string(), "{{name | default \"x\" | upper}}".to_string());
+
+        // quotes are escaped inside a json string
+        let mut reader = Reader::init("name | default \\\"x\\\"");
+        let expr = parse2(&mut reader).unwrap();
+        assert_eq!(expr.filters[0].value, ExprFilterValue::Default {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 15, 1, 16) },
+            value: ExprLiteral::String(ExprString { quote: String::from("\\\""), value: String::from("x") }),
+        });
+    }
+
+    #[test]
+    fn test_expr_filter_error() {
+        let mut reader = Reader::init("{{name | reverse}}");
+        let error = parse(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 10 });
+        assert_eq!(error.inner, ParseError::Expecting { value: String::from("filter") });
+        assert_eq!(error.recoverable, false);
+
+        let mut reader = Reader::init("{{now | format \"%Q\"}}");
+        let error = parse(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 16 });
+        assert_eq!(error.inner, ParseError::Expecting { value: String::from("date format") });
+
+        let mut reader = Reader::init("{{random int 10 1}}");
+        let error = parse(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 17 });
+        assert_eq!(error.inner, ParseError::Expecting { value: String::from("integer greater than 10") });
+    }
+
     #[test]
     fn test_expr_error() {
         let mut reader = Reader::init("{{host>}}");
@@ -22,9 +22,11 @@ use crate::core::json;
 
 use super::combinators::*;
 use super::error;
+use super::expr;
 use super::ParseResult;
 use super::primitives::*;
 use super::reader::*;
+use super::string::quoted_template;
 use super::template::*;
 
 pub fn parse(reader: &mut Reader) -> ParseResult<'static, json::Value> {
@@ -35,6 +37,8 @@ pub fn parse(reader: &mut Reader) -> ParseResult<'static, json::Value> {
             string_value,
             number_value,
             list_value,
+            matcher_value,
+            expression_value,
             object_value
         ],
         reader,
@@ -253,7 +257,9 @@ fn list_element(_type: Option<String>, reader: &mut Reader) -> ParseResult<'stat
         }),
     };
     if let Some(t) = _type {
-        if t != value._type() {
+        // a matcher or an expression can stand for an element of any type
+        let any = ["matcher", "expression"];
+        if t != value._type() && !any.contains(&t.as_str()) && !any.contains(&value._type().as_str()) {
             return Err(error::Error {
                 pos,
                 recoverable: false,
@@ -265,6 +271,70 @@ fn list_element(_type: Option<String>, reader: &mut Reader) -> ParseResult<'stat
     Ok(json::ListElement { space0, value, space1 })
 }
 
+///
+/// matchers are placeholders for values in an expected json body
+/// {{any}}, {{any <type>}} or {{matches "<regex>"}}
+///
+fn matcher_value(reader: &mut Reader) -> ParseResult<'static, json::Value> {
+    let start = reader.state.pos.clone();
+    try_literal("{{", reader)?;
+    let space0 = whitespace(reader);
+    let keyword_start = reader.state.pos.clone();
+    let keyword = reader.read_while(|c| c.is_alphanumeric() || *c == '_');
+    let value = match keyword.as_str() {
+        "any" => {
+            let save = reader.state.clone();
+            let space = whitespace(reader);
+            let name = reader.read_while(|c| c.is_alphabetic());
+            if name.is_empty() {
+                reader.state = save;
+                json::MatcherValue::Any {}
+            } else if ["string", "number", "integer", "boolean", "array", "object"].contains(&name.as_str()) {
+                json::MatcherValue::AnyType { space0: space, name }
+            } else {
+                return Err(error::Error {
+                    pos: save.pos,
+                    recoverable: false,
+                    inner: error::ParseError::Expecting { value: "json type".to_string() },
+                });
+            }
+        }
+        "matches" => {
+            let space = whitespace(reader);
+            if space.is_empty() {
+                return Err(error::Error {
+                    pos: reader.state.pos.clone(),
+                    recoverable: false,
+                    inner: error::ParseError::Space {},
+                });
+            }
+            let value = quoted_template(reader)?;
+            json::MatcherValue::Matches { space0: space, value }
+        }
+        // not a matcher, may still be an expression
+        _ => return Err(error::Error {
+            pos: keyword_start,
+            recoverable: true,
+            inner: error::ParseError::Expecting { value: "matcher".to_string() },
+        }),
+    };
+    let space1 = whitespace(reader);
+    literal("}}", reader)?;
+    let source_info = SourceInfo { start, end: reader.state.pos.clone() };
+    Ok(json::Value::Matcher(json::Matcher { space0, value, space1, source_info }))
+}
+
+///
+/// an unquoted {{variable}}, rendered with its json type
+///
+fn expression_value(reader: &mut Reader) -> ParseResult<'static, json::Value> {
+    // the closing brackets may be followed by the end of an object
+    try_literal("{{", reader)?;
+    let expr = expr::parse2(reader)?;
+    literal("}}", reader)?;
+    Ok(json::Value::Expression(expr))
+}
+
 fn object_value(reader: &mut Reader) -> ParseResult<'static, json::Value> {
     try_literal("{", reader)?;
     let space0 = whitespace(reader);
@@ -334,7 +404,7 @@ fn whitespace(reader: &mut Reader) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::ast::TemplateElement;
+    use crate::core::ast::{Expr, ExprValue, TemplateElement, Variable, Whitespace};
 
     use super::*;
 
@@ -690,6 +760,76 @@ mod tests {
         assert_eq!(error.recoverable, false);
     }
 
+    #[test]
+    fn test_matcher_value() {
+        let mut reader = Reader::init("{{any}}");
+        assert_eq!(matcher_value(&mut reader).unwrap(), json::Value::Matcher(json::Matcher {
+            space0: "".to_string(),
+            value: json::MatcherValue::Any {},
+            space1: "".to_string(),
+            source_info: SourceInfo::init(1, 1, 1, 8),
+        }));
+
+        let mut reader = Reader::init("{{ any integer }}");
+        assert_eq!(matcher_value(&mut reader).unwrap(), json::Value::Matcher(json::Matcher {
+            space0: " ".to_string(),
+            value: json::MatcherValue::AnyType { space0: " ".to_string(), name: "integer".to_string() },
+            space1: " ".to_string(),
+            source_info: SourceInfo::init(1, 1, 1, 18),
+        }));
+        assert_eq!(reader.state.cursor, 17);
+
+        let mut reader = Reader::init("{{matches \"\\\\d{4}-.*\"}}");
+        match matcher_value(&mut reader).unwrap() {
+            json::Value::Matcher(matcher) => assert_eq!(matcher.to_string(), "{{matches \"\\\\d{4}-.*\"}}".to_string()),
+            _ => panic!("expecting a matcher"),
+        }
+        assert_eq!(reader.state.cursor, 23);
+
+        let mut reader = Reader::init("[1, {{any integer}}]");
+        assert_eq!(list_value(&mut reader).is_ok(), true);
+    }
+
+    #[test]
+    fn test_matcher_value_error() {
+        let mut reader = Reader::init("{\"a\": 1}");
+        let error = matcher_value(&mut reader).err().unwrap();
+        assert_eq!(error.recoverable, true);
+
+        let mut reader = Reader::init("{{any date}}");
+        let error = matcher_value(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 6 });
+        assert_eq!(error.inner, error::ParseError::Expecting { value: "json type".to_string() });
+        assert_eq!(error.recoverable, false);
+
+        let mut reader = Reader::init("{{name}}");
+        let error = matcher_value(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 3 });
+        assert_eq!(error.inner, error::ParseError::Expecting { value: "matcher".to_string() });
+        assert_eq!(error.recoverable, true);
+    }
+
+    #[test]
+    fn test_expression_value() {
+        let mut reader = Reader::init("{{ count }}");
+        assert_eq!(parse(&mut reader).unwrap(), json::Value::Expression(Expr {
+            space0: Whitespace { value: " ".to_string(), source_info: SourceInfo::init(1, 3, 1, 4) },
+            value: ExprValue::Variable(Variable { name: "count".to_string(), source_info: SourceInfo::init(1, 4, 1, 9) }),
+            filters: vec![],
+            space1: Whitespace { value: " ".to_string(), source_info: SourceInfo::init(1, 9, 1, 10) },
+        }));
+        assert_eq!(reader.state.cursor, 11);
+
+        let mut reader = Reader::init("{{any_id}}");
+        assert_eq!(parse(&mut reader).unwrap()._type(), "expression".to_string());
+
+        let mut reader = Reader::init("[1, {{id}}]");
+        assert_eq!(list_value(&mut reader).is_ok(), true);
+
+        let mut reader = Reader::init("{\"user\": {{user}}}");
+        assert_eq!(parse(&mut reader).is_ok(), true);
+    }
+
     #[test]
     fn test_key() {
         let mut reader = Reader::init("name");
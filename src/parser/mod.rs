@@ -16,7 +16,7 @@
  *
  */
 use error::Error;
-use crate::core::ast::HurlFile;
+use crate::core::ast::{HurlFile, Template};
 
 mod base64;
 mod combinators;
@@ -46,3 +46,9 @@ pub fn parse_hurl_file(s: &str) -> ParseResult<'static, HurlFile> {
 }
 
 
+
+/// parse the content of a template file, such as a `template,payload.json;` body
+pub fn parse_template(s: &str) -> ParseResult<'static, Template> {
+    let mut reader = reader::Reader::init(s);
+    primitives::template_chars(&mut reader, s.chars().count())
+}
@@ -28,15 +28,118 @@ use super::reader::Reader;
 use super::sections::*;
 
 pub fn hurl_file(reader: &mut Reader) -> ParseResult<'static, HurlFile> {
-    let entries = zero_or_more(|p1| entry(p1), reader)?;
+    let sections = file_sections(reader)?;
+    check_duplicated_sections(&sections)?;
+    let templates = zero_or_more(entry_template, reader)?;
+    let entries = zero_or_more(file_entry, reader)?;
     let line_terminators = optional_line_terminators(reader)?;
     eof(reader)?;
+    check_template_calls(&templates, &entries)?;
     Ok(HurlFile {
+        sections,
+        templates,
         entries,
         line_terminators,
     })
 }
 
+fn file_entry(reader: &mut Reader) -> ParseResult<'static, FileEntry> {
+    match optional(template_call, reader)? {
+        Some(call) => Ok(FileEntry::Call(call)),
+        None => Ok(FileEntry::Entry(Box::new(entry(reader)?))),
+    }
+}
+
+fn entry_template(reader: &mut Reader) -> ParseResult<'static, EntryTemplate> {
+    let line_terminators = optional_line_terminators(reader)?;
+    let space0 = zero_or_more_spaces(reader)?;
+    try_literal("TEMPLATE", reader)?;
+    let space1 = one_or_more_spaces(reader)?;
+    let name = expr::variable_name(reader)?;
+    let params = zero_or_more(template_param, reader)?;
+    let line_terminator0 = line_terminator(reader)?;
+    let entry = entry(reader)?;
+    Ok(EntryTemplate {
+        line_terminators,
+        space0,
+        space1,
+        name,
+        params,
+        line_terminator0,
+        entry,
+    })
+}
+
+fn template_param(reader: &mut Reader) -> ParseResult<'static, (Whitespace, Variable)> {
+    let space0 = recover(one_or_more_spaces, reader)?;
+    let name = recover(expr::variable_name, reader)?;
+    Ok((space0, name))
+}
+
+fn template_call(reader: &mut Reader) -> ParseResult<'static, TemplateCall> {
+    let line_terminators = optional_line_terminators(reader)?;
+    let space0 = zero_or_more_spaces(reader)?;
+    try_literal("CALL", reader)?;
+    let space1 = one_or_more_spaces(reader)?;
+    let name = expr::variable_name(reader)?;
+    let line_terminator0 = line_terminator(reader)?;
+    let arguments = zero_or_more(key_value, reader)?;
+    Ok(TemplateCall {
+        line_terminators,
+        space0,
+        space1,
+        name,
+        line_terminator0,
+        arguments,
+    })
+}
+
+// every call must refer to a template of the file and give exactly one argument per parameter
+fn check_template_calls(templates: &[EntryTemplate], entries: &[FileEntry]) -> ParseResult<'static, ()> {
+    for (i, template) in templates.iter().enumerate() {
+        if templates.iter().take(i).any(|t| t.name.name == template.name.name) {
+            return Err(Error {
+                pos: template.name.source_info.start.clone(),
+                recoverable: false,
+                inner: ParseError::DuplicateTemplate,
+            });
+        }
+    }
+    for entry in entries {
+        let call = match entry {
+            FileEntry::Call(call) => call,
+            FileEntry::Entry(_) => continue,
+        };
+        let template = match templates.iter().find(|t| t.name.name == call.name.name) {
+            Some(template) => template,
+            None => return Err(Error {
+                pos: call.name.source_info.start.clone(),
+                recoverable: false,
+                inner: ParseError::UndefinedTemplate { name: call.name.name.clone() },
+            }),
+        };
+        for argument in &call.arguments {
+            if !template.params.iter().any(|(_, param)| param.name == argument.key.value) {
+                return Err(Error {
+                    pos: argument.key.source_info.start.clone(),
+                    recoverable: false,
+                    inner: ParseError::TemplateArgument { name: argument.key.value.clone() },
+                });
+            }
+        }
+        for (_, param) in &template.params {
+            if !call.arguments.iter().any(|argument| argument.key.value == param.name) {
+                return Err(Error {
+                    pos: call.name.source_info.start.clone(),
+                    recoverable: false,
+                    inner: ParseError::MissingTemplateArgument { name: param.name.clone() },
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 fn entry(reader: &mut Reader) -> ParseResult<'static, Entry> {
     let req = request(reader)?;
     let resp = optional(|p1| response(p1), reader)?;
@@ -64,19 +167,7 @@ fn request(reader: &mut Reader) -> ParseResult<'static, Request> {
         reader.state.pos.column,
     );
 
-    // check duplicated section
-    let mut section_names = vec![];
-    for section in sections.clone() {
-        if section_names.contains(&section.name().to_string()) {
-            return Err(Error {
-                pos: section.source_info.start,
-                recoverable: false,
-                inner: ParseError::DuplicateSection,
-            });
-        } else {
-            section_names.push(section.name().to_string());
-        }
-    }
+    check_duplicated_sections(&sections)?;
 
     Ok(Request {
         line_terminators,
@@ -92,6 +183,22 @@ fn request(reader: &mut Reader) -> ParseResult<'static, Request> {
     })
 }
 
+fn check_duplicated_sections(sections: &[Section]) -> ParseResult<'static, ()> {
+    let mut section_names = vec![];
+    for section in sections {
+        if section_names.contains(&section.name().to_string()) {
+            return Err(Error {
+                pos: section.source_info.start.clone(),
+                recoverable: false,
+                inner: ParseError::DuplicateSection,
+            });
+        } else {
+            section_names.push(section.name().to_string());
+        }
+    }
+    Ok(())
+}
+
 fn response(reader: &mut Reader) -> ParseResult<'static, Response> {
     let start = reader.state.clone();
     let line_terminators = optional_line_terminators(reader)?;
@@ -314,6 +421,90 @@ mod tests {
         assert_eq!(hurl_file.entries.len(), 1);
     }
 
+    #[test]
+    fn test_hurl_file_sections() {
+        let mut reader = Reader::init("# header\n[Variables]\ntoken: abc\n[Headers]\nAuthorization: Bearer {{token}}\n\nGET http://google.fr\n[Headers]\n");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 8, column: 1 });
+        assert_eq!(error.inner, ParseError::RequestSection);
+
+        let mut reader = Reader::init("# header\n[Variables]\ntoken: abc\n[Headers]\nAuthorization: Bearer {{token}}\n\nGET http://google.fr");
+        let hurl_file = hurl_file(&mut reader).unwrap();
+        assert_eq!(hurl_file.sections.iter().map(|s| s.name()).collect::<Vec<&str>>(), vec!["Variables", "Headers"]);
+        assert_eq!(hurl_file.variables()[0].key.value, "token");
+        assert_eq!(hurl_file.entries.len(), 1);
+        match &hurl_file.entries[0] {
+            FileEntry::Entry(entry) => assert!(entry.request.headers.is_empty()),
+            FileEntry::Call(_) => panic!("expecting an entry"),
+        }
+    }
+
+    #[test]
+    fn test_hurl_file_includes() {
+        let mut reader = Reader::init("[Includes]\ninclude/login.hurl\n../common.hurl # comment\n\nGET http://google.fr");
+        let hurl_file = hurl_file(&mut reader).unwrap();
+        let includes: Vec<String> = hurl_file.includes().iter().map(|i| i.filename.value.clone()).collect();
+        assert_eq!(includes, vec!["include/login.hurl", "../common.hurl"]);
+        assert_eq!(hurl_file.includes()[1].filename.source_info, SourceInfo::init(3, 1, 3, 15));
+        assert_eq!(hurl_file.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_hurl_file_templates() {
+        let mut reader = Reader::init("TEMPLATE create_user name role # comment\nPOST http://localhost/users\nHTTP/1.0 201\n\nCALL create_user\nname: bob\nrole: admin\nGET http://localhost/users\nCALL create_user\nrole: user\nname: alice\n");
+        let hurl_file = hurl_file(&mut reader).unwrap();
+        assert_eq!(hurl_file.templates.len(), 1);
+        let params: Vec<String> = hurl_file.templates[0].params.iter().map(|(_, p)| p.name.clone()).collect();
+        assert_eq!(params, vec!["name", "role"]);
+        assert_eq!(hurl_file.templates[0].entry.request.url.source_info, SourceInfo::init(2, 6, 2, 28));
+        assert_eq!(hurl_file.entries.len(), 3);
+        match &hurl_file.entries[0] {
+            FileEntry::Call(call) => {
+                assert_eq!(call.name.name, "create_user");
+                assert_eq!(call.name.source_info, SourceInfo::init(5, 6, 5, 17));
+                assert_eq!(call.arguments.len(), 2);
+            }
+            FileEntry::Entry(_) => panic!("expecting a template call"),
+        }
+        assert!(hurl_file.template("create_user").is_some());
+    }
+
+    #[test]
+    fn test_hurl_file_templates_error() {
+        let mut reader = Reader::init("CALL create_user\nname: bob\n");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 6 });
+        assert_eq!(error.inner, ParseError::UndefinedTemplate { name: "create_user".to_string() });
+
+        let mut reader = Reader::init("TEMPLATE create_user name\nPOST http://localhost/users\nCALL create_user\nname: bob\nrole: admin\n");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 5, column: 1 });
+        assert_eq!(error.inner, ParseError::TemplateArgument { name: "role".to_string() });
+
+        let mut reader = Reader::init("TEMPLATE create_user name role\nPOST http://localhost/users\nCALL create_user\nname: bob\n");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 3, column: 6 });
+        assert_eq!(error.inner, ParseError::MissingTemplateArgument { name: "role".to_string() });
+
+        let mut reader = Reader::init("TEMPLATE a\nGET http://localhost\nTEMPLATE a\nGET http://localhost\n");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 3, column: 10 });
+        assert_eq!(error.inner, ParseError::DuplicateTemplate);
+    }
+
+    #[test]
+    fn test_hurl_file_sections_error() {
+        let mut reader = Reader::init("[Cookies]\nGET http://google.fr");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 1 });
+        assert_eq!(error.inner, ParseError::FileSection);
+
+        let mut reader = Reader::init("[Headers]\n[Headers]\nGET http://google.fr");
+        let error = hurl_file(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 2, column: 1 });
+        assert_eq!(error.inner, ParseError::DuplicateSection);
+    }
+
 
     #[test]
     fn test_entry() {
@@ -637,10 +828,11 @@ mod tests {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 10, 1, 10),
                         },
-                        variable: Variable {
+                        value: ExprValue::Variable(Variable {
                             name: String::from("host"),
                             source_info: SourceInfo::init(1, 10, 1, 14),
-                        },
+                        }),
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::from(""),
                             source_info: SourceInfo::init(1, 14, 1, 14),
@@ -4,6 +4,7 @@ use crate::core::common::SourceInfo;
 use super::combinators::*;
 use super::error::*;
 use super::expr;
+use super::json;
 use super::ParseResult;
 use super::primitives::*;
 use super::reader::Reader;
@@ -52,6 +53,9 @@ fn predicate_func_value(reader: &mut Reader) -> ParseResult<'static, PredicateFu
             include_predicate,
             match_predicate,
             exist_predicate,
+            json_equal_predicate,
+            jsonschema_predicate,
+            xmlschema_predicate,
         ],
         reader,
     ) {
@@ -144,7 +148,7 @@ fn include_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncV
 fn match_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncValue> {
     try_literal("matches", reader)?;
     let space0 = one_or_more_spaces(reader)?;
-    let value = quoted_template(reader)?;
+    let value = regex_value(reader)?;
     Ok(PredicateFuncValue::Match { space0, value })
 }
 
@@ -153,6 +157,43 @@ fn exist_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncVal
     Ok(PredicateFuncValue::Exist {})
 }
 
+fn json_equal_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("jsonEquals", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let start = reader.state.clone();
+    match json::parse(reader) {
+        Ok(value) => Ok(PredicateFuncValue::JsonEqual { space0, value }),
+        Err(e) if !e.recoverable => Err(e),
+        Err(_) => Err(Error {
+            pos: start.pos,
+            recoverable: false,
+            inner: ParseError::PredicateValue {},
+        }),
+    }
+}
+
+fn jsonschema_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("jsonschema", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    literal("file,", reader)?;
+    let space1 = zero_or_more_spaces(reader)?;
+    let filename = filename(reader)?;
+    let space2 = zero_or_more_spaces(reader)?;
+    literal(";", reader)?;
+    Ok(PredicateFuncValue::JsonSchema { space0, space1, filename, space2 })
+}
+
+fn xmlschema_predicate(reader: &mut Reader) -> ParseResult<'static, PredicateFuncValue> {
+    try_literal("xmlschema", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    literal("file,", reader)?;
+    let space1 = zero_or_more_spaces(reader)?;
+    let filename = filename(reader)?;
+    let space2 = zero_or_more_spaces(reader)?;
+    literal(";", reader)?;
+    Ok(PredicateFuncValue::XmlSchema { space0, space1, filename, space2 })
+}
+
 
 /* internal to the parser */
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -319,10 +360,11 @@ mod tests {
                         value: String::from(""),
                         source_info: SourceInfo::init(1, 10, 1, 10),
                     },
-                    variable: Variable {
+                    value: ExprValue::Variable(Variable {
                         name: "count".to_string(),
                         source_info: SourceInfo::init(1, 10, 1, 15),
-                    },
+                    }),
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::from(""),
                         source_info: SourceInfo::init(1, 15, 1, 15),
@@ -366,6 +408,55 @@ mod tests {
         assert_eq!(error.inner, ParseError::Expecting { value: "\"".to_string() });
     }
 
+    #[test]
+    fn test_jsonschema_predicate() {
+        let mut reader = Reader::init("jsonschema file,user.schema.json;");
+        assert_eq!(jsonschema_predicate(&mut reader).unwrap(), PredicateFuncValue::JsonSchema {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 11, 1, 12) },
+            space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 17, 1, 17) },
+            filename: Filename { value: String::from("user.schema.json"), source_info: SourceInfo::init(1, 17, 1, 33) },
+            space2: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 33, 1, 33) },
+        });
+
+        let mut reader = Reader::init("jsonschema user.schema.json");
+        let error = jsonschema_predicate(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 12 });
+        assert_eq!(error.recoverable, false);
+        assert_eq!(error.inner, ParseError::Expecting { value: "file,".to_string() });
+    }
+
+    #[test]
+    fn test_json_equal_predicate() {
+        let mut reader = Reader::init("jsonEquals [1, 2]");
+        assert_eq!(json_equal_predicate(&mut reader).unwrap(), PredicateFuncValue::JsonEqual {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 11, 1, 12) },
+            value: crate::core::json::Value::List {
+                space0: "".to_string(),
+                elements: vec![
+                    crate::core::json::ListElement { space0: "".to_string(), value: crate::core::json::Value::Number("1".to_string()), space1: "".to_string() },
+                    crate::core::json::ListElement { space0: " ".to_string(), value: crate::core::json::Value::Number("2".to_string()), space1: "".to_string() },
+                ],
+            },
+        });
+
+        let mut reader = Reader::init("jsonEquals xxx");
+        let error = json_equal_predicate(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 12 });
+        assert_eq!(error.recoverable, false);
+        assert_eq!(error.inner, ParseError::PredicateValue {});
+    }
+
+    #[test]
+    fn test_xmlschema_predicate() {
+        let mut reader = Reader::init("xmlschema file, order.xsd ;");
+        assert_eq!(xmlschema_predicate(&mut reader).unwrap(), PredicateFuncValue::XmlSchema {
+            space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 10, 1, 11) },
+            space1: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 16, 1, 17) },
+            filename: Filename { value: String::from("order.xsd"), source_info: SourceInfo::init(1, 17, 1, 26) },
+            space2: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 26, 1, 27) },
+        });
+    }
+
     #[test]
     fn test_predicate_value() {
         let mut reader = Reader::init("true");
@@ -441,6 +441,32 @@ pub fn raw_string_value(reader: &mut Reader) -> ParseResult<'static, Template> {
     })
 }
 
+// read the next count chars as an unquoted template
+// used for xml bodies and template files
+pub fn template_chars(reader: &mut Reader, count: usize) -> ParseResult<'static, Template> {
+    let mut chars = vec![];
+    let start = reader.state.pos.clone();
+    for _ in 0..count {
+        let pos = reader.state.pos.clone();
+        match reader.read() {
+            None => break,
+            Some(c) => chars.push((c, c.to_string(), pos)),
+        }
+    }
+    let end = reader.state.pos.clone();
+
+    let encoded_string = template::EncodedString {
+        source_info: SourceInfo { start: start.clone(), end: end.clone() },
+        chars,
+    };
+    let elements = template::templatize(encoded_string)?;
+    Ok(Template {
+        quotes: false,
+        elements,
+        source_info: SourceInfo { start, end },
+    })
+}
+
 pub fn eof(reader: &mut Reader) -> ParseResult<'static, ()> {
     if reader.is_eof() {
         Ok(())
@@ -496,6 +522,97 @@ pub fn hex_digit(reader: &mut Reader) -> ParseResult<'static, u32> {
     }
 }
 
+pub fn regex_value(reader: &mut Reader) -> ParseResult<'static, RegexValue> {
+    if reader.peek() == Some('/') {
+        return Ok(RegexValue::Literal(regex_literal(reader)?));
+    }
+    let template = quoted_template(reader)?;
+
+    // without any variable, the regex can already be validated
+    if let [TemplateElement::String { value, .. }] = template.elements.as_slice() {
+        if regex::Regex::new(value.as_str()).is_err() {
+            return Err(Error {
+                pos: template.source_info.start,
+                recoverable: false,
+                inner: ParseError::RegexExpr,
+            });
+        }
+    }
+    Ok(RegexValue::Template(template))
+}
+
+pub fn regex_literal(reader: &mut Reader) -> ParseResult<'static, RegexLiteral> {
+    let start = reader.state.pos.clone();
+    literal("/", reader)?;
+
+    // the delimiter can be escaped with \/
+    // any other escape sequence is left to the regex engine
+    let mut value = String::from("");
+    let mut pattern = String::from("");
+    loop {
+        match reader.read() {
+            None | Some('\n') => {
+                return Err(Error {
+                    pos: reader.state.pos.clone(),
+                    recoverable: false,
+                    inner: ParseError::Expecting { value: "/".to_string() },
+                });
+            }
+            Some('/') => break,
+            Some('\\') => {
+                value.push('\\');
+                match reader.read() {
+                    Some('/') => {
+                        value.push('/');
+                        pattern.push('/');
+                    }
+                    Some(c) => {
+                        value.push(c);
+                        pattern.push('\\');
+                        pattern.push(c);
+                    }
+                    None => {}
+                }
+            }
+            Some(c) => {
+                value.push(c);
+                pattern.push(c);
+            }
+        }
+    }
+    let flags = reader.read_while(|c| c.is_ascii_alphabetic());
+    let end = reader.state.pos.clone();
+
+    let mut builder = regex::RegexBuilder::new(pattern.as_str());
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            'g' => &mut builder,
+            _ => return Err(Error {
+                pos: start,
+                recoverable: false,
+                inner: ParseError::RegexExpr,
+            }),
+        };
+    }
+    match builder.build() {
+        Ok(inner) => Ok(RegexLiteral {
+            value,
+            flags,
+            inner,
+            source_info: SourceInfo { start, end },
+        }),
+        Err(_) => Err(Error {
+            pos: start,
+            recoverable: false,
+            inner: ParseError::RegexExpr,
+        }),
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -679,10 +796,11 @@ mod tests {
                                 value: "".to_string(),
                                 source_info: SourceInfo::init(1, 18, 1, 18),
                             },
-                            variable: Variable {
+                            value: ExprValue::Variable(Variable {
                                 name: "name".to_string(),
                                 source_info: SourceInfo::init(1, 18, 1, 22),
-                            },
+                            }),
+                            filters: vec![],
                             space1: Whitespace {
                                 value: "".to_string(),
                                 source_info: SourceInfo::init(1, 22, 1, 22),
@@ -1098,4 +1216,55 @@ mod tests {
         assert_eq!(error.inner, ParseError::HexDigit {});
         assert_eq!(error.recoverable, true);
     }
+
+    #[test]
+    fn test_regex_literal() {
+        let mut reader = Reader::init(r#"/Hello \/ (?P<name>\w+)/ig xxx"#);
+        let regex = regex_literal(&mut reader).unwrap();
+        assert_eq!(regex.value, r#"Hello \/ (?P<name>\w+)"#.to_string());
+        assert_eq!(regex.flags, "ig".to_string());
+        assert_eq!(regex.source_info, SourceInfo::init(1, 1, 1, 27));
+        assert_eq!(regex.inner.as_str(), r#"Hello / (?P<name>\w+)"#);
+        assert!(regex.inner.is_match("hello / bob"));
+        assert_eq!(reader.state.cursor, 26);
+    }
+
+    #[test]
+    fn test_regex_literal_error() {
+        let mut reader = Reader::init("/abc");
+        let error = regex_literal(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 5 });
+        assert_eq!(error.inner, ParseError::Expecting { value: String::from("/") });
+        assert_eq!(error.recoverable, false);
+
+        let mut reader = Reader::init("/[a/");
+        let error = regex_literal(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 1 });
+        assert_eq!(error.inner, ParseError::RegexExpr);
+        assert_eq!(error.recoverable, false);
+
+        let mut reader = Reader::init("/a/z");
+        let error = regex_literal(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 1 });
+        assert_eq!(error.inner, ParseError::RegexExpr);
+        assert_eq!(error.recoverable, false);
+    }
+
+    #[test]
+    fn test_regex_value() {
+        let mut reader = Reader::init(r#""\\d+""#);
+        let value = regex_value(&mut reader).unwrap();
+        assert_eq!(value.is_global(), false);
+        assert_eq!(value.source_info(), SourceInfo::init(1, 1, 1, 7));
+
+        let mut reader = Reader::init("/\\d+/g");
+        let value = regex_value(&mut reader).unwrap();
+        assert_eq!(value.is_global(), true);
+
+        let mut reader = Reader::init(r#""[a""#);
+        let error = regex_value(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 1, column: 1 });
+        assert_eq!(error.inner, ParseError::RegexExpr);
+        assert_eq!(error.recoverable, false);
+    }
 }
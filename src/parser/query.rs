@@ -4,6 +4,7 @@ use crate::core::common::SourceInfo;
 
 use super::combinators::*;
 use super::cookiepath::cookiepath;
+use super::error::*;
 use super::ParseResult;
 use super::primitives::*;
 use super::reader::Reader;
@@ -20,11 +21,20 @@ pub fn query(reader: &mut Reader) -> ParseResult<'static, Query> {
 }
 
 
-pub fn subquery(reader: &mut Reader) -> ParseResult<'static, Subquery> {
+pub fn filters(reader: &mut Reader) -> ParseResult<'static, Vec<(Whitespace, Filter)>> {
+    zero_or_more(|p1| {
+        let space0 = recover(one_or_more_spaces, p1)?;
+        let f = filter(p1)?;
+        Ok((space0, f))
+    }, reader)
+}
+
+
+pub fn filter(reader: &mut Reader) -> ParseResult<'static, Filter> {
     let start = reader.state.pos.clone();
-    let value = subquery_value(reader)?;
+    let value = filter_value(reader)?;
     let end = reader.state.pos.clone();
-    Ok(Subquery {
+    Ok(Filter {
         source_info: SourceInfo { start, end },
         value,
     })
@@ -39,6 +49,7 @@ fn query_value(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
             cookie_query,
             body_query,
             xpath_query,
+            css_query,
             jsonpath_query,
             regex_query,
             variable_query,
@@ -92,6 +103,14 @@ fn xpath_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
 }
 
 
+fn css_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
+    try_literal("css", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let expr = quoted_template(reader)?;
+    Ok(QueryValue::Css { space0, expr })
+}
+
+
 fn jsonpath_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
     try_literal("jsonpath", reader)?;
     let space0 = one_or_more_spaces(reader)?;
@@ -117,7 +136,7 @@ fn jsonpath_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
 fn regex_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
     try_literal("regex", reader)?;
     let space0 = one_or_more_spaces(reader)?;
-    let expr = quoted_template(reader)?;
+    let expr = regex_value(reader)?;
     Ok(QueryValue::Regex { space0, expr })
 }
 
@@ -130,21 +149,120 @@ fn variable_query(reader: &mut Reader) -> ParseResult<'static, QueryValue> {
 }
 
 
-fn subquery_value(reader: &mut Reader) -> ParseResult<'static, SubqueryValue> {
+fn filter_value(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
     choice(
         vec![
-            regex_subquery,
+            count_filter,
+            nth_filter,
+            split_filter,
+            replace_filter,
+            url_decode_filter,
+            url_encode_filter,
+            base64_decode_filter,
+            to_int_filter,
+            to_float_filter,
+            html_unescape_filter,
+            jsonpath_filter,
+            regex_filter,
         ],
         reader,
     )
 }
 
 
-fn regex_subquery(reader: &mut Reader) -> ParseResult<'static, SubqueryValue> {
-    try_literal("regex", reader)?;
+fn count_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    let save = reader.state.clone();
+    try_literal("count", reader)?;
+    // do not take the countEquals predicate for a count filter
+    if let Some(c) = reader.peek() {
+        if c.is_alphanumeric() {
+            reader.state = save.clone();
+            return Err(Error {
+                pos: save.pos,
+                recoverable: true,
+                inner: ParseError::Expecting { value: "count".to_string() },
+            });
+        }
+    }
+    Ok(FilterValue::Count {})
+}
+
+
+fn nth_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("nth", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let n = nonrecover(natural, reader)?;
+    Ok(FilterValue::Nth { space0, n })
+}
+
+
+fn split_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("split", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let sep = quoted_template(reader)?;
+    Ok(FilterValue::Split { space0, sep })
+}
+
+
+fn replace_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("replace", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let old_value = quoted_template(reader)?;
+    let space1 = one_or_more_spaces(reader)?;
+    let new_value = quoted_template(reader)?;
+    Ok(FilterValue::Replace { space0, old_value, space1, new_value })
+}
+
+
+fn url_decode_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("urlDecode", reader)?;
+    Ok(FilterValue::UrlDecode {})
+}
+
+
+fn url_encode_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("urlEncode", reader)?;
+    Ok(FilterValue::UrlEncode {})
+}
+
+
+fn base64_decode_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("base64Decode", reader)?;
+    Ok(FilterValue::Base64Decode {})
+}
+
+
+fn to_int_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("toInt", reader)?;
+    Ok(FilterValue::ToInt {})
+}
+
+
+fn to_float_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("toFloat", reader)?;
+    Ok(FilterValue::ToFloat {})
+}
+
+
+fn html_unescape_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("htmlUnescape", reader)?;
+    Ok(FilterValue::HtmlUnescape {})
+}
+
+
+fn jsonpath_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("jsonpath", reader)?;
     let space0 = one_or_more_spaces(reader)?;
     let expr = quoted_template(reader)?;
-    Ok(SubqueryValue::Regex { space0, expr })
+    Ok(FilterValue::Jsonpath { space0, expr })
+}
+
+
+fn regex_filter(reader: &mut Reader) -> ParseResult<'static, FilterValue> {
+    try_literal("regex", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let expr = regex_value(reader)?;
+    Ok(FilterValue::Regex { space0, expr })
 }
 
 
@@ -276,6 +394,30 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_css_query() {
+        let mut reader = Reader::init("css \"div.item > a\"");
+        assert_eq!(
+            css_query(&mut reader).unwrap(),
+            QueryValue::Css {
+                space0: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::init(1, 4, 1, 5),
+                },
+                expr: Template {
+                    quotes: true,
+                    elements: vec![
+                        TemplateElement::String {
+                            value: String::from("div.item > a"),
+                            encoded: String::from("div.item > a"),
+                        }
+                    ],
+                    source_info: SourceInfo::init(1, 5, 1, 19),
+                },
+            },
+        );
+    }
+
     #[test]
     fn test_jsonpath_query() {
         let mut reader = Reader::init("jsonpath \"$['statusCode']\"");
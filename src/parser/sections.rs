@@ -24,7 +24,7 @@ use super::error::*;
 use super::ParseResult;
 use super::predicate::predicate;
 use super::primitives::*;
-use super::query::{query, subquery};
+use super::query::{filters, query};
 use super::reader::Reader;
 use super::string::*;
 
@@ -36,6 +36,7 @@ pub fn request_sections(reader: &mut Reader) -> ParseResult<'static, Vec<Section
             "FormParams",
             "MultipartFormData",
             "Cookies",
+            "Options",
         ]
             .contains(&section.name())
         {
@@ -52,7 +53,7 @@ pub fn request_sections(reader: &mut Reader) -> ParseResult<'static, Vec<Section
 pub fn response_sections(reader: &mut Reader) -> ParseResult<'static, Vec<Section>> {
     let sections = zero_or_more(|p1| section(p1), reader)?;
     for section in sections.clone() {
-        if !["Captures", "Asserts"].contains(&section.name()) {
+        if !["Captures", "Asserts", "Namespaces", "JsonIgnore"].contains(&section.name()) {
             return Err(Error {
                 pos: section.source_info.start,
                 recoverable: false,
@@ -63,6 +64,30 @@ pub fn response_sections(reader: &mut Reader) -> ParseResult<'static, Vec<Sectio
     Ok(sections)
 }
 
+pub fn file_sections(reader: &mut Reader) -> ParseResult<'static, Vec<Section>> {
+    let sections = zero_or_more(|p1| section(p1), reader)?;
+    for section in sections.clone() {
+        if !["Includes", "Variables", "Headers", "QueryStringParams", "Namespaces", "Options"].contains(&section.name()) {
+            return Err(Error {
+                pos: section.source_info.start,
+                recoverable: false,
+                inner: ParseError::FileSection,
+            });
+        }
+        // a loop or a skip condition only applies to an entry
+        if let SectionValue::Options(options) = section.value {
+            if let Some(option) = options.into_iter().find(|option| !["data", "name", "tags"].contains(&option.key.value.as_str())) {
+                return Err(Error {
+                    pos: option.key.source_info.start,
+                    recoverable: false,
+                    inner: ParseError::OptionName { name: option.key.value },
+                });
+            }
+        }
+    }
+    Ok(sections)
+}
+
 fn section(reader: &mut Reader) -> ParseResult<'static, Section> {
     let line_terminators = optional_line_terminators(reader)?;
     let space0 = zero_or_more_spaces(reader)?;
@@ -80,6 +105,12 @@ fn section(reader: &mut Reader) -> ParseResult<'static, Section> {
         "Cookies" => section_value_cookies(reader)?,
         "Captures" => section_value_captures(reader)?,
         "Asserts" => section_value_asserts(reader)?,
+        "Namespaces" => section_value_namespaces(reader)?,
+        "JsonIgnore" => section_value_json_ignore(reader)?,
+        "Includes" => section_value_includes(reader)?,
+        "Variables" => section_value_variables(reader)?,
+        "Headers" => section_value_headers(reader)?,
+        "Options" => section_value_options(reader)?,
         _ => {
             return Err(Error {
                 pos: Pos {
@@ -140,6 +171,93 @@ fn section_value_asserts(reader: &mut Reader) -> ParseResult<'static, SectionVal
     Ok(SectionValue::Asserts(asserts))
 }
 
+fn section_value_namespaces(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| key_value(p1), reader)?;
+    Ok(SectionValue::Namespaces(items))
+}
+
+fn section_value_json_ignore(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(ignored_path, reader)?;
+    Ok(SectionValue::JsonIgnore(items))
+}
+
+fn section_value_includes(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(include, reader)?;
+    Ok(SectionValue::Includes(items))
+}
+
+fn section_value_variables(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| key_value(p1), reader)?;
+    Ok(SectionValue::Variables(items))
+}
+
+fn section_value_headers(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| key_value(p1), reader)?;
+    Ok(SectionValue::Headers(items))
+}
+
+fn section_value_options(reader: &mut Reader) -> ParseResult<'static, SectionValue> {
+    let items = zero_or_more(|p1| key_value(p1), reader)?;
+    for item in items.clone() {
+        if !["data", "loop", "skip", "name", "tags"].contains(&item.key.value.as_str()) {
+            return Err(Error {
+                pos: item.key.source_info.start,
+                recoverable: false,
+                inner: ParseError::OptionName { name: item.key.value },
+            });
+        }
+        if item.key.value == "loop" && item.loop_variables().is_none() {
+            return Err(Error {
+                pos: item.value.source_info.start,
+                recoverable: false,
+                inner: ParseError::LoopOption,
+            });
+        }
+        if item.key.value == "skip" && item.condition().is_none() {
+            return Err(Error {
+                pos: item.value.source_info.start,
+                recoverable: false,
+                inner: ParseError::SkipOption,
+            });
+        }
+        if ["name", "tags"].contains(&item.key.value.as_str()) && item.text().map_or(true, |text| text.is_empty()) {
+            return Err(Error {
+                pos: item.value.source_info.start,
+                recoverable: false,
+                inner: ParseError::OptionValue { name: item.key.value },
+            });
+        }
+    }
+    Ok(SectionValue::Options(items))
+}
+
+fn include(reader: &mut Reader) -> ParseResult<'static, Include> {
+    let line_terminators = optional_line_terminators(reader)?;
+    let space0 = zero_or_more_spaces(reader)?;
+    // the following request or section ends the list of includes
+    let filename = recover(filename, reader)?;
+    let line_terminator0 = recover(line_terminator, reader)?;
+    Ok(Include {
+        line_terminators,
+        space0,
+        filename,
+        line_terminator0,
+    })
+}
+
+fn ignored_path(reader: &mut Reader) -> ParseResult<'static, IgnoredPath> {
+    let line_terminators = optional_line_terminators(reader)?;
+    let space0 = zero_or_more_spaces(reader)?;
+    let path = recover(quoted_template, reader)?;
+    let line_terminator0 = line_terminator(reader)?;
+    Ok(IgnoredPath {
+        line_terminators,
+        space0,
+        path,
+        line_terminator0,
+    })
+}
+
 fn cookie(reader: &mut Reader) -> ParseResult<'static, Cookie> {
     // let start = reader.state.clone();
     let line_terminators = optional_line_terminators(reader)?;
@@ -281,29 +399,7 @@ fn capture(reader: &mut Reader) -> ParseResult<'static, Capture> {
     recover(|p1| literal(":", p1), reader)?;
     let space2 = zero_or_more_spaces(reader)?;
     let q = query(reader)?;
-    let space3 = zero_or_more_spaces(reader)?;
-    let optional_subquery = if space3.value.is_empty() {
-        None
-    } else {
-        optional(subquery, reader)?
-    };
-
-    //    let (space3, subq) = match one_or_more_spaces(reader) {
-    //        Err(_) => {
-    //            reader.state = save;
-    //            (Whitespace { value: "".to_string(), source_info: SourceInfo { start: reader.state.pos.clone(), end: reader.state.pos.clone() } }, None)
-    //        }
-    //        Ok(space3) => {
-    //            match subquery(reader) {
-    //                Err(_) => {
-    //                    reader.state = save;
-    //                    (Whitespace { value: "".to_string(), source_info: SourceInfo { start: reader.state.pos.clone(), end: reader.state.pos.clone() } }, None)
-    //                }
-    //                Ok(subquery) => (space3, Some(subquery))
-    //            }
-    //        }
-    //    };
-
+    let filters = filters(reader)?;
     let line_terminator0 = line_terminator(reader)?;
     Ok(Capture {
         line_terminators,
@@ -312,8 +408,7 @@ fn capture(reader: &mut Reader) -> ParseResult<'static, Capture> {
         space1,
         space2,
         query: q,
-        space3,
-        subquery: optional_subquery,
+        filters,
         line_terminator0,
     })
 }
@@ -322,6 +417,7 @@ fn assert(reader: &mut Reader) -> ParseResult<'static, Assert> {
     let line_terminators = optional_line_terminators(reader)?;
     let space0 = zero_or_more_spaces(reader)?;
     let query0 = query(reader)?;
+    let filters = filters(reader)?;
     let space1 = one_or_more_spaces(reader)?;
     let predicate0 = predicate(reader)?;
 
@@ -354,6 +450,7 @@ fn assert(reader: &mut Reader) -> ParseResult<'static, Assert> {
         line_terminators,
         space0,
         query: query0,
+        filters,
         space1,
         predicate: predicate0,
         line_terminator0,
@@ -418,6 +515,7 @@ mod tests {
                             },
                         },
                     },
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::from(" "),
                         source_info: SourceInfo::init(2, 18, 2, 19),
@@ -636,7 +734,7 @@ mod tests {
     }
 
     #[test]
-    fn test_capture_with_subquery() {
+    fn test_capture_with_filter() {
         let mut reader = Reader::init("token: header \"Location\" regex \"token=(.*)\"");
         let capture0 = capture(&mut reader).unwrap();
 
@@ -661,38 +759,48 @@ mod tests {
             }
         );
         assert_eq!(
-            capture0.space3,
-            Whitespace {
-                value: " ".to_string(),
-                source_info: SourceInfo::init(1, 25, 1, 26),
-            }
+            capture0.filters,
+            vec![(
+                Whitespace {
+                    value: " ".to_string(),
+                    source_info: SourceInfo::init(1, 25, 1, 26),
+                },
+                Filter {
+                    source_info: SourceInfo::init(1, 26, 1, 44),
+                    value: FilterValue::Regex {
+                        space0: Whitespace {
+                            value: " ".to_string(),
+                            source_info: SourceInfo::init(1, 31, 1, 32),
+                        },
+                        expr: RegexValue::Template(Template {
+                            quotes: true,
+                            elements: vec![TemplateElement::String {
+                                value: "token=(.*)".to_string(),
+                                encoded: "token=(.*)".to_string(),
+                            }],
+                            source_info: SourceInfo::init(1, 32, 1, 44),
+                        }),
+                    },
+                }
+            )]
         );
+        assert_eq!(reader.state.cursor, 43);
+    }
 
+    #[test]
+    fn test_capture_with_filters() {
+        let mut reader = Reader::init("ids: jsonpath \"$.ids\" split \",\" nth 1 toInt # comment");
+        let capture0 = capture(&mut reader).unwrap();
         assert_eq!(
-            capture0.subquery.unwrap(),
-            Subquery {
-                source_info: SourceInfo::init(1, 26, 1, 44),
-                value: SubqueryValue::Regex {
-                    space0: Whitespace {
-                        value: " ".to_string(),
-                        source_info: SourceInfo::init(1, 31, 1, 32),
-                    },
-                    expr: Template {
-                        quotes: true,
-                        elements: vec![TemplateElement::String {
-                            value: "token=(.*)".to_string(),
-                            encoded: "token=(.*)".to_string(),
-                        }],
-                        source_info: SourceInfo::init(1, 32, 1, 44),
-                    },
-                },
-            }
+            capture0.filters.iter().map(|(_, f)| f.value.name()).collect::<Vec<&str>>(),
+            vec!["split", "nth", "toInt"]
         );
-        assert_eq!(reader.state.cursor, 43);
+        assert_eq!(capture0.filters.get(1).unwrap().1.source_info, SourceInfo::init(1, 33, 1, 38));
+        assert_eq!(capture0.line_terminator0.comment, Some(Comment { value: " comment".to_string() }));
     }
 
     #[test]
-    fn test_capture_with_subquery_error() {
+    fn test_capture_with_filter_error() {
         let mut reader = Reader::init("token: header \"Location\" regex ");
         let error = capture(&mut reader).err().unwrap();
         assert_eq!(
@@ -755,6 +863,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_assert_with_filters() {
+        let mut reader = Reader::init("jsonpath \"$.books\" count equals 2");
+        let assert0 = assert(&mut reader).unwrap();
+        assert_eq!(
+            assert0.filters,
+            vec![(
+                Whitespace {
+                    value: " ".to_string(),
+                    source_info: SourceInfo::init(1, 19, 1, 20),
+                },
+                Filter {
+                    source_info: SourceInfo::init(1, 20, 1, 25),
+                    value: FilterValue::Count {},
+                }
+            )]
+        );
+        assert_eq!(assert0.predicate.predicate_func.source_info, SourceInfo::init(1, 26, 1, 34));
+
+        let mut reader = Reader::init("jsonpath \"$.books\" countEquals 2");
+        let assert0 = assert(&mut reader).unwrap();
+        assert!(assert0.filters.is_empty());
+        assert_eq!(assert0.space1.source_info, SourceInfo::init(1, 19, 1, 20));
+    }
+
     #[test]
     fn test_assert_jsonpath() {
         let mut reader = Reader::init("jsonpath \"$.errors\" equals 5");
@@ -780,4 +913,136 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_namespaces_section() {
+        let mut reader = Reader::init("[Namespaces]\nsoap: http://schemas.xmlsoap.org/soap/envelope/\n");
+        let section = section(&mut reader).unwrap();
+        assert_eq!(section.name(), "Namespaces");
+        match section.value {
+            SectionValue::Namespaces(items) => {
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].key.value, "soap".to_string());
+                assert_eq!(items[0].value.elements, vec![TemplateElement::String {
+                    value: "http://schemas.xmlsoap.org/soap/envelope/".to_string(),
+                    encoded: "http://schemas.xmlsoap.org/soap/envelope/".to_string(),
+                }]);
+            }
+            _ => panic!("expecting a namespaces section"),
+        }
+
+        let mut reader = Reader::init("[Namespaces]\nsoap: http://schemas.xmlsoap.org/soap/envelope/\n");
+        assert_eq!(response_sections(&mut reader).unwrap().len(), 1);
+
+        let mut reader = Reader::init("[Namespaces]\nsoap: http://schemas.xmlsoap.org/soap/envelope/\n");
+        assert_eq!(file_sections(&mut reader).unwrap().len(), 1);
+
+        let mut reader = Reader::init("[Namespaces]\nsoap: http://schemas.xmlsoap.org/soap/envelope/\n");
+        let error = request_sections(&mut reader).err().unwrap();
+        assert_eq!(error.inner, ParseError::RequestSection);
+    }
+
+    #[test]
+    fn test_json_ignore_section() {
+        let mut reader = Reader::init("[JsonIgnore]\n\"$.id\"\n\"$.items[*].createdAt\"\n");
+        let section = section(&mut reader).unwrap();
+        assert_eq!(section.name(), "JsonIgnore");
+        match section.value {
+            SectionValue::JsonIgnore(items) => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1].path.elements, vec![TemplateElement::String {
+                    value: "$.items[*].createdAt".to_string(),
+                    encoded: "$.items[*].createdAt".to_string(),
+                }]);
+            }
+            _ => panic!("expecting a json ignore section"),
+        }
+    }
+
+    #[test]
+    fn test_options_section() {
+        let mut reader = Reader::init("[Options]\ndata: users.csv\n");
+        let section = section(&mut reader).unwrap();
+        assert_eq!(section.name(), "Options");
+        match section.value {
+            SectionValue::Options(items) => {
+                assert_eq!(items.len(), 1);
+                assert_eq!(items[0].key.value, "data".to_string());
+                assert_eq!(items[0].value.elements, vec![TemplateElement::String {
+                    value: "users.csv".to_string(),
+                    encoded: "users.csv".to_string(),
+                }]);
+            }
+            _ => panic!("expecting an options section"),
+        }
+
+        let mut reader = Reader::init("[Options]\ndata: users.csv\n");
+        assert_eq!(file_sections(&mut reader).unwrap().len(), 1);
+
+        let mut reader = Reader::init("[Options]\nrows: users.csv\n");
+        let error = file_sections(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 2, column: 1 });
+        assert_eq!(error.inner, ParseError::OptionName { name: "rows".to_string() });
+    }
+
+    #[test]
+    fn test_options_loop() {
+        let mut reader = Reader::init("[Options]\nloop: id in ids\n");
+        let sections = request_sections(&mut reader).unwrap();
+        match sections[0].value.clone() {
+            SectionValue::Options(items) => {
+                assert_eq!(items[0].loop_variables(), Some(("id".to_string(), "ids".to_string())));
+            }
+            _ => panic!("expecting an options section"),
+        }
+
+        let mut reader = Reader::init("[Options]\nloop: ids\n");
+        let error = request_sections(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 2, column: 7 });
+        assert_eq!(error.inner, ParseError::LoopOption);
+
+        let mut reader = Reader::init("[Options]\nloop: id in ids\n");
+        let error = file_sections(&mut reader).err().unwrap();
+        assert_eq!(error.inner, ParseError::OptionName { name: "loop".to_string() });
+    }
+
+    #[test]
+    fn test_options_skip() {
+        let condition = |s: &str| {
+            let mut reader = Reader::init(s);
+            match section(&mut reader).unwrap().value {
+                SectionValue::Options(items) => items[0].condition(),
+                _ => panic!("expecting an options section"),
+            }
+        };
+        assert_eq!(condition("[Options]\nskip: created\n"), Some(Condition { not: false, variable: "created".to_string(), comparison: None }));
+        assert_eq!(condition("[Options]\nskip: not created\n"), Some(Condition { not: true, variable: "created".to_string(), comparison: None }));
+        assert_eq!(condition("[Options]\nskip: env == prod\n"), Some(Condition { not: false, variable: "env".to_string(), comparison: Some((true, "prod".to_string())) }));
+        assert_eq!(condition("[Options]\nskip: env!=prod\n"), Some(Condition { not: false, variable: "env".to_string(), comparison: Some((false, "prod".to_string())) }));
+
+        let mut reader = Reader::init("[Options]\nskip: env = prod\n");
+        let error = request_sections(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 2, column: 7 });
+        assert_eq!(error.inner, ParseError::SkipOption);
+    }
+
+    #[test]
+    fn test_options_name_tags() {
+        let mut reader = Reader::init("[Options]\nname: login\ntags: smoke, auth\n");
+        match section(&mut reader).unwrap().value {
+            SectionValue::Options(items) => {
+                assert_eq!(items[0].text(), Some("login".to_string()));
+                assert_eq!(items[1].tags(), vec!["smoke".to_string(), "auth".to_string()]);
+            }
+            _ => panic!("expecting an options section"),
+        }
+
+        let mut reader = Reader::init("[Options]\ntags: api\n");
+        assert_eq!(file_sections(&mut reader).unwrap().len(), 1);
+
+        let mut reader = Reader::init("[Options]\nname: {{name}}\n");
+        let error = request_sections(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 2, column: 7 });
+        assert_eq!(error.inner, ParseError::OptionValue { name: "name".to_string() });
+    }
 }
@@ -347,7 +347,8 @@ mod tests {
                 TemplateElement::String { value: "hello ".to_string(), encoded: "hello\\u{20}".to_string() },
                 TemplateElement::Expression(Expr {
                     space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 14, 1, 14) },
-                    variable: Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 14, 1, 18) },
+                    value: ExprValue::Variable(Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 14, 1, 18) }),
+                    filters: vec![],
                     space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 18, 1, 18) },
                 }),
                 TemplateElement::String { value: "!".to_string(), encoded: "!".to_string() },
@@ -515,7 +516,8 @@ mod tests {
         assert_eq!(template_element_expression(&mut reader).unwrap(),
                    TemplateElement::Expression(Expr {
                        space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 3, 1, 3) },
-                       variable: Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 3, 1, 7) },
+                       value: ExprValue::Variable(Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 3, 1, 7) }),
+                       filters: vec![],
                        space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 7, 1, 7) },
                    })
         );
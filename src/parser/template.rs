@@ -94,6 +94,13 @@ pub fn templatize(encoded_string: EncodedString) -> ParseResult<'static, Vec<Tem
                     let mut reader = Reader::init(encoded.as_str());
                     reader.state = ReaderState { cursor: 0, pos: expression_start.unwrap().clone() };
                     let expression = expr::parse2(&mut reader)?;
+                    if !reader.is_eof() {
+                        return Err(error::Error {
+                            pos: reader.state.pos,
+                            recoverable: false,
+                            inner: error::ParseError::Expecting { value: "}}".to_string() },
+                        });
+                    }
                     elements.push(TemplateElement::Expression(expression));
                     value = "".to_string();
                     encoded = "".to_string();
@@ -137,7 +144,7 @@ pub fn templatize(encoded_string: EncodedString) -> ParseResult<'static, Vec<Tem
 
 #[cfg(test)]
 mod tests {
-    use crate::core::ast::{Variable, Whitespace};
+    use crate::core::ast::{ExprValue, Variable, Whitespace};
     use crate::core::ast::Expr;
 
     use super::*;
@@ -178,7 +185,8 @@ mod tests {
                     value: "".to_string(),
                     source_info: SourceInfo::init(1, 11, 1, 11),
                 },
-                variable: Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 11, 1, 15) },
+                value: ExprValue::Variable(Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 11, 1, 15) }),
+                filters: vec![],
                 space1: Whitespace {
                     value: "".to_string(),
                     source_info: SourceInfo::init(1, 15, 1, 15),
@@ -32,7 +32,9 @@ impl AssertResult {
             AssertResult::Status { actual, expected, .. } => actual != expected,
             AssertResult::Header { .. } => false,
             AssertResult::Explicit { .. } => true,
-            AssertResult::Body { .. } => true
+            AssertResult::Body { .. } => true,
+            AssertResult::JsonBody { .. } => true,
+            AssertResult::XmlBody { .. } => true,
         }
     }
 
@@ -47,6 +49,7 @@ impl AssertResult {
                         inner: RunnerError::AssertVersion { actual }
                         ,
                         assert: false,
+                        call_site: None,
                     })
                 }
             }
@@ -58,6 +61,7 @@ impl AssertResult {
                         source_info,
                         inner: RunnerError::AssertStatus { actual: actual.to_string() },
                         assert: false,
+                        call_site: None,
                     })
                 }
             }
@@ -72,6 +76,7 @@ impl AssertResult {
                                 source_info,
                                 inner: RunnerError::AssertHeaderValueError { actual: s },
                                 assert: false,
+                                call_site: None,
                             })
                         }
                     }
@@ -92,12 +97,34 @@ impl AssertResult {
                                     source_info,
                                     inner: RunnerError::AssertBodyValueError { actual, expected },
                                     assert: false,
+                                    call_site: None,
                                 })
                             }
                         }
                     }
                 }
             }
+            AssertResult::JsonBody { diffs: Err(e), .. } => Some(e),
+            AssertResult::JsonBody { diffs: Ok(diffs), source_info } => {
+                if diffs.is_empty() {
+                    None
+                } else {
+                    Some(Error {
+                        source_info,
+                        inner: RunnerError::AssertJsonBody { diff: diffs.iter().flat_map(|d| d.lines()).collect() },
+                        assert: true,
+                        call_site: None,
+                    })
+                }
+            }
+            AssertResult::XmlBody { diff: Err(e), .. } => Some(e),
+            AssertResult::XmlBody { diff: Ok(None), .. } => None,
+            AssertResult::XmlBody { diff: Ok(Some(diff)), source_info } => Some(Error {
+                source_info,
+                inner: RunnerError::AssertXmlBody { path: diff.path, expected: diff.expected, actual: diff.actual },
+                assert: true,
+                call_site: None,
+            }),
 
             AssertResult::Explicit { actual: Err(e), .. } => { Some(e) }
             AssertResult::Explicit { predicate_result: Some(Err(e)), .. } => { Some(e) }
@@ -107,12 +134,22 @@ impl AssertResult {
 }
 
 impl Assert {
-    pub fn eval(self, http_response: http::response::Response, variables: &HashMap<String, Value>) -> AssertResult {
-        let actual = self.query.eval(variables, http_response);
+    pub fn eval(self, http_response: http::response::Response, variables: &HashMap<String, Value>, context_dir: String, namespaces: &HashMap<String, String>) -> AssertResult {
+        let filters = self.filters;
+        let actual = self.query.eval(variables, http_response, namespaces).and_then(|value| {
+            let mut value = value;
+            for (_, filter) in filters {
+                value = match value {
+                    None => break,
+                    Some(value) => filter.eval(variables, value)?,
+                };
+            }
+            Ok(value)
+        });
         let source_info = self.predicate.clone().predicate_func.source_info;
         let predicate_result = match actual.clone() {
             Err(_) => None,
-            Ok(actual) => Some(self.predicate.eval(variables, actual))
+            Ok(actual) => Some(self.predicate.eval(variables, actual, context_dir))
         };
 
         AssertResult::Explicit { actual, source_info, predicate_result }
@@ -145,6 +182,7 @@ pub mod tests {
             line_terminators: vec![],
             space0: whitespace.clone(),
             query: query::tests::xpath_users(),
+            filters: vec![],
             space1: whitespace.clone(),
             predicate,
             line_terminator0: LineTerminator {
@@ -162,9 +200,33 @@ pub mod tests {
     fn test_eval() {
         let variables = HashMap::new();
         assert_eq!(
-            assert_count_user().eval(http::response::tests::xml_three_users_http_response(), &variables),
+            assert_count_user().eval(http::response::tests::xml_three_users_http_response(), &variables, "current_dir".to_string(), &HashMap::new()),
+            AssertResult::Explicit {
+                actual: Ok(Some(Value::Nodeset(vec![String::from("Bob"), String::from("Bill"), String::from("Bruce")]))),
+                source_info: SourceInfo::init(1, 14, 1, 27),
+                predicate_result: Some(Ok(())),
+            }
+        );
+    }
+
+    #[test]
+    fn test_eval_filters() {
+        // xpath //user count equals 3
+        let variables = HashMap::new();
+        let whitespace = Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(1, 1, 1, 1),
+        };
+        let mut assert = assert_count_user();
+        assert.filters = vec![(whitespace.clone(), Filter {
+            source_info: SourceInfo::init(1, 8, 1, 13),
+            value: FilterValue::Count {},
+        })];
+        assert.predicate.predicate_func.value = PredicateFuncValue::EqualInt { space0: whitespace, value: 3 };
+        assert_eq!(
+            assert.eval(http::response::tests::xml_three_users_http_response(), &variables, "current_dir".to_string(), &HashMap::new()),
             AssertResult::Explicit {
-                actual: Ok(Some(Value::Nodeset(3))),
+                actual: Ok(Some(Value::Integer(3))),
                 source_info: SourceInfo::init(1, 14, 1, 27),
                 predicate_result: Some(Ok(())),
             }
@@ -22,6 +22,7 @@ use std::io::prelude::*;
 use std::path::Path;
 
 use crate::core::common::Value;
+use crate::parser::parse_template;
 
 use super::core::{Error, RunnerError};
 use super::super::core::ast::*;
@@ -41,36 +42,65 @@ impl Bytes {
             }
             Bytes::Base64 { value, .. } => Ok(value),
             Bytes::Xml { value, .. } => Ok(value.into_bytes()),
+            Bytes::TemplateXml { value, .. } => {
+                let value = value.eval_xml(variables)?;
+                Ok(value.into_bytes())
+            }
             Bytes::Json { value, .. } => {
                 let value = value.eval(variables)?;
                 Ok(value.into_bytes())
             }
-            Bytes::File { filename, .. } => {
-                let path = Path::new(filename.value.as_str());
-                let absolute_filename = if path.is_absolute() {
-                    filename.clone().value
+            Bytes::File { filename, .. } => read_file(filename, context_dir),
+            Bytes::TemplateFile { filename, .. } => {
+                let bytes = read_file(filename.clone(), context_dir)?;
+                let invalid_template = Error {
+                    source_info: filename.source_info.clone(),
+                    inner: RunnerError::InvalidTemplateFile { value: filename.value.clone() },
+                    assert: false,
+                    call_site: None,
+                };
+                let content = match String::from_utf8(bytes) {
+                    Ok(s) => s,
+                    Err(_) => return Err(invalid_template),
+                };
+                let template = match parse_template(content.as_str()) {
+                    Ok(template) => template,
+                    Err(_) => return Err(invalid_template),
+                };
+                // values rendered in an xml template file are escaped
+                let value = if filename.value.ends_with(".xml") {
+                    template.eval_xml(variables)
                 } else {
-                    Path::new(context_dir.as_str()).join(filename.value).to_str().unwrap().to_string()
+                    template.eval(variables)
                 };
-                match File::open(absolute_filename.clone()) {
-                    Ok(f) => {
-                        let mut bytes = vec![];
-                        for byte in f.bytes() {
-                            bytes.push(byte.unwrap());
-                        }
-                        Ok(bytes)
-                    }
-                    Err(_) => Err(Error {
-                        source_info: filename.source_info,
-                        inner: RunnerError::FileReadAccess { value: absolute_filename },
-                        assert: false,
-                    })
+                // positions inside the template file are meaningless in the hurl file
+                match value {
+                    Ok(value) => Ok(value.into_bytes()),
+                    Err(e) => Err(Error { source_info: filename.source_info, ..e }),
                 }
             }
         }
     }
 }
 
+fn read_file(filename: Filename, context_dir: String) -> Result<Vec<u8>, Error> {
+    let path = Path::new(filename.value.as_str());
+    let absolute_filename = if path.is_absolute() {
+        filename.clone().value
+    } else {
+        Path::new(context_dir.as_str()).join(filename.value).to_str().unwrap().to_string()
+    };
+    let mut bytes = vec![];
+    match File::open(absolute_filename.clone()).and_then(|mut f| f.read_to_end(&mut bytes)) {
+        Ok(_) => Ok(bytes),
+        Err(_) => Err(Error {
+            source_info: filename.source_info,
+            inner: RunnerError::FileReadAccess { value: absolute_filename },
+            assert: false,
+            call_site: None,
+        })
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -133,4 +163,65 @@ mod tests {
         assert_eq!(error.inner, RunnerError::FileReadAccess { value: String::from("current_dir/data.bin") });
         assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 15));
     }
+
+    #[test]
+    pub fn test_body_xml_template() {
+        // template
+        // <a>{{id}}</a>
+        let whitespace = Whitespace {
+            value: String::from(""),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let bytes = Bytes::TemplateXml {
+            newline0: whitespace.clone(),
+            value: Template {
+                quotes: false,
+                elements: vec![
+                    TemplateElement::String { value: String::from("<a>"), encoded: String::from("<a>") },
+                    TemplateElement::Expression(Expr {
+                        space0: whitespace.clone(),
+                        value: ExprValue::Variable(Variable { name: String::from("id"), source_info: SourceInfo::init(1, 6, 1, 8) }),
+                        filters: vec![],
+                        space1: whitespace,
+                    }),
+                    TemplateElement::String { value: String::from("</a>"), encoded: String::from("</a>") },
+                ],
+                source_info: SourceInfo::init(1, 1, 1, 14),
+            }
+        };
+
+        let mut variables = HashMap::new();
+        variables.insert(String::from("id"), Value::Integer(12));
+        assert_eq!(bytes.clone().eval(&variables, "current_dir".to_string()).unwrap(), b"<a>12</a>");
+
+        let error = bytes.eval(&HashMap::new(), "current_dir".to_string()).err().unwrap();
+        assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("id") });
+        assert_eq!(error.source_info, SourceInfo::init(1, 6, 1, 8));
+    }
+
+    #[test]
+    pub fn test_body_template_file() {
+        let path = Path::new("/tmp/template.json");
+        let mut file = File::create(&path).unwrap();
+        file.write_all(b"{\"token\": \"{{token}}\"}").unwrap();
+
+        // template, template.json;
+        let whitespace = Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let bytes = Bytes::TemplateFile {
+            space0: whitespace.clone(),
+            filename: Filename { value: String::from("template.json"), source_info: SourceInfo::init(1, 11, 1, 24) },
+            space1: whitespace,
+        };
+
+        let mut variables = HashMap::new();
+        variables.insert(String::from("token"), Value::String(String::from("abc")));
+        assert_eq!(bytes.clone().eval(&variables, "/tmp".to_string()).unwrap(), b"{\"token\": \"abc\"}");
+
+        let error = bytes.eval(&HashMap::new(), "/tmp".to_string()).err().unwrap();
+        assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("token") });
+        assert_eq!(error.source_info, SourceInfo::init(1, 11, 1, 24));
+    }
 }
@@ -17,8 +17,6 @@
  */
 use std::collections::HashMap;
 
-use regex::Regex;
-
 use crate::core::common::Value;
 use crate::http;
 
@@ -27,68 +25,30 @@ use super::core::RunnerError;
 use super::super::core::ast::*;
 
 impl Capture {
-    pub fn eval(self, variables: &HashMap<String, Value>, http_response: http::response::Response) -> Result<CaptureResult, Error> {
+    pub fn eval(self, variables: &HashMap<String, Value>, http_response: http::response::Response, namespaces: &HashMap<String, String>) -> Result<CaptureResult, Error> {
         let name = self.name.value;
-        let value = self.query.clone().eval(variables, http_response)?;
-        let value = match value {
+        let value = self.query.clone().eval(variables, http_response, namespaces)?;
+        let mut value = match value {
             None => return Err(Error {
                 source_info: self.query.source_info,
                 inner: RunnerError::NoQueryResult {},
                 assert: false,
+                call_site: None,
             }),
-            Some(value) => match self.subquery {
-                None => value,
-                Some(subquery) => {
-                    let value = subquery.clone().eval(variables, value)?;
-                    match value {
-                        None => return Err(Error {
-                            source_info: subquery.source_info,
-                            inner: RunnerError::NoQueryResult {},
-                            assert: false,
-                        }),
-                        Some(value) => value
-                    }
-                }
-            }
+            Some(value) => value,
         };
-        Ok(CaptureResult { name, value })
-    }
-}
-
-
-impl Subquery {
-    pub fn eval(self, variables: &HashMap<String, Value>, value: Value) -> Result<Option<Value>, Error> {
-        match self.value {
-            SubqueryValue::Regex { expr, .. } => {
-                let source_info = expr.source_info.clone();
-                let expr = expr.eval(variables)?;
-                match value {
-                    Value::String(s) => {
-                        match Regex::new(expr.as_str()) {
-                            Ok(re) => {
-                                match re.captures(s.as_str()) {
-                                    Some(captures) => match captures.get(1) {
-                                        Some(v) => Ok(Some(Value::String(v.as_str().to_string()))),
-                                        None => Ok(None),
-                                    }
-                                    None => Ok(None),
-                                }
-                            }
-                            Err(_) => Err(Error {
-                                source_info,
-                                inner: RunnerError::InvalidRegex(),
-                                assert: false,
-                            })
-                        }
-                    }
-                    _ => Err(Error {
-                        source_info: self.source_info,
-                        inner: RunnerError::SubqueryInvalidInput,
-                        assert: false,
-                    })
-                }
-            }
+        for (_, filter) in self.filters {
+            value = match filter.clone().eval(variables, value)? {
+                None => return Err(Error {
+                    source_info: filter.source_info,
+                    inner: RunnerError::NoQueryResult {},
+                    assert: false,
+                    call_site: None,
+                }),
+                Some(value) => value,
+            };
         }
+        Ok(CaptureResult { name, value })
     }
 }
 
@@ -119,8 +79,7 @@ pub mod tests {
 
             // xpath count(//user)
             query: query::tests::xpath_count_user_query(),
-            space3: whitespace.clone(),
-            subquery: None,
+            filters: vec![],
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -147,8 +106,7 @@ pub mod tests {
 
             // xpath count(//user)
             query: query::tests::jsonpath_duration(),
-            space3: whitespace.clone(),
-            subquery: None,
+            filters: vec![],
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -174,8 +132,7 @@ pub mod tests {
             space2: whitespace.clone(),
 
             query: query::tests::xpath_invalid_query(),
-            space3: whitespace.clone(),
-            subquery: None,
+            filters: vec![],
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -183,7 +140,7 @@ pub mod tests {
             },
         };
 
-        let error = capture.eval(&variables, http::response::tests::xml_three_users_http_response()).err().unwrap();
+        let error = capture.eval(&variables, http::response::tests::xml_three_users_http_response(), &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info.start, Pos { line: 1, column: 7 });
         assert_eq!(error.inner, RunnerError::QueryInvalidXpathEval)
     }
@@ -222,8 +179,7 @@ pub mod tests {
                     },
                 },
             },
-            space3: whitespace.clone(),
-            subquery: None,
+            filters: vec![],
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -238,13 +194,13 @@ pub mod tests {
     #[test]
     fn test_capture() {
         let variables = HashMap::new();
-        assert_eq!(user_count_capture().eval(&variables, http::response::tests::xml_three_users_http_response()).unwrap(),
+        assert_eq!(user_count_capture().eval(&variables, http::response::tests::xml_three_users_http_response(), &HashMap::new()).unwrap(),
                    CaptureResult {
                        name: "UserCount".to_string(),
                        value: Value::from_f64(3.0),
                    });
 
-        assert_eq!(duration_capture().eval(&variables, http::response::tests::json_http_response()).unwrap(),
+        assert_eq!(duration_capture().eval(&variables, http::response::tests::json_http_response(), &HashMap::new()).unwrap(),
                    CaptureResult {
                        name: "duration".to_string(),
                        value: Value::from_f64(1.5),
@@ -252,50 +208,29 @@ pub mod tests {
     }
 
     #[test]
-    fn test_subquery_value_regex() {
-        // regex "Hello (.*)!"
+    fn test_capture_filters() {
         let variables = HashMap::new();
         let whitespace = Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) };
-        let subquery = Subquery {
-            source_info: SourceInfo::init(1, 1, 1, 20),
-            value: SubqueryValue::Regex {
-                space0: whitespace,
-                expr: Template {
-                    quotes: false,
-                    elements: vec![
-                        TemplateElement::String { value: "Hello (.*)!".to_string(), encoded: "Hello (.*)!".to_string() }
-                    ],
-                    source_info: SourceInfo::init(1, 7, 1, 20),
-                },
-            },
-        };
-        assert_eq!(subquery.clone().eval(&variables, Value::String("Hello Bob!".to_string())).unwrap().unwrap(),
-                   Value::String("Bob".to_string())
-        );
-        let error = subquery.eval(&variables, Value::Bool(true)).err().unwrap();
-        assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 20));
-        assert_eq!(error.inner, RunnerError::SubqueryInvalidInput);
-    }
+        let mut capture = duration_capture();
+
+        // jsonpath "$.duration" toInt
+        capture.filters = vec![(whitespace.clone(), Filter {
+            source_info: SourceInfo::init(1, 22, 1, 27),
+            value: FilterValue::ToInt {},
+        })];
+        assert_eq!(capture.clone().eval(&variables, http::response::tests::json_http_response(), &HashMap::new()).unwrap(),
+                   CaptureResult {
+                       name: "duration".to_string(),
+                       value: Value::Integer(1),
+                   });
 
-    #[test]
-    fn test_subquery_value_error() {
-        let variables = HashMap::new();
-        let whitespace = Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) };
-        let subquery = Subquery {
-            source_info: SourceInfo::init(1, 1, 1, 20),
-            value: SubqueryValue::Regex {
-                space0: whitespace,
-                expr: Template {
-                    quotes: false,
-                    elements: vec![
-                        TemplateElement::String { value: "???".to_string(), encoded: "???".to_string() }
-                    ],
-                    source_info: SourceInfo::init(1, 7, 1, 20),
-                },
-            },
-        };
-        let error = subquery.eval(&variables, Value::String("Hello Bob!".to_string())).err().unwrap();
-        assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 20));
-        assert_eq!(error.inner, RunnerError::InvalidRegex {});
+        // jsonpath "$.duration" toInt count
+        capture.filters.push((whitespace, Filter {
+            source_info: SourceInfo::init(1, 28, 1, 33),
+            value: FilterValue::Count {},
+        }));
+        let error = capture.eval(&variables, http::response::tests::json_http_response(), &HashMap::new()).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 28, 1, 33));
+        assert_eq!(error.inner, RunnerError::FilterInvalidInput { actual: "integer".to_string() });
     }
 }
@@ -23,11 +23,26 @@ use crate::core::common::{FormatError, SourceInfo, Value};
 use crate::http;
 use crate::http::cookie::Cookie;
 
+use super::json::JsonDiff;
+use super::xpath::XmlDiff;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RunnerOptions {
     pub fail_fast: bool,
-    pub variables: HashMap<String, String>,
+    pub variables: HashMap<String, Value>,
+    pub namespaces: HashMap<String, String>,
     pub to_entry: Option<usize>,
+    pub seed: Option<u64>,
+    pub entry_filter: Option<EntryFilter>,
+}
+
+// entries selected by name patterns and tags, optionally with the entries they depend on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntryFilter {
+    pub names: Vec<String>,
+    pub tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub with_dependencies: bool,
 }
 
 
@@ -40,6 +55,8 @@ pub struct HurlResult {
     pub time_in_ms: u128,
     pub success: bool,
     pub cookies: Vec<Cookie>,
+    // row of the data file the whole file has been run with, starting at 1
+    pub iteration: Option<usize>,
 }
 
 impl HurlResult {
@@ -61,6 +78,12 @@ pub struct EntryResult {
     pub asserts: Vec<AssertResult>,
     pub errors: Vec<Error>,
     pub time_in_ms: u128,
+    // row of the data file or element of the loop the entry has been run with, starting at 1
+    pub iteration: Option<usize>,
+    // the skip condition of the entry was met, the entry has not been run
+    pub skipped: bool,
+    // file the entry has been included from, None for the entries of the run file itself
+    pub filename: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -69,6 +92,8 @@ pub enum AssertResult {
     Status { actual: u64, expected: u64, source_info: SourceInfo },
     Header { actual: Result<String, Error>, expected: String, source_info: SourceInfo },
     Body { actual: Result<Value, Error>, expected: Result<Value, Error>, source_info: SourceInfo },
+    JsonBody { diffs: Result<Vec<JsonDiff>, Error>, source_info: SourceInfo },
+    XmlBody { diff: Result<Option<XmlDiff>, Error>, source_info: SourceInfo },
     Explicit { actual: Result<Option<Value>, Error>, source_info: SourceInfo, predicate_result: Option<PredicateResult> },
 }
 
@@ -90,6 +115,8 @@ pub struct Error {
     pub source_info: SourceInfo,
     pub inner: RunnerError,
     pub assert: bool,
+    // call of the template the error has occurred in
+    pub call_site: Option<Box<SourceInfo>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -99,6 +126,12 @@ pub enum RunnerError {
     InvalidURL(String),
     HttpConnection { url: String, message: String },
     FileReadAccess { value: String },
+    InvalidTemplateFile { value: String },
+    InvalidInclude { value: String },
+    IncludeCycle { value: String },
+    UndefinedTemplate { name: String },
+    InvalidDataFile { value: String, message: String },
+    InvalidLoop { name: String, value_type: String },
 
     // Capture
     //CaptureNonScalarUnsupported,
@@ -111,24 +144,33 @@ pub enum RunnerError {
     QueryCookieNotFound,
     QueryInvalidJsonpathExpression { value: String },
     QueryInvalidXpathEval,
+    QueryInvalidCssSelector { message: String },
     QueryInvalidXml,
     QueryInvalidJson,
     NoQueryResult,
 
-    SubqueryInvalidInput,
+    // Filter
+    FilterInvalidInput { actual: String },
+    FilterDecode { encoding: String },
+    OperationInvalidOperands { operator: String, left: String, right: String },
 
     // Predicate
     PredicateType,
     PredicateValue(Value),
     AssertFailure { actual: String, expected: String, type_mismatch: bool },
     InvalidRegex(),
+    InvalidJsonSchema,
+    InvalidXmlSchema { errors: Vec<String> },
 
     AssertHeaderValueError { actual: String },
     AssertBodyValueError { actual: String, expected: String },
+    AssertJsonBody { diff: Vec<String> },
+    AssertXmlBody { path: String, expected: String, actual: String },
     AssertVersion { actual: String },
     AssertStatus { actual: String },
 
     UnrenderableVariable { value: String },
+    UnexpectedMatcher { value: String },
 
 }
 
@@ -146,23 +188,37 @@ impl FormatError for Error {
             RunnerError::HttpConnection { .. } => "Http Connection".to_string(),
             RunnerError::PredicateValue { .. } => "Assert - Predicate Value Failed".to_string(),
             RunnerError::InvalidRegex {} => "Invalid regex".to_string(),
+            RunnerError::InvalidJsonSchema {} => "Invalid json schema".to_string(),
+            RunnerError::InvalidXmlSchema { .. } => "Invalid xml schema".to_string(),
             RunnerError::FileReadAccess { .. } => "File ReadAccess".to_string(),
+            RunnerError::InvalidTemplateFile { .. } => "Invalid template file".to_string(),
+            RunnerError::InvalidInclude { .. } => "Invalid include".to_string(),
+            RunnerError::IncludeCycle { .. } => "Include cycle".to_string(),
+            RunnerError::UndefinedTemplate { .. } => "Undefined template".to_string(),
+            RunnerError::InvalidDataFile { .. } => "Invalid data file".to_string(),
+            RunnerError::InvalidLoop { .. } => "Invalid loop".to_string(),
             RunnerError::QueryInvalidXml { .. } => "Invalid XML".to_string(),
             RunnerError::QueryInvalidXpathEval {} => "Invalid xpath expression".to_string(),
+            RunnerError::QueryInvalidCssSelector { .. } => "Invalid css selector".to_string(),
             RunnerError::QueryHeaderNotFound {} => "Header not Found".to_string(),
             RunnerError::QueryCookieNotFound {} => "Cookie not Found".to_string(),
             RunnerError::AssertHeaderValueError { .. } => "Assert Header Value".to_string(),
             RunnerError::AssertBodyValueError { .. } => "Assert Body Value".to_string(),
+            RunnerError::AssertJsonBody { .. } => "Assert Json Body".to_string(),
+            RunnerError::AssertXmlBody { .. } => "Assert Xml Body".to_string(),
             RunnerError::AssertVersion { .. } => "Assert Http Version".to_string(),
             RunnerError::AssertStatus { .. } => "Assert Status".to_string(),
             RunnerError::QueryInvalidJson { .. } => "Invalid Json".to_string(),
             RunnerError::InvalidUtf8 { .. } => "Invalid Utf8".to_string(),
             RunnerError::QueryInvalidJsonpathExpression { .. } => "Invalid jsonpath".to_string(),
             RunnerError::PredicateType { .. } => "Assert - Inconsistent predicate type".to_string(),
-            RunnerError::SubqueryInvalidInput { .. } => "Subquery error".to_string(),
+            RunnerError::FilterInvalidInput { .. } => "Filter error".to_string(),
+            RunnerError::FilterDecode { .. } => "Filter error".to_string(),
+            RunnerError::OperationInvalidOperands { .. } => "Invalid operation".to_string(),
             RunnerError::InvalidDecoding { .. } => "Invalid Decoding".to_string(),
             RunnerError::AssertFailure { .. } => "Assert Failure".to_string(),
             RunnerError::UnrenderableVariable { .. } => "Unrenderable Variable".to_string(),
+            RunnerError::UnexpectedMatcher { .. } => "Unexpected Matcher".to_string(),
             RunnerError::NoQueryResult { .. } => "No query result".to_string(),
         }
     }
@@ -176,22 +232,39 @@ impl FormatError for Error {
             RunnerError::AssertStatus { actual, .. } => format!("actual value is <{}>", actual),
             RunnerError::PredicateValue(value) => format!("actual value is <{}>", value.to_string()),
             RunnerError::InvalidRegex {} => "Regex expression is not valid".to_string(),
+            RunnerError::InvalidJsonSchema {} => "The schema file is not a valid json document".to_string(),
+            RunnerError::InvalidXmlSchema { errors } => {
+                let details: String = errors.iter().map(|e| format!("\n    {}", e)).collect();
+                format!("The schema file is not a valid XML Schema or DTD{}", details)
+            }
             RunnerError::FileReadAccess { value } => format!("File {} can not be read", value),
+            RunnerError::InvalidTemplateFile { value } => format!("File {} is not a valid utf8 template", value),
+            RunnerError::InvalidInclude { value } => format!("File {} is not a valid Hurl file", value),
+            RunnerError::IncludeCycle { value } => format!("File {} is already being included", value),
+            RunnerError::UndefinedTemplate { name } => format!("The template {} is not defined", name),
+            RunnerError::InvalidDataFile { value, message } => format!("File {} is not a valid data file: {}", value, message),
+            RunnerError::InvalidLoop { name, value_type } => format!("The variable {} is not a list but of type {}", name, value_type),
             RunnerError::QueryInvalidXml { .. } => "The Http response is not a valid XML".to_string(),
             RunnerError::QueryHeaderNotFound {} => "This header has not been found in the response".to_string(),
             RunnerError::QueryCookieNotFound {} => "This cookie has not been found in the response".to_string(),
             RunnerError::QueryInvalidXpathEval {} => "The xpath expression is not valid".to_string(),
+            RunnerError::QueryInvalidCssSelector { message } => format!("The css selector is not valid: {}", message),
             RunnerError::AssertHeaderValueError { actual } => format!("actual value is <{}>", actual),
             RunnerError::AssertBodyValueError { actual, .. } => format!("actual value is <{}>", actual),
+            RunnerError::AssertJsonBody { diff } => diff.join("\n"),
+            RunnerError::AssertXmlBody { path, expected, actual } => format!("first difference at {}\nexpected: {}\nactual:   {}", path, expected, actual),
             RunnerError::QueryInvalidJson { .. } => "The http response is not a valid json".to_string(),
             RunnerError::InvalidUtf8 { .. } => "The http response is not a valid utf8 string".to_string(),
             RunnerError::QueryInvalidJsonpathExpression { value } => format!("the jsonpath expression '{}' is not valid", value),
             RunnerError::PredicateType { .. } => "predicate type inconsistent with value return by query".to_string(),
-            RunnerError::SubqueryInvalidInput => "Type from query result and subquery do not match".to_string(),
+            RunnerError::FilterInvalidInput { actual } => format!("invalid filter input: {}", actual),
+            RunnerError::FilterDecode { encoding } => format!("value can not be decoded with {} encoding", encoding),
+            RunnerError::OperationInvalidOperands { operator, left, right } => format!("{} can not be applied to {} and {}", operator, left, right),
             RunnerError::InvalidDecoding { charset } => format!("The body can not be decoded with charset '{}'", charset),
             RunnerError::AssertFailure { actual, expected, .. } => format!("actual:   {}\nexpected: {}", actual, expected),
             RunnerError::VariableNotDefined { name } => format!("You must set the variable {}", name),
             RunnerError::UnrenderableVariable { value } => format!("value {} can not be rendered", value),
+            RunnerError::UnexpectedMatcher { value } => format!("{} can only be used in an expected json body", value),
             RunnerError::NoQueryResult { .. } => "The query didn't return any result".to_string(),
         }
     }
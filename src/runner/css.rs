@@ -0,0 +1,335 @@
+/*
+ * hurl (https://hurl.dev)
+ * Copyright (C) 2020 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//!
+//! CSS selectors
+//!
+//! A css selector is translated into an equivalent xpath expression
+//! and evaluated on the html document with libxml.
+//!
+//! Supported syntax:
+//! - type (`div`), universal (`*`), id (`#main`) and class (`.item`) selectors
+//! - attribute selectors: `[a]`, `[a=v]`, `[a~=v]`, `[a|=v]`, `[a^=v]`, `[a$=v]`, `[a*=v]`
+//! - pseudo-classes: `:first-child`, `:last-child`, `:nth-child(n)`
+//! - combinators: descendant (` `), child (`>`), adjacent sibling (`+`), general sibling (`~`)
+//! - selector groups (`a, b`)
+//! - a trailing `::attr(name)` pseudo-element to select an attribute instead of the element
+//!
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CssError {
+    pub pos: usize,
+    pub message: String,
+}
+
+///
+/// translate a css selector into an xpath expression
+///
+pub fn to_xpath(selector: &str) -> Result<String, CssError> {
+    let mut parser = Parser { chars: selector.chars().collect(), pos: 0 };
+    let mut paths = vec![];
+    loop {
+        paths.push(parser.selector()?);
+        parser.skip_spaces();
+        if parser.is_eof() {
+            break;
+        }
+        parser.expect(',')?;
+    }
+    Ok(paths.join(" | "))
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn is_eof(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn error(&self, message: &str) -> CssError {
+        CssError { pos: self.pos, message: message.to_string() }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), CssError> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!("expecting '{}'", c).as_str()))
+        }
+    }
+
+    fn try_literal(&mut self, s: &str) -> bool {
+        let end = self.pos + s.chars().count();
+        if end <= self.chars.len() && self.chars[self.pos..end].iter().collect::<String>() == s {
+            self.pos = end;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) -> bool {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    // complex selector: compound selectors separated by combinators
+    fn selector(&mut self) -> Result<String, CssError> {
+        self.skip_spaces();
+        let mut path = format!("//{}", self.compound()?);
+        loop {
+            let has_spaces = self.skip_spaces();
+            let axis = match self.peek() {
+                Some('>') => "/",
+                Some('+') => "/following-sibling::*[1]/self::",
+                Some('~') => "/following-sibling::",
+                Some(',') | None => break,
+                Some(':') if self.chars.get(self.pos + 1) == Some(&':') => {
+                    path.push_str(self.pseudo_element()?.as_str());
+                    self.skip_spaces();
+                    break;
+                }
+                _ if has_spaces => "//",
+                _ => return Err(self.error("unexpected character")),
+            };
+            if axis != "//" {
+                self.pos += 1;
+                self.skip_spaces();
+            }
+            path.push_str(axis);
+            path.push_str(self.compound()?.as_str());
+        }
+        Ok(path)
+    }
+
+    // compound selector: an optional type selector followed by id, class, attribute and pseudo-class selectors
+    fn compound(&mut self) -> Result<String, CssError> {
+        let start = self.pos;
+        let element = if self.try_literal("*") {
+            "*".to_string()
+        } else {
+            match self.identifier() {
+                Some(name) => name.to_lowercase(),
+                None => "*".to_string(),
+            }
+        };
+        let mut conditions = vec![];
+        loop {
+            match self.peek() {
+                Some('#') => {
+                    self.pos += 1;
+                    let id = self.identifier().ok_or_else(|| self.error("expecting an id"))?;
+                    conditions.push(format!("@id={}", string_literal(id.as_str())));
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    let class = self.identifier().ok_or_else(|| self.error("expecting a class name"))?;
+                    conditions.push(contains_word("@class", class.as_str()));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    conditions.push(self.attribute()?);
+                }
+                Some(':') if self.chars.get(self.pos + 1) != Some(&':') => {
+                    self.pos += 1;
+                    conditions.push(self.pseudo_class()?);
+                }
+                _ => break,
+            }
+        }
+        if self.pos == start {
+            return Err(self.error("expecting a selector"));
+        }
+        Ok(conditions.iter().fold(element, |acc, condition| format!("{}[{}]", acc, condition)))
+    }
+
+    fn attribute(&mut self) -> Result<String, CssError> {
+        self.skip_spaces();
+        let name = self.identifier().ok_or_else(|| self.error("expecting an attribute name"))?;
+        let attribute = format!("@{}", name);
+        self.skip_spaces();
+        if self.try_literal("]") {
+            return Ok(attribute);
+        }
+        let op = ["=", "~=", "|=", "^=", "$=", "*="]
+            .iter()
+            .find(|op| self.try_literal(op))
+            .ok_or_else(|| self.error("expecting an attribute operator"))?
+            .to_string();
+        self.skip_spaces();
+        let value = match self.peek() {
+            Some(c) if c == '"' || c == '\'' => self.quoted_string()?,
+            _ => self.identifier().ok_or_else(|| self.error("expecting an attribute value"))?,
+        };
+        self.skip_spaces();
+        self.expect(']')?;
+        let literal = string_literal(value.as_str());
+        Ok(match op.as_str() {
+            "=" => format!("{}={}", attribute, literal),
+            "~=" => contains_word(attribute.as_str(), value.as_str()),
+            "|=" => format!("({}={} or starts-with({}, {}))", attribute, literal, attribute, string_literal(format!("{}-", value).as_str())),
+            "^=" => format!("starts-with({}, {})", attribute, literal),
+            "$=" => format!("substring({}, string-length({}) - {} + 1)={}", attribute, attribute, value.chars().count(), literal),
+            _ => format!("contains({}, {})", attribute, literal),
+        })
+    }
+
+    fn pseudo_class(&mut self) -> Result<String, CssError> {
+        if self.try_literal("first-child") {
+            Ok("not(preceding-sibling::*)".to_string())
+        } else if self.try_literal("last-child") {
+            Ok("not(following-sibling::*)".to_string())
+        } else if self.try_literal("nth-child(") {
+            self.skip_spaces();
+            let start = self.pos;
+            while let Some('0'..='9') = self.peek() {
+                self.pos += 1;
+            }
+            let n: usize = self.chars[start..self.pos].iter().collect::<String>().parse().map_err(|_| self.error("expecting a positive integer"))?;
+            if n == 0 {
+                return Err(self.error("expecting a positive integer"));
+            }
+            self.skip_spaces();
+            self.expect(')')?;
+            Ok(format!("count(preceding-sibling::*)={}", n - 1))
+        } else {
+            Err(self.error("unsupported pseudo-class"))
+        }
+    }
+
+    fn pseudo_element(&mut self) -> Result<String, CssError> {
+        if !self.try_literal("::attr(") {
+            return Err(self.error("unsupported pseudo-element"));
+        }
+        self.skip_spaces();
+        let name = self.identifier().ok_or_else(|| self.error("expecting an attribute name"))?;
+        self.skip_spaces();
+        self.expect(')')?;
+        Ok(format!("/@{}", name))
+    }
+
+    fn identifier(&mut self) -> Option<String> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if !(c.is_alphanumeric() || c == '-' || c == '_') {
+                break;
+            }
+            self.pos += 1;
+        }
+        if self.pos == start {
+            None
+        } else {
+            Some(self.chars[start..self.pos].iter().collect())
+        }
+    }
+
+    fn quoted_string(&mut self) -> Result<String, CssError> {
+        let delimiter = self.peek().unwrap();
+        self.pos += 1;
+        let mut value = String::from("");
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some('\\') if self.chars.get(self.pos + 1).is_some() => {
+                    value.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) if c == delimiter => {
+                    self.pos += 1;
+                    return Ok(value);
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+}
+
+// xpath condition for a whitespace-separated list containing the given word
+fn contains_word(attribute: &str, word: &str) -> String {
+    format!("contains(concat(' ', normalize-space({}), ' '), {})", attribute, string_literal(format!(" {} ", word).as_str()))
+}
+
+// xpath string literal (xpath 1.0 has no escape sequence)
+fn string_literal(value: &str) -> String {
+    if !value.contains('\'') {
+        format!("'{}'", value)
+    } else if !value.contains('"') {
+        format!("\"{}\"", value)
+    } else {
+        let parts = value.split('\'').map(|s| format!("'{}'", s)).collect::<Vec<String>>();
+        format!("concat({})", parts.join(", \"'\", "))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_selectors() {
+        assert_eq!(to_xpath("div").unwrap(), "//div");
+        assert_eq!(to_xpath("*").unwrap(), "//*");
+        assert_eq!(to_xpath("#main").unwrap(), "//*[@id='main']");
+        assert_eq!(to_xpath("LI.item").unwrap(), "//li[contains(concat(' ', normalize-space(@class), ' '), ' item ')]");
+        assert_eq!(to_xpath("a[href]").unwrap(), "//a[@href]");
+        assert_eq!(to_xpath("a[href^=\"https\"]").unwrap(), "//a[starts-with(@href, 'https')]");
+        assert_eq!(to_xpath("a[href$='.pdf']").unwrap(), "//a[substring(@href, string-length(@href) - 4 + 1)='.pdf']");
+        assert_eq!(to_xpath("li:nth-child(2)").unwrap(), "//li[count(preceding-sibling::*)=1]");
+    }
+
+    #[test]
+    fn test_combinators() {
+        assert_eq!(to_xpath("div.item > a").unwrap(), "//div[contains(concat(' ', normalize-space(@class), ' '), ' item ')]/a");
+        assert_eq!(to_xpath("ul li").unwrap(), "//ul//li");
+        assert_eq!(to_xpath("h1 + p").unwrap(), "//h1/following-sibling::*[1]/self::p");
+        assert_eq!(to_xpath("h1~p").unwrap(), "//h1/following-sibling::p");
+        assert_eq!(to_xpath("h1, h2").unwrap(), "//h1 | //h2");
+        assert_eq!(to_xpath("a::attr(href)").unwrap(), "//a/@href");
+    }
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!(string_literal("it's"), "\"it's\"");
+        assert_eq!(string_literal("it's \"ok\""), "concat('it', \"'\", 's \"ok\"')");
+    }
+
+    #[test]
+    fn test_error() {
+        assert_eq!(to_xpath("").err().unwrap(), CssError { pos: 0, message: "expecting a selector".to_string() });
+        assert_eq!(to_xpath("a[href").err().unwrap(), CssError { pos: 6, message: "expecting an attribute operator".to_string() });
+        assert_eq!(to_xpath("a:hover").err().unwrap(), CssError { pos: 2, message: "unsupported pseudo-class".to_string() });
+        assert_eq!(to_xpath("a::attr(href) b").err().unwrap(), CssError { pos: 14, message: "expecting ','".to_string() });
+    }
+}
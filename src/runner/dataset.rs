@@ -0,0 +1,158 @@
+/*
+ * hurl (https://hurl.dev)
+ * Copyright (C) 2020 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//!
+//! Data files running an entry or a file once per row
+//!
+//! A csv file has a header line with the column names, its columns are strings.
+//! A json file is an array of objects, its values keep their json type.
+//!
+
+use std::fs;
+use std::path::Path;
+
+use crate::core::common::Value;
+
+use super::core::RunnerError;
+
+pub type Row = Vec<(String, Value)>;
+
+pub fn read(path: &Path) -> Result<Vec<Row>, RunnerError> {
+    let filename = path.display().to_string();
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return Err(RunnerError::FileReadAccess { value: filename }),
+    };
+    let rows = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+        parse_json(contents.as_str())
+    } else {
+        parse_csv(contents.as_str())
+    };
+    match rows {
+        Ok(rows) if rows.is_empty() => Err(RunnerError::InvalidDataFile { value: filename, message: "no row to run".to_string() }),
+        Ok(rows) => Ok(rows),
+        Err(message) => Err(RunnerError::InvalidDataFile { value: filename, message }),
+    }
+}
+
+pub fn parse_json(s: &str) -> Result<Vec<Row>, String> {
+    let value = match serde_json::from_str::<serde_json::Value>(s) {
+        Ok(value) => value,
+        Err(e) => return Err(e.to_string()),
+    };
+    let elements = match value {
+        serde_json::Value::Array(elements) => elements,
+        _ => return Err("expecting an array of objects".to_string()),
+    };
+    let mut rows = vec![];
+    for element in elements {
+        match element {
+            serde_json::Value::Object(map) => rows.push(map.iter().map(|(k, v)| (k.clone(), Value::from_json(v))).collect()),
+            _ => return Err("expecting an array of objects".to_string()),
+        }
+    }
+    Ok(rows)
+}
+
+pub fn parse_csv(s: &str) -> Result<Vec<Row>, String> {
+    let mut records = csv_records(s)?.into_iter();
+    let names = match records.next() {
+        Some(names) => names,
+        None => return Ok(vec![]),
+    };
+    let mut rows = vec![];
+    for (i, record) in records.enumerate() {
+        if record.len() != names.len() {
+            return Err(format!("line {} has {} columns instead of {}", i + 2, record.len(), names.len()));
+        }
+        rows.push(names.iter().cloned().zip(record.into_iter().map(Value::String)).collect());
+    }
+    Ok(rows)
+}
+
+// comma separated fields, a quoted field may contain commas, newlines and doubled quotes
+fn csv_records(s: &str) -> Result<Vec<Vec<String>>, String> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => record.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // blank lines are ignored
+    Ok(records.into_iter().filter(|r| !(r.len() == 1 && r[0].is_empty())).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(columns: Vec<(&str, &str)>) -> Row {
+        columns.iter().map(|(k, v)| (k.to_string(), Value::String(v.to_string()))).collect()
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        assert_eq!(parse_csv("name,age\nbob,12\r\n\n\"Smith, \"\"Al\"\"\",\"1\n2\"\n").unwrap(), vec![
+            row(vec![("name", "bob"), ("age", "12")]),
+            row(vec![("name", "Smith, \"Al\""), ("age", "1\n2")]),
+        ]);
+        assert!(parse_csv("name,age").unwrap().is_empty());
+        assert!(parse_csv("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_csv_error() {
+        assert_eq!(parse_csv("name,age\nbob\n").err().unwrap(), "line 2 has 1 columns instead of 2");
+        assert_eq!(parse_csv("name\n\"bob\n").err().unwrap(), "unterminated quoted field");
+    }
+
+    #[test]
+    fn test_parse_json() {
+        assert_eq!(parse_json(r#"[{"name": "bob", "age": 12, "id": "12"}]"#).unwrap(), vec![vec![
+            ("age".to_string(), Value::Integer(12)),
+            ("id".to_string(), Value::String("12".to_string())),
+            ("name".to_string(), Value::String("bob".to_string())),
+        ]]);
+        assert_eq!(parse_json(r#"{"name": "bob"}"#).err().unwrap(), "expecting an array of objects");
+    }
+}
@@ -48,12 +48,15 @@ use crate::format::logger::Logger;
 ///        redirect: http::client::Redirect::None,
 ///        http_proxy: None,
 ///        https_proxy: None,
-///        all_proxy: None
+///        all_proxy: None,
+///        timeout: None
 ///    });
 /// ```
+#[allow(clippy::too_many_arguments)]
 pub fn run(entry: Entry, http_client: &http::client::Client,
            entry_index: usize,
            variables: &mut HashMap<String, Value>,
+           namespaces: &HashMap<String, String>,
            cookiejar: &mut CookieJar,
            context_dir: String,
            logger: &Logger,
@@ -72,6 +75,9 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
                 errors: vec![error],
 
                 time_in_ms: 0,
+                iteration: None,
+                skipped: false,
+                filename: None,
             };
         }
     };
@@ -110,8 +116,12 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
                             url: e.url,
                         },
                         assert: false,
+                        call_site: None,
                     }],
                 time_in_ms: 0,
+                iteration: None,
+                skipped: false,
+                filename: None,
             };
         }
     };
@@ -123,10 +133,35 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
 
     //entry_log_builder.response(http_response.clone(), verbose);
 
+    // namespaces of the response only apply to this entry
+    let mut namespaces = namespaces.clone();
+    if let Some(response) = entry.response.clone() {
+        for namespace in response.namespaces() {
+            match namespace.value.eval(variables) {
+                Ok(uri) => {
+                    namespaces.insert(namespace.key.value, uri);
+                }
+                Err(e) => {
+                    return EntryResult {
+                        request: Some(http_request),
+                        response: Some(http_response),
+                        captures: vec![],
+                        asserts: vec![],
+                        errors: vec![e],
+                        time_in_ms,
+                        iteration: None,
+                        skipped: false,
+                        filename: None,
+                    };
+                }
+            }
+        }
+    }
+
     //hurl_log.entries.push(log_builder.build());
     let captures = match entry.response.clone() {
         None => vec![],
-        Some(response) => match response.eval_captures(http_response.clone(), variables) {
+        Some(response) => match response.eval_captures(http_response.clone(), variables, &namespaces) {
             Ok(captures) => captures,
             Err(e) => {
                 return EntryResult {
@@ -136,6 +171,9 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
                     asserts: vec![],
                     errors: vec![e],
                     time_in_ms,
+                    iteration: None,
+                    skipped: false,
+                    filename: None,
                 };
             }
         }
@@ -149,13 +187,13 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
 
     let asserts = match entry.response {
         None => vec![],
-        Some(response) => response.eval_asserts(variables, http_response.clone(), context_dir)
+        Some(response) => response.eval_asserts(variables, http_response.clone(), context_dir, &namespaces)
     };
 
     let errors = asserts
         .iter()
         .filter_map(|assert| assert.clone().error())
-        .map(|Error { source_info, inner, .. }| Error { source_info, inner, assert: true })
+        .map(|Error { source_info, inner, call_site, .. }| Error { source_info, inner, assert: true, call_site })
         .collect();
 
 
@@ -204,6 +242,9 @@ pub fn run(entry: Entry, http_client: &http::client::Client,
         asserts,
         errors,
         time_in_ms,
+        iteration: None,
+        skipped: false,
+        filename: None,
     }
 }
 
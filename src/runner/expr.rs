@@ -15,23 +15,305 @@
  * limitations under the License.
  *
  */
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::core::ast::Expr;
-use crate::core::common::Value;
+use chrono::{DateTime, Utc};
+use percent_encoding::utf8_percent_encode;
+
+use crate::core::ast::*;
+use crate::core::common::{SourceInfo, Value};
 
 use super::core::{RunnerError, Error};
+use super::query::URL_COMPONENT;
 
 impl Expr {
     pub fn eval(self, variables: &HashMap<String, Value>) -> Result<Value, Error> {
-        if let Some(value) = variables.get(self.variable.name.as_str()) {
-            Ok(value.clone())
-        } else {
-            Err(Error {
-                source_info: self.variable.source_info,
-                inner: RunnerError::TemplateVariableNotDefined { name: self.variable.name },
-                assert: false
-            })
+        let mut value = self.value.eval(variables);
+        for filter in self.filters {
+            value = filter.eval(value);
+        }
+        value
+    }
+}
+
+impl ExprValue {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<Value, Error> {
+        match self {
+            ExprValue::Variable(Variable { name, source_info }) => {
+                if let Some(value) = variables.get(name.as_str()) {
+                    Ok(value.clone())
+                } else {
+                    Err(Error {
+                        source_info,
+                        inner: RunnerError::TemplateVariableNotDefined { name },
+                        assert: false,
+                        call_site: None,
+                    })
+                }
+            }
+            ExprValue::Literal(value) => Ok(value.eval()),
+            ExprValue::Function(function) => Ok(function.value.eval()),
+            ExprValue::Operation(operation) => {
+                let Operation { left, operator, right, source_info, .. } = *operation;
+                let left = left.eval(variables)?;
+                let right = right.eval(variables)?;
+                eval_operation(operator, left, right, source_info)
+            }
+        }
+    }
+}
+
+impl ExprLiteral {
+    pub fn eval(self) -> Value {
+        match self {
+            ExprLiteral::Integer(value) => Value::Integer(value),
+            ExprLiteral::String(ExprString { value, .. }) => Value::String(value),
+        }
+    }
+}
+
+impl FunctionValue {
+    pub fn eval(self) -> Value {
+        match self {
+            FunctionValue::NewUuid {} => Value::String(new_uuid()),
+            FunctionValue::Now {} => Value::String(Utc::now().to_rfc3339()),
+            FunctionValue::RandomInt { min, max, .. } => {
+                let range = (max as i128 - min as i128 + 1) as u128;
+                let value = min as i128 + (next_random() as u128 % range) as i128;
+                Value::Integer(value as i64)
+            }
+        }
+    }
+}
+
+fn eval_operation(operator: Operator, left: Value, right: Value, source_info: SourceInfo) -> Result<Value, Error> {
+    let value = match (&left, &right) {
+        (Value::Integer(v1), Value::Integer(v2)) => match operator {
+            Operator::Add => v1.checked_add(*v2).map(Value::Integer),
+            Operator::Subtract => v1.checked_sub(*v2).map(Value::Integer),
+        },
+        _ => match (number(&left), number(&right)) {
+            (Some(v1), Some(v2)) => match operator {
+                Operator::Add => Some(Value::from_f64(v1 + v2)),
+                Operator::Subtract => Some(Value::from_f64(v1 - v2)),
+            },
+            _ => None,
+        },
+    };
+    match value {
+        Some(value) => Ok(value),
+        None => Err(Error {
+            source_info,
+            inner: RunnerError::OperationInvalidOperands {
+                operator: operator.symbol().to_string(),
+                left: left.to_string(),
+                right: right.to_string(),
+            },
+            assert: false,
+            call_site: None,
+        }),
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(v) => Some(*v as f64),
+        Value::Float(_, _) => value.to_json().as_f64(),
+        _ => None,
+    }
+}
+
+impl ExprFilter {
+    ///
+    /// apply the filter to the value of the expression
+    /// only the default filter accepts an undefined variable
+    ///
+    pub fn eval(self, value: Result<Value, Error>) -> Result<Value, Error> {
+        let source_info = self.source_info.clone();
+        let invalid_input = |value: &Value| Error {
+            source_info: source_info.clone(),
+            inner: RunnerError::FilterInvalidInput { actual: value._type() },
+            assert: false,
+            call_site: None,
+        };
+        match self.value {
+            ExprFilterValue::Default { value: default, .. } => match value {
+                Err(Error { inner: RunnerError::TemplateVariableNotDefined { .. }, .. }) => Ok(default.eval()),
+                value => value,
+            },
+            ExprFilterValue::Format { format, .. } => match value? {
+                Value::String(s) => match DateTime::parse_from_rfc3339(s.as_str()) {
+                    Ok(date) => Ok(Value::String(date.format(format.value.as_str()).to_string())),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterInvalidInput { actual: s },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            ExprFilterValue::Base64Encode {} => match value? {
+                Value::String(s) => Ok(Value::String(base64::encode(s.as_bytes()))),
+                Value::Bytes(bytes) => Ok(Value::String(base64::encode(&bytes))),
+                v => Err(invalid_input(&v)),
+            },
+            ExprFilterValue::Base64Decode {} => match value? {
+                Value::String(s) => match base64::decode(s.as_str()) {
+                    Ok(bytes) => match String::from_utf8(bytes) {
+                        Ok(s) => Ok(Value::String(s)),
+                        Err(e) => Ok(Value::Bytes(e.into_bytes())),
+                    },
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterDecode { encoding: "base64".to_string() },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            ExprFilterValue::UrlEncode {} => match value? {
+                Value::String(s) => Ok(Value::String(utf8_percent_encode(s.as_str(), URL_COMPONENT).to_string())),
+                v => Err(invalid_input(&v)),
+            },
+            ExprFilterValue::Upper {} => match value? {
+                Value::String(s) => Ok(Value::String(s.to_uppercase())),
+                v => Err(invalid_input(&v)),
+            },
+            ExprFilterValue::Lower {} => match value? {
+                Value::String(s) => Ok(Value::String(s.to_lowercase())),
+                v => Err(invalid_input(&v)),
+            },
+        }
+    }
+}
+
+// random values come from a splitmix64 generator
+// it can be seeded for reproducible runs
+thread_local! {
+    static RANDOM_STATE: Cell<u64> = Cell::new(
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0)
+    );
+}
+
+pub fn seed(value: u64) {
+    RANDOM_STATE.with(|state| state.set(value));
+}
+
+fn next_random() -> u64 {
+    RANDOM_STATE.with(|state| {
+        let value = state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+        state.set(value);
+        let mut z = value;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    })
+}
+
+// version 4 uuid
+fn new_uuid() -> String {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&next_random().to_be_bytes());
+    bytes.extend_from_slice(&next_random().to_be_bytes());
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32])
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whitespace() -> Whitespace {
+        Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) }
+    }
+
+    fn variable(name: &str) -> ExprValue {
+        ExprValue::Variable(Variable { name: name.to_string(), source_info: SourceInfo::init(1, 3, 1, 7) })
+    }
+
+    fn filter(value: ExprFilterValue) -> ExprFilter {
+        ExprFilter { space0: whitespace(), space1: whitespace(), value, source_info: SourceInfo::init(1, 10, 1, 16) }
+    }
+
+    fn expr(value: ExprValue, filters: Vec<ExprFilter>) -> Expr {
+        Expr { space0: whitespace(), value, filters, space1: whitespace() }
+    }
+
+    #[test]
+    fn test_operation() {
+        let mut variables = HashMap::new();
+        variables.insert(String::from("counter"), Value::Integer(1));
+        variables.insert(String::from("name"), Value::String(String::from("Bob")));
+        let operation = |name: &str, operator: Operator| ExprValue::Operation(Box::new(Operation {
+            left: variable(name),
+            space0: whitespace(),
+            operator,
+            space1: whitespace(),
+            right: ExprValue::Literal(ExprLiteral::Integer(1)),
+            source_info: SourceInfo::init(1, 3, 1, 14),
+        }));
+        assert_eq!(expr(operation("counter", Operator::Add), vec![]).eval(&variables).unwrap(), Value::Integer(2));
+        assert_eq!(expr(operation("counter", Operator::Subtract), vec![]).eval(&variables).unwrap(), Value::Integer(0));
+
+        let error = expr(operation("name", Operator::Add), vec![]).eval(&variables).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 3, 1, 14));
+        assert_eq!(error.inner, RunnerError::OperationInvalidOperands {
+            operator: String::from("+"),
+            left: String::from("Bob"),
+            right: String::from("1"),
+        });
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut variables = HashMap::new();
+        variables.insert(String::from("token"), Value::String(String::from("Hello")));
+        variables.insert(String::from("date"), Value::String(String::from("2020-10-01T12:30:00+00:00")));
+        let string = |value: &str| ExprString { quote: String::from("\""), value: value.to_string() };
+
+        assert_eq!(expr(variable("token"), vec![filter(ExprFilterValue::Base64Encode {})]).eval(&variables).unwrap(), Value::String(String::from("SGVsbG8=")));
+        assert_eq!(expr(variable("token"), vec![
+            filter(ExprFilterValue::Base64Encode {}),
+            filter(ExprFilterValue::Base64Decode {}),
+            filter(ExprFilterValue::Upper {}),
+        ]).eval(&variables).unwrap(), Value::String(String::from("HELLO")));
+        assert_eq!(expr(variable("date"), vec![filter(ExprFilterValue::Format { space0: whitespace(), format: string("%Y/%m") })]).eval(&variables).unwrap(), Value::String(String::from("2020/10")));
+        let default = filter(ExprFilterValue::Default { space0: whitespace(), value: ExprLiteral::String(string("x")) });
+        assert_eq!(expr(variable("name"), vec![default.clone()]).eval(&variables).unwrap(), Value::String(String::from("x")));
+        assert_eq!(expr(variable("token"), vec![default]).eval(&variables).unwrap(), Value::String(String::from("Hello")));
+
+        let error = expr(variable("name"), vec![filter(ExprFilterValue::Upper {})]).eval(&variables).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 3, 1, 7));
+        assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("name") });
+
+        let error = expr(variable("token"), vec![filter(ExprFilterValue::Format { space0: whitespace(), format: string("%Y") })]).eval(&variables).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 10, 1, 16));
+        assert_eq!(error.inner, RunnerError::FilterInvalidInput { actual: String::from("Hello") });
+    }
+
+    #[test]
+    fn test_seeded_functions() {
+        let random = || FunctionValue::RandomInt { space0: whitespace(), space1: whitespace(), min: 1, space2: whitespace(), max: 100 }.eval();
+        seed(42);
+        let values = vec![random(), random(), FunctionValue::NewUuid {}.eval()];
+        seed(42);
+        assert_eq!(values, vec![random(), random(), FunctionValue::NewUuid {}.eval()]);
+        for value in values {
+            match value {
+                Value::Integer(v) => assert!((1..=100).contains(&v)),
+                Value::String(uuid) => {
+                    assert_eq!(uuid.len(), 36);
+                    assert_eq!(&uuid[14..15], "4");
+                }
+                _ => panic!("unexpected value"),
+            }
         }
     }
 }
@@ -16,15 +16,21 @@
  *
  */
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use crate::core::ast::*;
-use crate::core::common::Value;
+use crate::core::common::{SourceInfo, Value};
 use crate::http;
+use crate::parser;
 
 use super::core::*;
 use super::super::format;
+use super::dataset;
+use super::selection;
 use super::entry;
+use super::expr;
 use crate::core::common::FormatError;
 
 
@@ -54,15 +60,20 @@ use crate::core::common::FormatError;
 ///        redirect: http::client::Redirect::None,
 ///        http_proxy: None,
 ///        https_proxy: None,
-///        all_proxy: None
+///        all_proxy: None,
+///        timeout: None
 ///    });
 ///
 /// // Define runner options
 /// let variables = std::collections::HashMap::new();
+/// let namespaces = std::collections::HashMap::new();
 /// let options = runner::core::RunnerOptions {
 ///        fail_fast: false,
 ///        variables,
+///        namespaces,
 ///        to_entry: None,
+///        seed: None,
+///        entry_filter: None,
 ///  };
 ///
 /// // create a logger
@@ -98,11 +109,10 @@ pub fn run(
     options: RunnerOptions,
     logger: format::logger::Logger,
 ) -> HurlResult {
-    let mut entries = vec![];
-    let mut variables = HashMap::default();
+    let variables = options.variables;
 
-    for (key, value) in options.variables {
-        variables.insert(key.to_string(), Value::String(value.to_string()));
+    if let Some(seed) = options.seed {
+        expr::seed(seed);
     }
 
     let n = if let Some(to_entry) = options.to_entry {
@@ -110,28 +120,34 @@ pub fn run(
     } else {
         hurl_file.entries.len()
     };
+    let selected: Vec<bool> = match options.entry_filter {
+        None => vec![true; hurl_file.entries.len()],
+        Some(filter) => selection::select(&hurl_file, &filter),
+    };
+    let selected: Vec<bool> = selected.iter().enumerate().map(|(i, selected)| *selected && i < n).collect();
 
-    let start = Instant::now();
-    for (entry_index, entry) in hurl_file.entries.iter().take(n).cloned().enumerate().collect::<Vec<(usize, Entry)>>() {
-        let entry_result = entry::run(entry, &http_client, entry_index, &mut variables, cookiejar, context_dir.clone(), &logger);
-        entries.push(entry_result.clone());
-        for e in entry_result.errors.clone() {
-            let error = format::error::Error {
-                source_info: e.clone().source_info,
-                description: e.clone().description(),
-                fixme: e.fixme(),
-                lines: vec![],
-                filename: "".to_string(),
-                warning: false,
-                color: false,
-            };
-            logger.clone().error(&error);
-        }
+    // included files are relative to the including file
+    let (dir, included_files) = if filename == "-" {
+        (PathBuf::new(), vec![])
+    } else {
+        let path = Path::new(filename.as_str());
+        let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        (dir, fs::canonicalize(path).into_iter().collect())
+    };
 
-        if options.fail_fast && !entry_result.errors.is_empty() {
-            break;
-        }
-    }
+    let start = Instant::now();
+    let mut file_run = FileRun {
+        http_client: &http_client,
+        cookiejar,
+        context_dir,
+        fail_fast: options.fail_fast,
+        variables,
+        namespaces: options.namespaces,
+        entries: vec![],
+        call_site: None,
+    };
+    file_run.run(&hurl_file, &selected, dir.as_path(), &included_files, &logger);
+    let entries = file_run.entries;
     let time_in_ms = start.elapsed().as_millis();
     let success = entries.iter().flat_map(|e| e.errors.clone()).next().is_none();
 
@@ -142,6 +158,582 @@ pub fn run(
         time_in_ms,
         success,
         cookies,
+        iteration: None,
     }
 }
 
+///
+/// Rows of the data file of the file header, the whole file being run once per row.
+/// The data file is relative to the context dir.
+///
+pub fn dataset(hurl_file: &HurlFile, variables: &HashMap<String, Value>, context_dir: &str) -> Result<Option<Vec<dataset::Row>>, Error> {
+    let data = match hurl_file.options().into_iter().find(|option| option.key.value == "data") {
+        None => return Ok(None),
+        Some(data) => data,
+    };
+    let rows = read_dataset(data.value, variables, context_dir)?;
+    Ok(Some(rows))
+}
+
+///
+/// Whether the file has an entry selected by the filter, a file without any is not run.
+///
+pub fn is_selected(hurl_file: &HurlFile, filter: &EntryFilter) -> bool {
+    selection::select(hurl_file, filter).contains(&true)
+}
+
+fn read_dataset(filename: Template, variables: &HashMap<String, Value>, context_dir: &str) -> Result<Vec<dataset::Row>, Error> {
+    let source_info = filename.source_info.clone();
+    let filename = filename.eval(variables)?;
+    match dataset::read(Path::new(context_dir).join(filename).as_path()) {
+        Ok(rows) => Ok(rows),
+        Err(inner) => Err(Error { source_info, inner, assert: false, call_site: None }),
+    }
+}
+
+// state shared by a file and the files it includes
+struct FileRun<'a> {
+    http_client: &'a http::client::Client,
+    cookiejar: &'a mut http::cookie::CookieJar,
+    context_dir: String,
+    fail_fast: bool,
+    variables: HashMap<String, Value>,
+    namespaces: HashMap<String, String>,
+    entries: Vec<EntryResult>,
+    // call of the template being run, if any
+    call_site: Option<Box<SourceInfo>>,
+}
+
+impl FileRun<'_> {
+    // run the selected entries of the file, return false if the run must stop
+    fn run(&mut self,
+           hurl_file: &HurlFile,
+           selected: &[bool],
+           dir: &Path,
+           included_files: &[PathBuf],
+           logger: &format::logger::Logger,
+    ) -> bool {
+        // variables of the file header do not override the variables given to the runner
+        for variable in hurl_file.variables() {
+            let name = variable.key.value;
+            if self.variables.contains_key(&name) {
+                continue;
+            }
+            match variable.value.eval(&self.variables) {
+                Ok(value) => {
+                    self.variables.insert(name, Value::String(value));
+                }
+                Err(e) => {
+                    self.add_error(e, logger);
+                    return false;
+                }
+            }
+        }
+
+        // namespaces of the file header apply to all the entries, over the namespaces given to the runner
+        for namespace in hurl_file.namespaces() {
+            match namespace.value.eval(&self.variables) {
+                Ok(uri) => {
+                    self.namespaces.insert(namespace.key.value, uri);
+                }
+                Err(e) => {
+                    self.add_error(e, logger);
+                    return false;
+                }
+            }
+        }
+
+        for include in hurl_file.includes() {
+            match self.run_include(include.filename, dir, included_files, logger) {
+                Ok(true) => {}
+                Ok(false) => return false,
+                Err(e) => {
+                    self.add_error(e, logger);
+                    return false;
+                }
+            }
+        }
+
+        for (i, file_entry) in hurl_file.entries.iter().enumerate() {
+            if !selected[i] {
+                continue;
+            }
+            let result = match file_entry {
+                FileEntry::Entry(entry) => self.run_iterations(entry.as_ref().clone(), &hurl_file.sections, logger),
+                FileEntry::Call(call) => self.run_call(call, hurl_file, logger),
+            };
+            if let Err(e) = result {
+                self.add_error(e, logger);
+                return false;
+            }
+            if self.fail_fast && !self.success() {
+                return false;
+            }
+        }
+        true
+    }
+
+    // run the entry once, or once per row of the data file of its options
+    // and/or once per element of the list of its loop
+    fn run_iterations(&mut self,
+                      entry: Entry,
+                      file_sections: &[Section],
+                      logger: &format::logger::Logger,
+    ) -> Result<(), Error> {
+        let options = entry.request.clone().options();
+        let data = options.iter().find(|option| option.key.value == "data");
+        let loop_option = options.iter().find(|option| option.key.value == "loop");
+        if data.is_none() && loop_option.is_none() {
+            self.run_entry(entry, file_sections, None, logger);
+            return Ok(());
+        }
+        let rows = match data {
+            None => vec![vec![]],
+            Some(data) => read_dataset(data.value.clone(), &self.variables, self.context_dir.as_str())?,
+        };
+        let mut iteration = 0;
+        for (i, row) in rows.into_iter().enumerate() {
+            if data.is_some() {
+                logger.verbose(format!("data row {}", i + 1).as_str());
+            }
+            let previous_values = self.bind(row);
+            let items = match loop_option {
+                None => Ok(vec![vec![]]),
+                Some(option) => self.loop_items(option),
+            };
+            let items = match items {
+                Ok(items) => items,
+                Err(e) => {
+                    self.restore(previous_values);
+                    return Err(e);
+                }
+            };
+            for item in items {
+                iteration += 1;
+                for (name, value) in item.iter() {
+                    logger.verbose(format!("loop {}: {}", name, value).as_str());
+                }
+                let previous_item = self.bind(item);
+                let success = self.run_entry(entry.clone(), file_sections, Some(iteration), logger);
+                self.restore(previous_item);
+                if self.fail_fast && !success {
+                    self.restore(previous_values);
+                    return Ok(());
+                }
+            }
+            self.restore(previous_values);
+        }
+        Ok(())
+    }
+
+    // the elements of the list of a loop option, each bound to the item variable
+    fn loop_items(&self, option: &KeyValue) -> Result<Vec<Vec<(String, Value)>>, Error> {
+        let (item, list) = option.loop_variables().unwrap();
+        match self.variables.get(list.as_str()) {
+            Some(Value::List(values)) => Ok(values.iter().map(|value| vec![(item.clone(), value.clone())]).collect()),
+            Some(value) => Err(Error {
+                source_info: option.value.source_info.clone(),
+                inner: RunnerError::InvalidLoop { name: list, value_type: value._type() },
+                assert: false,
+                call_site: None,
+            }),
+            None => Err(Error {
+                source_info: option.value.source_info.clone(),
+                inner: RunnerError::TemplateVariableNotDefined { name: list },
+                assert: false,
+                call_site: None,
+            }),
+        }
+    }
+
+    // run the entry and add its result, return false if it has failed
+    fn run_entry(&mut self,
+                 entry: Entry,
+                 file_sections: &[Section],
+                 iteration: Option<usize>,
+                 logger: &format::logger::Logger,
+    ) -> bool {
+        let entry_index = self.entries.len();
+        let skip = entry.request.clone().options().into_iter().find(|option| option.key.value == "skip");
+        if let Some(condition) = skip.and_then(|option| option.condition()) {
+            if self.is_met(&condition) {
+                logger.verbose(format!("entry {} skipped", entry_index + 1).as_str());
+                self.entries.push(EntryResult {
+                    request: None,
+                    response: None,
+                    captures: vec![],
+                    asserts: vec![],
+                    errors: vec![],
+                    time_in_ms: 0,
+                    iteration,
+                    skipped: true,
+                    filename: None,
+                });
+                return true;
+            }
+        }
+        let entry = Entry {
+            request: entry.request.with_defaults(file_sections),
+            ..entry
+        };
+        let mut entry_result = entry::run(entry, self.http_client, entry_index, &mut self.variables, &self.namespaces, self.cookiejar, self.context_dir.clone(), logger);
+        for e in entry_result.errors.iter_mut() {
+            e.call_site = self.call_site.clone();
+        }
+        for e in entry_result.errors.clone() {
+            log_error(logger, e);
+        }
+        let success = entry_result.errors.is_empty();
+        self.entries.push(EntryResult { iteration, ..entry_result });
+        success
+    }
+
+    // run the entry of the template with its parameters bound to the arguments of the call
+    // the parameters are only defined during the call, while the captures are kept
+    fn run_call(&mut self,
+                call: &TemplateCall,
+                hurl_file: &HurlFile,
+                logger: &format::logger::Logger,
+    ) -> Result<(), Error> {
+        let template = match hurl_file.template(call.name.name.as_str()) {
+            Some(template) => template,
+            None => return Err(Error {
+                source_info: call.name.source_info.clone(),
+                inner: RunnerError::UndefinedTemplate { name: call.name.name.clone() },
+                assert: false,
+                call_site: None,
+            }),
+        };
+        let mut arguments = vec![];
+        for argument in call.arguments.clone() {
+            let value = argument.value.eval(&self.variables)?;
+            arguments.push((argument.key.value, Value::String(value)));
+        }
+
+        logger.verbose(format!("call template {}", call.name.name).as_str());
+        let previous_values = self.bind(arguments);
+        // the errors point into the template, they keep the call site as a secondary source info
+        self.call_site = Some(Box::new(call.name.source_info.clone()));
+        let result = self.run_iterations(template.entry, &hurl_file.sections, logger);
+        self.call_site = None;
+        self.restore(previous_values);
+        result.map_err(|e| Error { call_site: Some(Box::new(call.name.source_info.clone())), ..e })
+    }
+
+    // an undefined, false or null variable does not meet the condition,
+    // a value is compared to the variable as rendered in a template
+    fn is_met(&self, condition: &Condition) -> bool {
+        let value = self.variables.get(condition.variable.as_str());
+        let met = match &condition.comparison {
+            None => match value {
+                None | Some(Value::Bool(false)) | Some(Value::Null) => false,
+                Some(_) => true,
+            },
+            Some((equals, expected)) => {
+                let actual = value.and_then(|value| value.render());
+                (actual.as_deref() == Some(expected.as_str())) == *equals
+            }
+        };
+        met != condition.not
+    }
+
+    // define variables, returning their previous values
+    fn bind(&mut self, variables: Vec<(String, Value)>) -> Vec<(String, Option<Value>)> {
+        variables
+            .into_iter()
+            .map(|(name, value)| (name.clone(), self.variables.insert(name, value)))
+            .collect()
+    }
+
+    fn restore(&mut self, previous_values: Vec<(String, Option<Value>)>) {
+        for (name, previous_value) in previous_values.into_iter().rev() {
+            match previous_value {
+                Some(value) => self.variables.insert(name, value),
+                None => self.variables.remove(&name),
+            };
+        }
+    }
+
+    fn run_include(&mut self,
+                   filename: Filename,
+                   dir: &Path,
+                   included_files: &[PathBuf],
+                   logger: &format::logger::Logger,
+    ) -> Result<bool, Error> {
+        let error = |inner: RunnerError| Error {
+            source_info: filename.source_info.clone(),
+            inner,
+            assert: false,
+            call_site: None,
+        };
+        let path = dir.join(filename.value.as_str());
+        let (absolute_path, contents) = match (fs::canonicalize(&path), fs::read_to_string(&path)) {
+            (Ok(absolute_path), Ok(contents)) => (absolute_path, contents),
+            _ => return Err(error(RunnerError::FileReadAccess { value: filename.value.clone() })),
+        };
+        if included_files.contains(&absolute_path) {
+            return Err(error(RunnerError::IncludeCycle { value: filename.value.clone() }));
+        }
+
+        let lines: Vec<String> = regex::Regex::new(r"\n|\r\n")
+            .unwrap()
+            .split(&contents)
+            .map(|l| l.to_string())
+            .collect();
+        let include_logger = format::logger::Logger {
+            filename: Some(path.display().to_string()),
+            lines,
+            verbose: logger.verbose,
+            color: logger.color,
+        };
+        let hurl_file = match parser::parse_hurl_file(contents.as_str()) {
+            Ok(hurl_file) => hurl_file,
+            Err(e) => {
+                let parse_error = format::error::Error {
+                    source_info: e.source_info(),
+                    description: e.description(),
+                    fixme: e.fixme(),
+                    lines: vec![],
+                    filename: "".to_string(),
+                    warning: false,
+                    color: logger.color,
+                };
+                include_logger.error(&parse_error);
+                return Err(error(RunnerError::InvalidInclude { value: filename.value.clone() }));
+            }
+        };
+
+        logger.verbose(format!("include {}", path.display()).as_str());
+        let mut included_files = included_files.to_vec();
+        included_files.push(absolute_path);
+        let dir = path.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+        // the file header of the included file only applies to its entries, its captures are kept
+        let namespaces = self.namespaces.clone();
+        let header_variables: Vec<String> = hurl_file
+            .variables()
+            .into_iter()
+            .map(|variable| variable.key.value)
+            .filter(|name| !self.variables.contains_key(name))
+            .collect();
+
+        // the source positions of the included entries refer to the included file
+        let start = self.entries.len();
+        let completed = self.run(&hurl_file, &vec![true; hurl_file.entries.len()], dir.as_path(), &included_files, &include_logger);
+        for entry in self.entries[start..].iter_mut().filter(|entry| entry.filename.is_none()) {
+            entry.filename = Some(path.display().to_string());
+        }
+        self.namespaces = namespaces;
+        for name in header_variables {
+            self.variables.remove(&name);
+        }
+
+        // the including file is not run after a failed included file
+        Ok(completed && self.entries[start..].iter().all(|entry| entry.errors.is_empty()))
+    }
+
+    fn success(&self) -> bool {
+        self.entries.iter().all(|e| e.errors.is_empty())
+    }
+
+    // errors outside of an entry are reported in an entry result without request
+    fn add_error(&mut self, e: Error, logger: &format::logger::Logger) {
+        log_error(logger, e.clone());
+        self.entries.push(EntryResult {
+            request: None,
+            response: None,
+            captures: vec![],
+            asserts: vec![],
+            errors: vec![e],
+            time_in_ms: 0,
+            iteration: None,
+            skipped: false,
+            filename: None,
+        });
+    }
+}
+
+fn log_error(logger: &format::logger::Logger, e: Error) {
+    let error = format::error::Error {
+        source_info: e.clone().source_info,
+        description: e.clone().description(),
+        fixme: e.fixme(),
+        lines: vec![],
+        filename: "".to_string(),
+        warning: false,
+        color: false,
+    };
+    logger.clone().error(&error);
+    if let Some(call_site) = e.call_site {
+        logger.error(&format::error::Error {
+            source_info: *call_site,
+            description: "Template call".to_string(),
+            fixme: "the template is called here".to_string(),
+            lines: vec![],
+            filename: "".to_string(),
+            warning: false,
+            color: false,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::Pos;
+
+    fn run_content(content: &str, variables: HashMap<String, Value>) -> HurlResult {
+        run_file(content, "test.hurl", variables)
+    }
+
+    fn run_file(content: &str, filename: &str, variables: HashMap<String, Value>) -> HurlResult {
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let client = http::client::Client::init(http::client::ClientOptions {
+            noproxy_hosts: vec![],
+            insecure: false,
+            redirect: http::client::Redirect::None,
+            http_proxy: None,
+            https_proxy: None,
+            all_proxy: None,
+            timeout: None,
+        });
+        let options = RunnerOptions {
+            fail_fast: false,
+            variables,
+            namespaces: HashMap::new(),
+            to_entry: None,
+            seed: None,
+            entry_filter: None,
+        };
+        let logger = format::logger::Logger {
+            filename: None,
+            lines: content.split('\n').map(|line| line.to_string()).collect(),
+            verbose: false,
+            color: false,
+        };
+        let mut cookiejar = http::cookie::CookieJar::init(vec![]);
+        run(hurl_file, client, filename.to_string(), &mut cookiejar, ".".to_string(), options, logger)
+    }
+
+    #[test]
+    fn test_template_call_error() {
+        let hurl_result = run_content(r#"TEMPLATE hello expected
+GET http://localhost:8000/{{undefined}}
+
+CALL hello
+expected: Bye
+"#, HashMap::new());
+        assert_eq!(hurl_result.entries.len(), 1);
+        assert_eq!(hurl_result.errors(), vec![Error {
+            source_info: SourceInfo::init(2, 29, 2, 38),
+            inner: RunnerError::TemplateVariableNotDefined { name: "undefined".to_string() },
+            assert: false,
+            call_site: Some(Box::new(SourceInfo::init(4, 6, 4, 11))),
+        }]);
+    }
+
+    #[test]
+    fn test_template_call_error_outside_template() {
+        let hurl_result = run_content(r#"TEMPLATE hello expected
+GET http://localhost:8000/{{expected}}
+
+GET http://localhost:8000/{{expected}}
+"#, HashMap::new());
+        let errors = hurl_result.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].source_info.start, Pos { line: 4, column: 29 });
+        assert_eq!(errors[0].call_site, None);
+    }
+
+    #[test]
+    fn test_template_call_data_error() {
+        let hurl_result = run_content(r#"TEMPLATE hello expected
+GET http://localhost:8000/hello
+[Options]
+data: missing.csv
+
+CALL hello
+expected: Bye
+"#, HashMap::new());
+        let errors = hurl_result.errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].inner, RunnerError::FileReadAccess { value: "./missing.csv".to_string() });
+        assert_eq!(errors[0].call_site, Some(Box::new(SourceInfo::init(6, 6, 6, 11))));
+    }
+
+    #[test]
+    fn test_skip_float() {
+        let mut variables = HashMap::new();
+        variables.insert("ratio".to_string(), Value::from_f64(1.5));
+        let hurl_result = run_content(r#"GET http://localhost:8000/{{undefined}}
+[Options]
+skip: ratio == 1.5
+"#, variables);
+        assert!(hurl_result.entries[0].skipped);
+        assert!(hurl_result.errors().is_empty());
+    }
+
+    #[test]
+    fn test_include_file_header_scope() {
+        let dir = std::env::temp_dir().join("hurl_test_include_file_header_scope");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("included.hurl"), r#"[Variables]
+host: localhost
+
+[Namespaces]
+ns: http://example.net/ns
+"#).unwrap();
+
+        let http_client = http::client::Client::init(http::client::ClientOptions {
+            noproxy_hosts: vec![],
+            insecure: false,
+            redirect: http::client::Redirect::None,
+            http_proxy: None,
+            https_proxy: None,
+            all_proxy: None,
+            timeout: None,
+        });
+        let mut cookiejar = http::cookie::CookieJar::init(vec![]);
+        let mut file_run = FileRun {
+            http_client: &http_client,
+            cookiejar: &mut cookiejar,
+            context_dir: ".".to_string(),
+            fail_fast: false,
+            variables: HashMap::new(),
+            namespaces: HashMap::new(),
+            entries: vec![],
+            call_site: None,
+        };
+        let logger = format::logger::Logger {
+            filename: None,
+            lines: vec![],
+            verbose: false,
+            color: false,
+        };
+        let filename = Filename {
+            value: "included.hurl".to_string(),
+            source_info: SourceInfo::init(2, 1, 2, 13),
+        };
+        assert!(file_run.run_include(filename, dir.as_path(), &[], &logger).unwrap());
+        assert!(file_run.entries.is_empty());
+        assert!(file_run.namespaces.is_empty());
+        assert!(file_run.variables.is_empty());
+    }
+
+    #[test]
+    fn test_include_failure() {
+        let dir = std::env::temp_dir().join("hurl_test_include_failure");
+        fs::create_dir_all(&dir).unwrap();
+        let included = dir.join("login.hurl");
+        fs::write(&included, "GET http://localhost:8000/{{undefined}}\n").unwrap();
+
+        let content = r#"[Includes]
+login.hurl
+
+GET http://localhost:8000/{{undefined}}
+"#;
+        let hurl_result = run_file(content, dir.join("test.hurl").to_str().unwrap(), HashMap::new());
+        assert_eq!(hurl_result.entries.len(), 1);
+        assert_eq!(hurl_result.entries[0].filename, Some(included.display().to_string()));
+        assert!(!hurl_result.success);
+    }
+}
@@ -17,10 +17,38 @@
  */
 use std::collections::HashMap;
 
+use regex::Regex;
+
+use crate::core::ast::Expr;
 use crate::core::common::Value;
 use crate::core::json;
 
-use super::core::Error;
+use super::core::{Error, RunnerError};
+
+///
+/// A difference between an expected json value and the actual one
+/// a value is None when it is missing from one side
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonDiff {
+    pub path: String,
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+impl JsonDiff {
+    /// diff-like lines: "- path: expected" and "+ path: actual"
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if let Some(expected) = &self.expected {
+            lines.push(format!("- {}: {}", self.path, expected));
+        }
+        if let Some(actual) = &self.actual {
+            lines.push(format!("+ {}: {}", self.path, actual));
+        }
+        lines
+    }
+}
 
 impl json::Value {
     pub fn eval(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
@@ -48,10 +76,204 @@ impl json::Value {
                 }
                 Ok(format!("{{{}{}}}", space0, elems_string.join(",")))
             }
+            // a matcher is only meaningful in an expected body
+            json::Value::Matcher(matcher) => Err(Error {
+                source_info: matcher.source_info.clone(),
+                inner: RunnerError::UnexpectedMatcher { value: matcher.to_string() },
+                assert: false,
+                call_site: None,
+            }),
+            json::Value::Expression(expr) => Ok(expression_json(expr, variables)?.to_string()),
+        }
+    }
+
+    ///
+    /// compact json text of the expected value, used in the assert errors
+    /// matchers are displayed as written
+    ///
+    pub fn display(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
+        match self {
+            json::Value::String(template) => Ok(serde_json::Value::String(template.eval(variables)?).to_string()),
+            json::Value::List { elements, .. } => {
+                let mut values = vec![];
+                for element in elements {
+                    values.push(element.value.display(variables)?);
+                }
+                Ok(format!("[{}]", values.join(",")))
+            }
+            json::Value::Object { elements, .. } => {
+                let mut values = vec![];
+                for element in elements {
+                    let name = serde_json::Value::String(element.name).to_string();
+                    values.push(format!("{}:{}", name, element.value.display(variables)?));
+                }
+                Ok(format!("{{{}}}", values.join(",")))
+            }
+            json::Value::Matcher(matcher) => Ok(matcher.to_string()),
+            value => value.eval(variables),
+        }
+    }
+}
+
+impl json::Value {
+    ///
+    /// compare structurally with the actual json value
+    /// key order and whitespaces are not significant, numbers are compared by value
+    /// the paths matching one of the ignored patterns are not compared
+    ///
+    pub fn diff(self, actual: &serde_json::Value, variables: &HashMap<String, Value>, ignored: &[Regex]) -> Result<Vec<JsonDiff>, Error> {
+        let mut diffs = vec![];
+        diff_value(self, actual, "$".to_string(), variables, ignored, &mut diffs)?;
+        Ok(diffs)
+    }
+}
+
+fn diff_value(expected: json::Value, actual: &serde_json::Value, path: String, variables: &HashMap<String, Value>, ignored: &[Regex], diffs: &mut Vec<JsonDiff>) -> Result<(), Error> {
+    if ignored.iter().any(|re| re.is_match(path.as_str())) {
+        return Ok(());
+    }
+    let equal = match (expected.clone(), actual) {
+        (json::Value::Null {}, serde_json::Value::Null) => true,
+        (json::Value::Boolean(v1), serde_json::Value::Bool(v2)) => v1 == *v2,
+        (json::Value::Number(v1), serde_json::Value::Number(v2)) => match (v1.parse::<f64>(), v2.as_f64()) {
+            (Ok(v1), Some(v2)) => v1 == v2,
+            _ => false,
+        },
+        (json::Value::String(template), serde_json::Value::String(v2)) => template.eval(variables)? == *v2,
+        (json::Value::Matcher(matcher), value) => match_value(matcher.value, value, variables)?,
+        (json::Value::Expression(expr), value) => match (expression_json(expr, variables)?, value) {
+            (serde_json::Value::Number(v1), serde_json::Value::Number(v2)) => v1.as_f64() == v2.as_f64(),
+            (v1, v2) => v1 == *v2,
+        },
+        (json::Value::List { elements, .. }, serde_json::Value::Array(values)) => {
+            for (i, element) in elements.iter().enumerate() {
+                let path = format!("{}[{}]", path, i);
+                match values.get(i) {
+                    Some(value) => diff_value(element.value.clone(), value, path, variables, ignored, diffs)?,
+                    None => push_diff(diffs, path, Some(element.value.clone().display(variables)?), None, ignored),
+                }
+            }
+            for (i, value) in values.iter().enumerate().skip(elements.len()) {
+                push_diff(diffs, format!("{}[{}]", path, i), None, Some(value.to_string()), ignored);
+            }
+            true
+        }
+        (json::Value::Object { elements, .. }, serde_json::Value::Object(map)) => {
+            for element in elements.iter() {
+                let path = format!("{}{}", path, key_path(element.name.as_str()));
+                match map.get(element.name.as_str()) {
+                    Some(value) => diff_value(element.value.clone(), value, path, variables, ignored, diffs)?,
+                    None => push_diff(diffs, path, Some(element.value.clone().display(variables)?), None, ignored),
+                }
+            }
+            for (name, value) in map.iter() {
+                if !elements.iter().any(|e| e.name == *name) {
+                    push_diff(diffs, format!("{}{}", path, key_path(name.as_str())), None, Some(value.to_string()), ignored);
+                }
+            }
+            true
+        }
+        _ => false,
+    };
+    if !equal {
+        let expected = expected.display(variables)?;
+        diffs.push(JsonDiff { path, expected: Some(expected), actual: Some(actual.to_string()) });
+    }
+    Ok(())
+}
+
+///
+/// json value of the variable in an unquoted {{expression}}
+///
+fn expression_json(expr: Expr, variables: &HashMap<String, Value>) -> Result<serde_json::Value, Error> {
+    let source_info = expr.source_info();
+    match expr.eval(variables)? {
+        value @ Value::Bytes(_) => Err(Error {
+            source_info,
+            inner: RunnerError::UnrenderableVariable { value: value.to_string() },
+            assert: false,
+            call_site: None,
+        }),
+        value => Ok(value.to_json()),
+    }
+}
+
+fn push_diff(diffs: &mut Vec<JsonDiff>, path: String, expected: Option<String>, actual: Option<String>, ignored: &[Regex]) {
+    if !ignored.iter().any(|re| re.is_match(path.as_str())) {
+        diffs.push(JsonDiff { path, expected, actual });
+    }
+}
+
+fn match_value(matcher: json::MatcherValue, value: &serde_json::Value, variables: &HashMap<String, Value>) -> Result<bool, Error> {
+    match matcher {
+        json::MatcherValue::Any {} => Ok(true),
+        json::MatcherValue::AnyType { name, .. } => Ok(match name.as_str() {
+            "string" => value.is_string(),
+            "number" => value.is_number(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "boolean" => value.is_boolean(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => false,
+        }),
+        json::MatcherValue::Matches { value: template, .. } => {
+            let source_info = template.source_info.clone();
+            let regex = match Regex::new(template.eval(variables)?.as_str()) {
+                Ok(regex) => regex,
+                Err(_) => return Err(Error {
+                    source_info,
+                    inner: RunnerError::InvalidRegex(),
+                    assert: false,
+                    call_site: None,
+                }),
+            };
+            Ok(match value {
+                serde_json::Value::String(s) => regex.is_match(s.as_str()),
+                _ => false,
+            })
         }
     }
 }
 
+// path segment of an object key, bracket notation for non-identifier keys
+fn key_path(name: &str) -> String {
+    if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        format!(".{}", name)
+    } else {
+        format!("['{}']", name)
+    }
+}
+
+///
+/// compile a path pattern to ignore in a json comparison
+/// the pattern is a path starting with $, with optional wildcards:
+/// `[*]` any index, `.*` any key and `..` any descendant
+///
+pub fn ignored_path(pattern: &str) -> Option<Regex> {
+    if !pattern.starts_with('$') {
+        return None;
+    }
+    let mut re = String::from(r"^\$");
+    let mut rest = &pattern[1..];
+    while !rest.is_empty() {
+        if rest.starts_with("[*]") {
+            re.push_str(r"\[\d+\]");
+            rest = &rest[3..];
+        } else if rest.starts_with(".*") {
+            re.push_str(r"(\.[^.\[]+|\['[^']*'\])");
+            rest = &rest[2..];
+        } else if rest.starts_with("..") {
+            re.push_str(r"(\.[^.\[]+|\[[^\]]*\])*\.");
+            rest = &rest[2..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            re.push_str(regex::escape(c.to_string().as_str()).as_str());
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    re.push('$');
+    Regex::new(re.as_str()).ok()
+}
 
 impl json::ListElement {
     pub fn eval(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
@@ -70,7 +292,7 @@ impl json::ObjectElement {
 
 #[cfg(test)]
 mod tests {
-    use crate::core::ast::{Template, TemplateElement};
+    use crate::core::ast::{ExprValue, Template, TemplateElement, Variable, Whitespace};
     use crate::core::common::SourceInfo;
 
     use super::*;
@@ -140,4 +362,118 @@ mod tests {
     "firstName": "John"
 }"#.to_string());
     }
+
+    #[test]
+    fn test_diff() {
+        let variables = HashMap::new();
+        let expected = json::tests::person_value();
+        assert_eq!(expected.clone().diff(&serde_json::json!({"firstName": "John"}), &variables, &[]).unwrap(), vec![]);
+        assert_eq!(expected.clone().diff(&serde_json::json!({"firstName": "Bob", "age": 20}), &variables, &[]).unwrap(), vec![
+            JsonDiff { path: "$.firstName".to_string(), expected: Some("\"John\"".to_string()), actual: Some("\"Bob\"".to_string()) },
+            JsonDiff { path: "$.age".to_string(), expected: None, actual: Some("20".to_string()) },
+        ]);
+        assert_eq!(expected.clone().diff(&serde_json::json!([]), &variables, &[]).unwrap(), vec![
+            JsonDiff { path: "$".to_string(), expected: Some("{\"firstName\":\"John\"}".to_string()), actual: Some("[]".to_string()) },
+        ]);
+        let ignored = vec![ignored_path("$.age").unwrap()];
+        assert_eq!(expected.diff(&serde_json::json!({"firstName": "John", "age": 20}), &variables, &ignored).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_diff_list() {
+        let variables = HashMap::new();
+        let expected = json::Value::List {
+            space0: "".to_string(),
+            elements: vec![
+                json::ListElement { space0: "".to_string(), value: json::Value::Number("1.0".to_string()), space1: "".to_string() },
+                json::ListElement { space0: "".to_string(), value: json::Value::Null {}, space1: "".to_string() },
+            ],
+        };
+        assert_eq!(expected.clone().diff(&serde_json::json!([1, null]), &variables, &[]).unwrap(), vec![]);
+        assert_eq!(expected.diff(&serde_json::json!([2]), &variables, &[]).unwrap(), vec![
+            JsonDiff { path: "$[0]".to_string(), expected: Some("1.0".to_string()), actual: Some("2".to_string()) },
+            JsonDiff { path: "$[1]".to_string(), expected: Some("null".to_string()), actual: None },
+        ]);
+    }
+
+    #[test]
+    fn test_diff_matcher() {
+        let variables = HashMap::new();
+        let any_integer = json::Value::Matcher(json::Matcher {
+            space0: "".to_string(),
+            value: json::MatcherValue::AnyType { space0: " ".to_string(), name: "integer".to_string() },
+            space1: "".to_string(),
+            source_info: SourceInfo::init(1, 1, 1, 1),
+        });
+        let date = json::Value::Matcher(json::Matcher {
+            space0: "".to_string(),
+            value: json::MatcherValue::Matches {
+                space0: " ".to_string(),
+                value: Template {
+                    quotes: true,
+                    elements: vec![TemplateElement::String { value: "^\\d{4}-".to_string(), encoded: "^\\\\d{4}-".to_string() }],
+                    source_info: SourceInfo::init(1, 1, 1, 1),
+                },
+            },
+            space1: "".to_string(),
+            source_info: SourceInfo::init(1, 1, 1, 1),
+        });
+        let expected = json::Value::Object {
+            space0: "".to_string(),
+            elements: vec![
+                json::ObjectElement { space0: "".to_string(), name: "id".to_string(), space1: "".to_string(), space2: "".to_string(), value: any_integer, space3: "".to_string() },
+                json::ObjectElement { space0: "".to_string(), name: "date".to_string(), space1: "".to_string(), space2: "".to_string(), value: date, space3: "".to_string() },
+            ],
+        };
+        assert_eq!(expected.clone().diff(&serde_json::json!({"id": 12, "date": "2020-10-01"}), &variables, &[]).unwrap(), vec![]);
+        assert_eq!(expected.clone().diff(&serde_json::json!({"id": 1.5, "date": "01/10/2020"}), &variables, &[]).unwrap(), vec![
+            JsonDiff { path: "$.id".to_string(), expected: Some("{{any integer}}".to_string()), actual: Some("1.5".to_string()) },
+            JsonDiff { path: "$.date".to_string(), expected: Some("{{matches \"^\\\\d{4}-\"}}".to_string()), actual: Some("\"01/10/2020\"".to_string()) },
+        ]);
+        // a matcher can not be sent in a request body
+        let error = expected.eval(&variables).err().unwrap();
+        assert_eq!(error.inner, RunnerError::UnexpectedMatcher { value: "{{any integer}}".to_string() });
+    }
+
+    #[test]
+    fn test_expression_value() {
+        let expression = |name: &str| json::Value::Expression(Expr {
+            space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 3, 1, 3) },
+            value: ExprValue::Variable(Variable { name: name.to_string(), source_info: SourceInfo::init(1, 3, 1, 7) }),
+            filters: vec![],
+            space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 7, 1, 7) },
+        });
+        let mut variables = HashMap::new();
+        variables.insert("id".to_string(), Value::Integer(12));
+        variables.insert("admin".to_string(), Value::Bool(true));
+        variables.insert("name".to_string(), Value::String("Bob".to_string()));
+        variables.insert("user".to_string(), Value::Object(vec![("id".to_string(), Value::Integer(1))]));
+        assert_eq!(expression("id").eval(&variables).unwrap(), "12".to_string());
+        assert_eq!(expression("admin").eval(&variables).unwrap(), "true".to_string());
+        assert_eq!(expression("name").eval(&variables).unwrap(), "\"Bob\"".to_string());
+        assert_eq!(expression("user").eval(&variables).unwrap(), "{\"id\":1}".to_string());
+        assert_eq!(expression("id").diff(&serde_json::json!(12.0), &variables, &[]).unwrap(), vec![]);
+
+        let error = expression("undefined").eval(&variables).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 3, 1, 7));
+        assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: "undefined".to_string() });
+    }
+
+    #[test]
+    fn test_ignored_path() {
+        assert!(ignored_path("$.id").unwrap().is_match("$.id"));
+        assert!(!ignored_path("$.id").unwrap().is_match("$.ids"));
+        assert!(ignored_path("$.items[*].createdAt").unwrap().is_match("$.items[12].createdAt"));
+        assert!(ignored_path("$.*.id").unwrap().is_match("$.user.id"));
+        assert!(ignored_path("$..id").unwrap().is_match("$.users[0].id"));
+        assert!(ignored_path("$..id").unwrap().is_match("$.id"));
+        assert!(!ignored_path("$..id").unwrap().is_match("$.uuid"));
+        assert!(ignored_path("id").is_none());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let diff = JsonDiff { path: "$.id".to_string(), expected: Some("1".to_string()), actual: Some("2".to_string()) };
+        assert_eq!(diff.lines(), vec!["- $.id: 1".to_string(), "+ $.id: 2".to_string()]);
+    }
 }
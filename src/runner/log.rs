@@ -33,6 +33,9 @@ impl Serialize for HurlResult {
         state.serialize_field("success", &self.clone().success)?;
         state.serialize_field("time", &self.time_in_ms)?;
         state.serialize_field("cookies", &self.cookies)?;
+        if let Some(iteration) = self.iteration {
+            state.serialize_field("iteration", &iteration)?;
+        }
         state.end()
     }
 }
@@ -52,6 +55,15 @@ impl Serialize for EntryResult {
         state.serialize_field("captures", &self.captures)?;
         state.serialize_field("asserts", &self.asserts)?;
         state.serialize_field("time", &self.time_in_ms)?;
+        if let Some(iteration) = self.iteration {
+            state.serialize_field("iteration", &iteration)?;
+        }
+        if self.skipped {
+            state.serialize_field("skipped", &self.skipped)?;
+        }
+        if let Some(filename) = &self.filename {
+            state.serialize_field("filename", filename)?;
+        }
         state.end()
     }
 }
@@ -146,7 +158,9 @@ fn parse_result(value: serde_json::Value) -> Result<HurlResult, ParseError> {
             Some(_) => return Err("expecting list for cookies".to_string()),
         };
 
-        Ok(HurlResult { filename, entries, time_in_ms, success, cookies })
+        let iteration = parse_iteration(&value)?;
+
+        Ok(HurlResult { filename, entries, time_in_ms, success, cookies, iteration })
     } else {
         Err("expecting an object for the result".to_string())
     }
@@ -167,6 +181,17 @@ fn parse_entry_result(value: serde_json::Value) -> Result<EntryResult, String> {
             Some(r)
         }
     };
+    let iteration = parse_iteration(&value)?;
+    let skipped = match value.get("skipped") {
+        None => false,
+        Some(serde_json::Value::Bool(skipped)) => *skipped,
+        Some(_) => return Err("expecting a boolean for skipped".to_string()),
+    };
+    let filename = match value.get("filename") {
+        None => None,
+        Some(serde_json::Value::String(filename)) => Some(filename.clone()),
+        Some(_) => return Err("expecting a string for the filename".to_string()),
+    };
     Ok(EntryResult {
         request,
         response,
@@ -174,7 +199,21 @@ fn parse_entry_result(value: serde_json::Value) -> Result<EntryResult, String> {
         asserts: vec![],
         errors: vec![],
         time_in_ms: 0,
+        iteration,
+        skipped,
+        filename,
     })
 }
 
+fn parse_iteration(value: &serde_json::Value) -> Result<Option<usize>, String> {
+    match value.get("iteration") {
+        None => Ok(None),
+        Some(serde_json::Value::Number(n)) => match n.as_u64() {
+            Some(x) => Ok(Some(x as usize)),
+            None => Err("expecting an integer for the iteration".to_string()),
+        },
+        Some(_) => Err("expecting an integer for the iteration".to_string()),
+    }
+}
+
 
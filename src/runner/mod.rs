@@ -23,10 +23,15 @@
 //!
 //!
 
+// the errors carry the source info of the template call they have occurred in
+#![allow(clippy::result_large_err)]
+
 mod assert;
 mod body;
 mod capture;
 mod cookie;
+mod css;
+pub mod dataset;
 pub mod core;
 mod entry;
 pub mod file;
@@ -36,6 +41,7 @@ mod predicate;
 mod query;
 pub mod request;
 mod response;
+mod selection;
 mod template;
 mod text;
 mod xpath;
@@ -17,13 +17,14 @@
  */
 use std::collections::HashMap;
 
-use regex::Regex;
 
 use crate::core::common::{Pos, SourceInfo};
 use crate::core::common::Value;
+use crate::jsonschema;
 
 use super::core::{Error, RunnerError};
 use super::core::*;
+use super::xpath;
 use super::super::core::ast::*;
 
 // equals 10         function  return ()
@@ -54,8 +55,8 @@ use super::super::core::ast::*;
 // equals template  becomes and equals string
 
 impl Predicate {
-    pub fn eval(self, variables: &HashMap<String, Value>, value: Option<Value>) -> PredicateResult {
-        let assert_result = self.predicate_func.clone().eval(variables, value)?;
+    pub fn eval(self, variables: &HashMap<String, Value>, value: Option<Value>, context_dir: String) -> PredicateResult {
+        let assert_result = self.predicate_func.clone().eval(variables, value, context_dir)?;
         let source_info = SourceInfo {
             start: Pos { line: self.space0.source_info.start.line, column: 0 },
             end: Pos { line: self.space0.source_info.start.line, column: 0 },
@@ -69,6 +70,7 @@ impl Predicate {
                     type_mismatch: true,
                 },
                 assert: true,
+                call_site: None,
             })
         } else if self.not && assert_result.success {
             Err(Error {
@@ -79,6 +81,7 @@ impl Predicate {
                     type_mismatch: false,
                 },
                 assert: true,
+                call_site: None,
             })
         } else if !self.not && !assert_result.success {
             Err(Error {
@@ -89,6 +92,7 @@ impl Predicate {
                     type_mismatch: false,
                 },
                 assert: true,
+                call_site: None,
             })
         } else {
             Ok(())
@@ -111,7 +115,7 @@ impl Value {
             Value::String(v) => format!("string <{}>", v),
             Value::Float(i, d) => format!("float <{}.{}>", i, d),
             Value::List(values) => format!("[{}]", values.iter().map(|v| v.clone().display()).collect::<Vec<String>>().join(", ")),
-            Value::Nodeset(n) => format!("nodeset of size <{}>", n),
+            Value::Nodeset(values) => format!("nodeset of size <{}>", values.len()),
             Value::Object(_) => "object".to_string(),
             Value::Bytes(values) => format!("byte array of size <{}>", values.len()),
             Value::Null => "null".to_string(),
@@ -120,7 +124,7 @@ impl Value {
 }
 
 impl PredicateFunc {
-    fn eval(self, variables: &HashMap<String, Value>, optional_value: Option<Value>) -> Result<AssertResult, Error> {
+    fn eval(self, variables: &HashMap<String, Value>, optional_value: Option<Value>, context_dir: String) -> Result<AssertResult, Error> {
         match optional_value {
             None => {
                 let type_mismatch = if let PredicateFuncValue::Exist {} = self.value {
@@ -135,7 +139,7 @@ impl PredicateFunc {
                     type_mismatch,
                 })
             }
-            Some(value) => self.eval_something(variables, value)
+            Some(value) => self.eval_something(variables, value, context_dir)
         }
     }
 
@@ -193,14 +197,20 @@ impl PredicateFunc {
             }
             PredicateFuncValue::Match { value: expected, .. } => {
                 let expected = expected.eval(variables)?;
-                Ok(format!("matches regex <{}>", expected))
+                Ok(format!("matches regex <{}>", expected.as_str()))
             }
-            PredicateFuncValue::Exist {} => Ok("something".to_string())
+            PredicateFuncValue::Exist {} => Ok("something".to_string()),
+            PredicateFuncValue::JsonEqual { value: expected, .. } => {
+                let expected = expected.display(variables)?;
+                Ok(format!("json equals to <{}>", expected))
+            }
+            PredicateFuncValue::JsonSchema { filename, .. } => Ok(format!("valid against json schema <{}>", filename.value)),
+            PredicateFuncValue::XmlSchema { filename, .. } => Ok(format!("valid against xml schema <{}>", filename.value)),
         }
     }
 
 
-    fn eval_something(self, variables: &HashMap<String, Value>, value: Value) -> Result<AssertResult, Error> {
+    fn eval_something(self, variables: &HashMap<String, Value>, value: Value, context_dir: String) -> Result<AssertResult, Error> {
         match self.value {
 
             // equals int
@@ -246,8 +256,8 @@ impl PredicateFunc {
                         expected,
                         type_mismatch: false,
                     }),
-                    Value::Nodeset(n) => Ok(AssertResult {
-                        success: n as u64 == expected_value,
+                    Value::Nodeset(values) => Ok(AssertResult {
+                        success: values.len() as u64 == expected_value,
                         actual,
                         expected,
                         type_mismatch: false,
@@ -334,11 +344,8 @@ impl PredicateFunc {
 
             // match string
             PredicateFuncValue::Match { value: expected, .. } => {
-                let expected = expected.eval(variables)?;
-                let regex = match Regex::new(expected.as_str()) {
-                    Ok(re) => re,
-                    Err(_) => return Err(Error { source_info: self.source_info.clone(), inner: RunnerError::InvalidRegex(), assert: false })
-                };
+                let regex = expected.eval(variables)?;
+                let expected = regex.as_str();
                 match value.clone() {
                     Value::String(actual) => Ok(AssertResult {
                         success: regex.is_match(actual.as_str()),
@@ -358,7 +365,7 @@ impl PredicateFunc {
             // exists
             PredicateFuncValue::Exist {} => {
                 match value {
-                    Value::Nodeset(0) => Ok(AssertResult {
+                    Value::Nodeset(ref values) if values.is_empty() => Ok(AssertResult {
                         success: false,
                         actual: value.display(),
                         expected: "something".to_string(),
@@ -372,6 +379,73 @@ impl PredicateFunc {
                     })
                 }
             }
+
+            // json schema
+            PredicateFuncValue::JsonSchema { space0, filename, space2, .. } => {
+                let expected = format!("valid against json schema <{}>", filename.value);
+                let source_info = filename.source_info.clone();
+                let bytes = Bytes::File { space0, filename, space1: space2 }.eval(variables, context_dir)?;
+                let schema = match serde_json::from_slice(bytes.as_slice()) {
+                    Ok(schema) => schema,
+                    Err(_) => return Err(Error {
+                        source_info,
+                        inner: RunnerError::InvalidJsonSchema,
+                        assert: false,
+                        call_site: None,
+                    }),
+                };
+                assert_json_schema(value, &schema, expected)
+            }
+
+            // json equals (structural comparison)
+            PredicateFuncValue::JsonEqual { value: expected, .. } => assert_json_equal(value, expected, variables),
+
+            // xml schema (a .dtd file is a DTD, any other file an XML Schema)
+            PredicateFuncValue::XmlSchema { space0, filename, space2, .. } => {
+                let expected = format!("valid against xml schema <{}>", filename.value);
+                let kind = if filename.value.ends_with(".dtd") { xpath::SchemaKind::Dtd } else { xpath::SchemaKind::Xsd };
+                let schema_source_info = filename.source_info.clone();
+                let schema = Bytes::File { space0, filename, space1: space2 }.eval(variables, context_dir)?;
+                let xml = match value {
+                    Value::String(ref xml) => xml.clone(),
+                    _ => return Ok(AssertResult {
+                        success: false,
+                        actual: value.display(),
+                        expected,
+                        type_mismatch: true,
+                    })
+                };
+                match xpath::validate_xml(xml, schema.as_slice(), kind) {
+                    Ok(errors) => {
+                        let actual = match errors.len() {
+                            0 => "valid xml".to_string(),
+                            1 => "1 validation error".to_string(),
+                            n => format!("{} validation errors", n),
+                        };
+                        let details: String = errors.iter().map(|e| format!("\n    line {}: {}", e.line, e.message)).collect();
+                        Ok(AssertResult {
+                            success: errors.is_empty(),
+                            actual: format!("{}{}", actual, details),
+                            expected,
+                            type_mismatch: false,
+                        })
+                    }
+                    Err(xpath::SchemaError::InvalidXML {}) => Err(Error {
+                        source_info: self.source_info,
+                        inner: RunnerError::QueryInvalidXml,
+                        assert: false,
+                        call_site: None,
+                    }),
+                    Err(xpath::SchemaError::InvalidSchema { errors }) => Err(Error {
+                        source_info: schema_source_info,
+                        inner: RunnerError::InvalidXmlSchema {
+                            errors: errors.iter().map(|e| format!("line {}: {}", e.line, e.message)).collect(),
+                        },
+                        assert: false,
+                        call_site: None,
+                    }),
+                }
+            }
         }
     }
 }
@@ -437,6 +511,68 @@ fn assert_values_equal(actual: Value, expected: Value) -> Result<AssertResult, E
 }
 
 
+// a string (such as the body) is used as a json document if it can be parsed, as a json string otherwise
+fn json_instance(value: &Value) -> Option<serde_json::Value> {
+    match value {
+        Value::Bytes(_) | Value::Nodeset(_) => None,
+        Value::String(s) => Some(serde_json::from_str(s).unwrap_or_else(|_| serde_json::Value::String(s.clone()))),
+        _ => Some(value.to_json()),
+    }
+}
+
+fn assert_json_schema(value: Value, schema: &serde_json::Value, expected: String) -> Result<AssertResult, Error> {
+    let instance = match json_instance(&value) {
+        Some(instance) => instance,
+        None => return Ok(AssertResult {
+            success: false,
+            actual: value.display(),
+            expected,
+            type_mismatch: true,
+        }),
+    };
+    let violations = jsonschema::validate(schema, &instance);
+    let actual = match violations.len() {
+        0 => "valid json".to_string(),
+        1 => "1 schema violation".to_string(),
+        n => format!("{} schema violations", n),
+    };
+    let details: String = violations.iter()
+        .map(|v| format!("\n    {}: {}", if v.pointer.is_empty() { "(root)" } else { v.pointer.as_str() }, v.message))
+        .collect();
+    Ok(AssertResult {
+        success: violations.is_empty(),
+        actual: format!("{}{}", actual, details),
+        expected,
+        type_mismatch: false,
+    })
+}
+
+fn assert_json_equal(value: Value, expected_value: crate::core::json::Value, variables: &HashMap<String, Value>) -> Result<AssertResult, Error> {
+    let expected = format!("json equals to <{}>", expected_value.clone().display(variables)?);
+    let instance = match json_instance(&value) {
+        Some(instance) => instance,
+        None => return Ok(AssertResult {
+            success: false,
+            actual: value.display(),
+            expected,
+            type_mismatch: true,
+        }),
+    };
+    let diffs = expected_value.diff(&instance, variables, &[])?;
+    let actual = match diffs.len() {
+        0 => format!("json <{}>", instance),
+        1 => "1 difference".to_string(),
+        n => format!("{} differences", n),
+    };
+    let details: String = diffs.iter().flat_map(|d| d.lines()).map(|line| format!("\n    {}", line)).collect();
+    Ok(AssertResult {
+        success: diffs.is_empty(),
+        actual: format!("{}{}", actual, details),
+        expected,
+        type_mismatch: false,
+    })
+}
+
 fn assert_include(value: Value, element: Value) -> Result<AssertResult, Error> {
    let expected = format!("includes {}", element.clone().display());
     match value.clone() {
@@ -456,6 +592,15 @@ fn assert_include(value: Value, element: Value) -> Result<AssertResult, Error> {
                 type_mismatch: false,
             })
         }
+        Value::Nodeset(values) => {
+            let success = values.iter().any(|v| Value::String(v.clone()) == element);
+            Ok(AssertResult {
+                success,
+                actual: value.display(),
+                expected,
+                type_mismatch: false,
+            })
+        }
         _ => Ok(AssertResult {
             success: false,
             actual: value.display(),
@@ -499,7 +644,7 @@ mod tests {
             },
         };
 
-        let error = predicate.clone().eval(&variables, Some(Value::Bool(true))).err().unwrap();
+        let error = predicate.clone().eval(&variables, Some(Value::Bool(true)), "current_dir".to_string()).err().unwrap();
         assert_eq!(error.inner, RunnerError::AssertFailure {
             actual: "bool <true>".to_string(),
             expected: "int <10>".to_string(),
@@ -508,7 +653,7 @@ mod tests {
         assert_eq!(error.source_info, SourceInfo::init(1, 0, 1, 0));
 
 
-        let error = predicate.clone().eval(&variables, Some(Value::Integer(10))).err().unwrap();
+        let error = predicate.clone().eval(&variables, Some(Value::Integer(10)), "current_dir".to_string()).err().unwrap();
         assert_eq!(error.inner, RunnerError::AssertFailure {
             actual: "int <10>".to_string(),
             expected: "not int <10>".to_string(),
@@ -516,7 +661,7 @@ mod tests {
         });
         assert_eq!(error.source_info, SourceInfo::init(1, 0, 1, 0));
 
-        assert_eq!(predicate.eval(&variables, Some(Value::Integer(1))).unwrap(), ());
+        assert_eq!(predicate.eval(&variables, Some(Value::Integer(1)), "current_dir".to_string()).unwrap(), ());
     }
 
     #[test]
@@ -529,7 +674,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualInt { space0: whitespace, value: 10 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval(&variables, Some(Value::Bool(true))).unwrap();
+        }.eval(&variables, Some(Value::Bool(true)), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, true);
         assert_eq!(assert_result.actual.as_str(), "bool <true>");
@@ -547,7 +692,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 10 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Integer(1)).unwrap();
+        }.eval_something(&variables, Value::Integer(1), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "int <1>");
@@ -556,7 +701,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualBool { space0: whitespace.clone(), value: true },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Bool(false)).unwrap();
+        }.eval_something(&variables, Value::Bool(false), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "bool <false>");
@@ -565,7 +710,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualFloat { space0: whitespace, value: Float { int: 1, decimal: 200_000_000_000_000_000, decimal_digits: 0 } },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Float(1, 1)).unwrap();
+        }.eval_something(&variables, Value::Float(1, 1), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "float <1.1>");
@@ -583,7 +728,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualInt { space0: whitespace.clone(), value: 1 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Integer(1)).unwrap();
+        }.eval_something(&variables, Value::Integer(1), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "int <1>");
@@ -592,7 +737,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualBool { space0: whitespace.clone(), value: false },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Bool(false)).unwrap();
+        }.eval_something(&variables, Value::Bool(false), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "bool <false>");
@@ -602,7 +747,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualFloat { space0: whitespace.clone(), value: Float { int: 1, decimal: 1, decimal_digits: 1 } },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Float(1, 1)).unwrap();
+        }.eval_something(&variables, Value::Float(1, 1), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "float <1.1>");
@@ -613,7 +758,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualInt { space0: whitespace, value: 1 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Float(1, 0)).unwrap();
+        }.eval_something(&variables, Value::Float(1, 0), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "float <1.0>");
@@ -634,7 +779,8 @@ mod tests {
             elements: vec![
                 TemplateElement::Expression(Expr {
                     space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 11, 1, 11) },
-                    variable: Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 11, 1, 19) },
+                    value: ExprValue::Variable(Variable { name: String::from("base_url"), source_info: SourceInfo::init(1, 11, 1, 19) }),
+                    filters: vec![],
                     space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 19, 1, 19) },
                 })],
             source_info: SourceInfo::init(1, 1, 1, 1),
@@ -643,7 +789,7 @@ mod tests {
         let error = PredicateFunc {
             value: PredicateFuncValue::EqualString { space0: whitespace.clone(), value: template.clone() },
             source_info: SourceInfo::init(1, 1, 1, 21),
-        }.eval_something(&variables, Value::String(String::from("http://localhost:8000"))).err().unwrap();
+        }.eval_something(&variables, Value::String(String::from("http://localhost:8000")), "current_dir".to_string()).err().unwrap();
         assert_eq!(error.inner, RunnerError::TemplateVariableNotDefined { name: String::from("base_url") });
         assert_eq!(error.source_info, SourceInfo::init(1, 11, 1, 19));
 
@@ -651,7 +797,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::EqualString { space0: whitespace, value: template },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::String(String::from("http://localhost:8000"))).unwrap();
+        }.eval_something(&variables, Value::String(String::from("http://localhost:8000")), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "string <http://localhost:8000>");
@@ -669,7 +815,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 10 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Bool(true)).unwrap();
+        }.eval_something(&variables, Value::Bool(true), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, true);
         assert_eq!(assert_result.actual.as_str(), "bool <true>");
@@ -678,7 +824,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::List(vec![])).unwrap();
+        }.eval_something(&variables, Value::List(vec![]), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "[]");
@@ -687,7 +833,7 @@ mod tests {
         let assert_result = PredicateFunc {
             source_info: SourceInfo::init(0, 0, 0, 0),
             value: PredicateFuncValue::CountEqual { space0: whitespace, value: 1 },
-        }.eval_something(&variables, Value::Nodeset(3)).unwrap();
+        }.eval_something(&variables, Value::Nodeset(vec![String::from("a"), String::from("b"), String::from("c")]), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, false);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "nodeset of size <3>");
@@ -704,7 +850,7 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::CountEqual { space0: whitespace.clone(), value: 1 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::List(vec![Value::Integer(1)])).unwrap();
+        }.eval_something(&variables, Value::List(vec![Value::Integer(1)]), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "[int <1>]");
@@ -714,11 +860,101 @@ mod tests {
         let assert_result = PredicateFunc {
             value: PredicateFuncValue::CountEqual { space0: whitespace, value: 1 },
             source_info: SourceInfo::init(0, 0, 0, 0),
-        }.eval_something(&variables, Value::Nodeset(1)).unwrap();
+        }.eval_something(&variables, Value::Nodeset(vec![String::from("a")]), "current_dir".to_string()).unwrap();
         assert_eq!(assert_result.success, true);
         assert_eq!(assert_result.type_mismatch, false);
         assert_eq!(assert_result.actual.as_str(), "nodeset of size <1>");
         assert_eq!(assert_result.expected.as_str(), "count equals to <1>");
     }
-}
 
+    #[test]
+    fn test_predicate_match() {
+        let variables = HashMap::new();
+        let whitespace = Whitespace {
+            value: String::from(" "),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let value = RegexValue::Literal(RegexLiteral {
+            value: "^hello".to_string(),
+            flags: "i".to_string(),
+            inner: regex::RegexBuilder::new("^hello").case_insensitive(true).build().unwrap(),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        });
+
+        let assert_result = PredicateFunc {
+            value: PredicateFuncValue::Match { space0: whitespace.clone(), value: value.clone() },
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        }.eval_something(&variables, Value::String(String::from("Hello World!")), "current_dir".to_string()).unwrap();
+        assert_eq!(assert_result.success, true);
+        assert_eq!(assert_result.type_mismatch, false);
+        assert_eq!(assert_result.expected.as_str(), "matches regex <^hello>");
+
+        let assert_result = PredicateFunc {
+            value: PredicateFuncValue::Match { space0: whitespace, value },
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        }.eval_something(&variables, Value::String(String::from("Bye!")), "current_dir".to_string()).unwrap();
+        assert_eq!(assert_result.success, false);
+        assert_eq!(assert_result.type_mismatch, false);
+    }
+
+    #[test]
+    fn test_include_nodeset() {
+        let nodeset = Value::Nodeset(vec![String::from("Bob"), String::from("Bill")]);
+        assert_eq!(assert_include(nodeset.clone(), Value::String(String::from("Bill"))).unwrap().success, true);
+        assert_eq!(assert_include(nodeset, Value::String(String::from("Bruce"))).unwrap().success, false);
+    }
+
+    #[test]
+    fn test_assert_json_schema() {
+        let schema = serde_json::json!({"type": "object", "required": ["id"], "properties": {"id": {"type": "integer"}}});
+        let expected = String::from("valid against json schema <user.schema.json>");
+
+        let assert_result = assert_json_schema(Value::String(String::from("{\"id\": 1}")), &schema, expected.clone()).unwrap();
+        assert_eq!(assert_result.success, true);
+        assert_eq!(assert_result.actual.as_str(), "valid json");
+
+        let assert_result = assert_json_schema(Value::Object(vec![(String::from("id"), Value::String(String::from("1")))]), &schema, expected.clone()).unwrap();
+        assert_eq!(assert_result.success, false);
+        assert_eq!(assert_result.type_mismatch, false);
+        assert_eq!(assert_result.actual.as_str(), "1 schema violation\n    /id: expected type integer but found string");
+
+        let assert_result = assert_json_schema(Value::String(String::from("Hello")), &schema, expected.clone()).unwrap();
+        assert_eq!(assert_result.actual.as_str(), "1 schema violation\n    (root): expected type object but found string");
+
+        let assert_result = assert_json_schema(Value::Bytes(vec![]), &schema, expected).unwrap();
+        assert_eq!(assert_result.type_mismatch, true);
+    }
+
+    #[test]
+    fn test_assert_json_equal() {
+        let variables = HashMap::new();
+        let expected = crate::core::json::tests::person_value();
+
+        let assert_result = assert_json_equal(Value::String(String::from("{ \"firstName\":\"John\" }")), expected.clone(), &variables).unwrap();
+        assert_eq!(assert_result.success, true);
+        assert_eq!(assert_result.expected.as_str(), "json equals to <{\"firstName\":\"John\"}>");
+
+        let assert_result = assert_json_equal(Value::Object(vec![(String::from("firstName"), Value::String(String::from("Bob")))]), expected.clone(), &variables).unwrap();
+        assert_eq!(assert_result.success, false);
+        assert_eq!(assert_result.actual.as_str(), "1 difference\n    - $.firstName: \"John\"\n    + $.firstName: \"Bob\"");
+
+        let assert_result = assert_json_equal(Value::Bytes(vec![]), expected, &variables).unwrap();
+        assert_eq!(assert_result.type_mismatch, true);
+    }
+
+    #[test]
+    fn test_predicate_json_schema_file_error() {
+        let variables = HashMap::new();
+        let error = PredicateFunc {
+            value: PredicateFuncValue::JsonSchema {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 11, 1, 12) },
+                space1: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 17, 1, 17) },
+                filename: Filename { value: String::from("user.schema.json"), source_info: SourceInfo::init(1, 17, 1, 33) },
+                space2: Whitespace { value: String::from(""), source_info: SourceInfo::init(1, 33, 1, 33) },
+            },
+            source_info: SourceInfo::init(1, 1, 1, 34),
+        }.eval_something(&variables, Value::Null, "current_dir".to_string()).err().unwrap();
+        assert_eq!(error.inner, RunnerError::FileReadAccess { value: String::from("current_dir/user.schema.json") });
+        assert_eq!(error.source_info, SourceInfo::init(1, 17, 1, 33));
+    }
+}
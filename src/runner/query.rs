@@ -19,7 +19,8 @@ use std::collections::HashMap;
 
 use encoding::{DecoderTrap, Encoding};
 use encoding::all::ISO_8859_1;
-use regex::Regex;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+use regex::{Captures, Regex};
 
 #[cfg(test)]
 use crate::core::common::{Pos, SourceInfo};
@@ -32,6 +33,7 @@ use crate::jsonpath;
 use super::core::{Error, RunnerError};
 //use super::http;
 use super::super::core::ast::*;
+use super::css;
 use super::xpath;
 
 // QueryResult
@@ -111,7 +113,7 @@ impl http::response::Response {
 }
 
 impl Query {
-    pub fn eval(self, variables: &HashMap<String, Value>, http_response: http::response::Response) -> QueryResult {
+    pub fn eval(self, variables: &HashMap<String, Value>, http_response: http::response::Response, namespaces: &HashMap<String, String>) -> QueryResult {
         match self.value {
             QueryValue::Status {} => Ok(Some(Value::Integer(i64::from(http_response.status)))),
             QueryValue::Header { name, .. } => {
@@ -145,19 +147,19 @@ impl Query {
                 // can return a string if encoding is known and utf8
                 match http_response.text() {
                     Ok(s) => Ok(Some(Value::String(s))),
-                    Err(inner) => Err(Error { source_info: self.source_info.clone(), inner, assert: false }),
+                    Err(inner) => Err(Error { source_info: self.source_info.clone(), inner, assert: false, call_site: None }),
                 }
             }
             QueryValue::Xpath { expr, .. } => {
                 let source_info = expr.source_info.clone();
                 let value = expr.eval(variables)?;
                 match http_response.text() {
-                    Err(inner) => Err(Error { source_info: self.source_info.clone(), inner, assert: false }),
+                    Err(inner) => Err(Error { source_info: self.source_info.clone(), inner, assert: false, call_site: None }),
                     Ok(xml) => {
                         let result = if http_response.is_html() {
-                            xpath::eval_html(xml, value.clone())
+                            xpath::eval_html(xml, value.clone(), namespaces)
                         } else {
-                            xpath::eval_xml(xml, value.clone())
+                            xpath::eval_xml(xml, value.clone(), namespaces)
                         };
                         match result {
                             Ok(value) => Ok(Some(value)),
@@ -166,17 +168,20 @@ impl Query {
                                 inner: RunnerError::QueryInvalidXml
                                 ,
                                 assert: false,
+                                call_site: None,
                             }),
                             Err(xpath::XpathError::InvalidHtml {}) => Err(Error {
                                 source_info: self.source_info,
                                 inner: RunnerError::QueryInvalidXml
                                 ,
                                 assert: false,
+                                call_site: None,
                             }),
                             Err(xpath::XpathError::Eval {}) => Err(Error {
                                 source_info,
                                 inner: RunnerError::QueryInvalidXpathEval,
                                 assert: false,
+                                call_site: None,
                             }),
                             Err(xpath::XpathError::Unsupported {}) => {
                                 panic!("Unsupported xpath {}", value); // good usecase for panic - I could nmot reporduce this usecase myself
@@ -185,6 +190,37 @@ impl Query {
                     }
                 }
             }
+            QueryValue::Css { expr, .. } => {
+                let source_info = expr.source_info.clone();
+                let value = expr.eval(variables)?;
+                let xpath_expr = match css::to_xpath(value.as_str()) {
+                    Ok(xpath_expr) => xpath_expr,
+                    Err(css::CssError { pos, message }) => return Err(Error {
+                        source_info,
+                        inner: RunnerError::QueryInvalidCssSelector { message: format!("{} at offset {}", message, pos) },
+                        assert: false,
+                        call_site: None,
+                    }),
+                };
+                match http_response.text() {
+                    Err(inner) => Err(Error { source_info: self.source_info.clone(), inner, assert: false, call_site: None }),
+                    Ok(html) => match xpath::eval_html(html, xpath_expr, namespaces) {
+                        Ok(value) => Ok(Some(value)),
+                        Err(xpath::XpathError::InvalidHtml {}) | Err(xpath::XpathError::InvalidXML {}) => Err(Error {
+                            source_info: self.source_info,
+                            inner: RunnerError::QueryInvalidXml,
+                            assert: false,
+                            call_site: None,
+                        }),
+                        Err(_) => Err(Error {
+                            source_info,
+                            inner: RunnerError::QueryInvalidCssSelector { message: String::from("the selector can not be evaluated") },
+                            assert: false,
+                            call_site: None,
+                        }),
+                    }
+                }
+            }
             QueryValue::Jsonpath { expr, .. } => {
                 let value = expr.clone().eval(variables)?;
                 let source_info = expr.source_info;
@@ -204,51 +240,24 @@ impl Query {
 //                        if value == Value::List(vec![]) { Value::None } else { value }
 //                    }
 //                };
-                // Using your own json implem
-                let query = match jsonpath::parser::parse::parse(value.as_str()) {
-                    Ok(q) => q,
-                    Err(_) => return Err(Error { source_info, inner: RunnerError::QueryInvalidJsonpathExpression { value }, assert: false }),
-                };
                 let json = match http_response.text() {
-                    Err(inner) => return Err(Error { source_info: self.source_info.clone(), inner, assert: false }),
+                    Err(inner) => return Err(Error { source_info: self.source_info.clone(), inner, assert: false, call_site: None }),
                     Ok(v) => v,
                 };
-                let value = match serde_json::from_str(json.as_str()) {
-                    Err(_) => {
-                        return Err(Error { source_info: self.source_info, inner: RunnerError::QueryInvalidJson, assert: false });
-                    }
-                    Ok(v) => v
-                };
-                let results = query.eval(value);
-                if results.is_empty() {
-                    Ok(None)
-                } else if results.len() == 1 { // list coercions
-                    Ok(Some(Value::from_json(results.get(0).unwrap())))
-                } else {
-                    Ok(Some(Value::from_json(&serde_json::Value::Array(results))))
+                match eval_jsonpath(json, value) {
+                    Err(JsonpathError::InvalidExpression { value }) => Err(Error { source_info, inner: RunnerError::QueryInvalidJsonpathExpression { value }, assert: false, call_site: None }),
+                    Err(JsonpathError::InvalidJson) => Err(Error { source_info: self.source_info, inner: RunnerError::QueryInvalidJson, assert: false, call_site: None }),
+                    Ok(value) => Ok(value),
                 }
             }
             QueryValue::Regex { expr, .. } => {
-                let value = expr.clone().eval(variables)?;
-                let source_info = expr.source_info;
+                let global = expr.is_global();
+                let re = expr.eval(variables)?;
                 let s = match http_response.text() {
-                    Err(inner) => return Err(Error { source_info: self.source_info.clone(), inner, assert: false }),
+                    Err(inner) => return Err(Error { source_info: self.source_info.clone(), inner, assert: false, call_site: None }),
                     Ok(v) => v,
                 };
-                match Regex::new(value.as_str()) {
-                    Ok(re) => match re.captures(s.as_str()) {
-                        Some(captures) => match captures.get(1) {
-                            Some(v) => Ok(Some(Value::String(v.as_str().to_string()))),
-                            None => Ok(None),
-                        }
-                        None => Ok(None),
-                    }
-                    Err(_) => Err(Error {
-                        source_info,
-                        inner: RunnerError::InvalidRegex(),
-                        assert: false,
-                    })
-                }
+                Ok(eval_regex(&re, global, s.as_str()))
             }
             QueryValue::Variable { name, .. } => {
                 let name = name.eval(variables)?;
@@ -278,6 +287,254 @@ impl CookieAttributeName {
 }
 
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum JsonpathError {
+    InvalidExpression { value: String },
+    InvalidJson,
+}
+
+// shared by the jsonpath query and the jsonpath filter
+fn eval_jsonpath(json: String, expr: String) -> Result<Option<Value>, JsonpathError> {
+    let query = match jsonpath::parser::parse::parse(expr.as_str()) {
+        Ok(q) => q,
+        Err(_) => return Err(JsonpathError::InvalidExpression { value: expr }),
+    };
+    let value = match serde_json::from_str(json.as_str()) {
+        Err(_) => return Err(JsonpathError::InvalidJson),
+        Ok(v) => v
+    };
+    let results = query.eval(value);
+    if results.is_empty() {
+        Ok(None)
+    } else if results.len() == 1 { // list coercions
+        Ok(Some(Value::from_json(results.first().unwrap())))
+    } else {
+        Ok(Some(Value::from_json(&serde_json::Value::Array(results))))
+    }
+}
+
+// same as encodeURIComponent in javascript
+pub const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'!')
+    .remove(b'~')
+    .remove(b'*')
+    .remove(b'\'')
+    .remove(b'(')
+    .remove(b')');
+
+impl Filter {
+    pub fn eval(self, variables: &HashMap<String, Value>, value: Value) -> QueryResult {
+        let source_info = self.source_info.clone();
+        let invalid_input = |value: &Value| Error {
+            source_info: source_info.clone(),
+            inner: RunnerError::FilterInvalidInput { actual: value._type() },
+            assert: false,
+            call_site: None,
+        };
+        match self.value {
+            FilterValue::Count {} => match value {
+                Value::List(values) => Ok(Some(Value::Integer(values.len() as i64))),
+                Value::Nodeset(values) => Ok(Some(Value::Integer(values.len() as i64))),
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Nth { n, .. } => match value {
+                Value::List(values) => Ok(values.get(n as usize).cloned()),
+                Value::Nodeset(values) => Ok(values.get(n as usize).map(|v| Value::String(v.clone()))),
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Split { sep, .. } => match value {
+                Value::String(s) => {
+                    let sep = sep.eval(variables)?;
+                    let values = s.split(sep.as_str()).map(|v| Value::String(v.to_string())).collect();
+                    Ok(Some(Value::List(values)))
+                }
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Replace { old_value, new_value, .. } => match value {
+                Value::String(s) => {
+                    let old_value = old_value.eval(variables)?;
+                    let new_value = new_value.eval(variables)?;
+                    Ok(Some(Value::String(s.replace(old_value.as_str(), new_value.as_str()))))
+                }
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::UrlDecode {} => match value {
+                Value::String(s) => match percent_decode_str(s.as_str()).decode_utf8() {
+                    Ok(decoded) => Ok(Some(Value::String(decoded.to_string()))),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterDecode { encoding: "url".to_string() },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::UrlEncode {} => match value {
+                Value::String(s) => Ok(Some(Value::String(utf8_percent_encode(s.as_str(), URL_COMPONENT).to_string()))),
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Base64Decode {} => match value {
+                Value::String(s) => match base64::decode(s.as_str()) {
+                    Ok(bytes) => Ok(Some(Value::Bytes(bytes))),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterDecode { encoding: "base64".to_string() },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::ToInt {} => match value {
+                Value::Integer(i) => Ok(Some(Value::Integer(i))),
+                Value::Float(int, _) => Ok(Some(Value::Integer(int))),
+                Value::String(s) => match s.trim().parse::<i64>() {
+                    Ok(i) => Ok(Some(Value::Integer(i))),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterInvalidInput { actual: s },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::ToFloat {} => match value {
+                Value::Integer(i) => Ok(Some(Value::Float(i, 0))),
+                Value::Float(int, decimal) => Ok(Some(Value::Float(int, decimal))),
+                Value::String(s) => match s.trim().parse::<f64>() {
+                    Ok(f) => Ok(Some(Value::from_f64(f))),
+                    Err(_) => Err(Error {
+                        source_info,
+                        inner: RunnerError::FilterInvalidInput { actual: s },
+                        assert: false,
+                        call_site: None,
+                    }),
+                },
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::HtmlUnescape {} => match value {
+                Value::String(s) => Ok(Some(Value::String(html_unescape(s.as_str())))),
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Jsonpath { expr, .. } => match value {
+                Value::String(s) => {
+                    let expr_source_info = expr.source_info.clone();
+                    let expr = expr.eval(variables)?;
+                    match eval_jsonpath(s, expr) {
+                        Err(JsonpathError::InvalidExpression { value }) => Err(Error {
+                            source_info: expr_source_info,
+                            inner: RunnerError::QueryInvalidJsonpathExpression { value },
+                            assert: false,
+                            call_site: None,
+                        }),
+                        Err(JsonpathError::InvalidJson) => Err(Error {
+                            source_info,
+                            inner: RunnerError::QueryInvalidJson,
+                            assert: false,
+                            call_site: None,
+                        }),
+                        Ok(value) => Ok(value),
+                    }
+                }
+                v => Err(invalid_input(&v)),
+            },
+            FilterValue::Regex { expr, .. } => match value {
+                Value::String(s) => {
+                    let global = expr.is_global();
+                    let re = expr.eval(variables)?;
+                    Ok(eval_regex(&re, global, s.as_str()))
+                }
+                v => Err(invalid_input(&v)),
+            },
+        }
+    }
+}
+
+// a match returns
+// - an object when the regex has named groups
+// - the first group when the regex has unnamed groups
+// - the whole match otherwise
+// a global regex returns the list of all the matches
+fn eval_regex(re: &Regex, global: bool, s: &str) -> Option<Value> {
+    if global {
+        let values = re.captures_iter(s).filter_map(|captures| captures_value(re, &captures)).collect();
+        Some(Value::List(values))
+    } else {
+        match re.captures(s) {
+            None => None,
+            Some(captures) => captures_value(re, &captures),
+        }
+    }
+}
+
+fn captures_value(re: &Regex, captures: &Captures) -> Option<Value> {
+    let names: Vec<&str> = re.capture_names().flatten().collect();
+    if !names.is_empty() {
+        let elements = names.iter().map(|name| {
+            let value = match captures.name(name) {
+                None => Value::Null,
+                Some(m) => Value::String(m.as_str().to_string()),
+            };
+            (name.to_string(), value)
+        }).collect();
+        Some(Value::Object(elements))
+    } else if re.captures_len() > 1 {
+        captures.get(1).map(|m| Value::String(m.as_str().to_string()))
+    } else {
+        captures.get(0).map(|m| Value::String(m.as_str().to_string()))
+    }
+}
+
+// unescape named and numeric character references
+fn html_unescape(s: &str) -> String {
+    let mut buffer = String::new();
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        buffer.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = match rest.find(';') {
+            None => None,
+            Some(end) => {
+                let reference = &rest[1..end];
+                let c = match reference {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some('\u{a0}'),
+                    _ if reference.starts_with("#x") || reference.starts_with("#X") => {
+                        u32::from_str_radix(&reference[2..], 16).ok().and_then(std::char::from_u32)
+                    }
+                    _ if reference.starts_with('#') => {
+                        reference[1..].parse::<u32>().ok().and_then(std::char::from_u32)
+                    }
+                    _ => None,
+                };
+                c.map(|c| (c, end))
+            }
+        };
+        match decoded {
+            Some((c, end)) => {
+                buffer.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                buffer.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    buffer.push_str(rest);
+    buffer
+}
+
+
 #[cfg(test)]
 pub mod tests {
     use crate::http::cookie::ResponseCookie;
@@ -452,7 +709,7 @@ pub mod tests {
                     value: String::from(""),
                     source_info: SourceInfo::init(1, 6, 1, 7),
                 },
-                expr: Template {
+                expr: RegexValue::Template(Template {
                     quotes: true,
                     elements: vec![
                         TemplateElement::String {
@@ -461,7 +718,7 @@ pub mod tests {
                         }
                     ],
                     source_info: SourceInfo::init(1, 7, 1, 26),
-                },
+                }),
             },
         }
     }
@@ -475,7 +732,7 @@ pub mod tests {
                     value: String::from(""),
                     source_info: SourceInfo::init(1, 6, 1, 7),
                 },
-                expr: Template {
+                expr: RegexValue::Template(Template {
                     quotes: true,
                     elements: vec![
                         TemplateElement::String {
@@ -484,7 +741,7 @@ pub mod tests {
                         }
                     ],
                     source_info: SourceInfo::init(1, 7, 1, 10),
-                },
+                }),
             },
         }
     }
@@ -493,7 +750,7 @@ pub mod tests {
     fn test_query_status() {
         let variables = HashMap::new();
         assert_eq!(
-            Query { source_info: SourceInfo::init(0, 0, 0, 0), value: QueryValue::Status {} }.eval(&variables, http::response::tests::hello_http_response()).unwrap().unwrap(),
+            Query { source_info: SourceInfo::init(0, 0, 0, 0), value: QueryValue::Status {} }.eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::Integer(200)
         );
     }
@@ -521,7 +778,7 @@ pub mod tests {
 //    let error = query_header.eval(http::hello_http_response()).err().unwrap();
 //    assert_eq!(error.source_info.start, Pos { line: 1, column: 8 });
 //    assert_eq!(error.inner, RunnerError::QueryHeaderNotFound);
-        assert_eq!(query_header.eval(&variables, http::response::tests::hello_http_response()).unwrap(), None);
+        assert_eq!(query_header.eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).unwrap(), None);
     }
 
     #[test]
@@ -545,7 +802,7 @@ pub mod tests {
             },
         };
         assert_eq!(
-            query_header.eval(&variables, http::response::tests::hello_http_response()).unwrap().unwrap(),
+            query_header.eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::String(String::from("text/html; charset=utf-8"))
         );
     }
@@ -586,7 +843,7 @@ pub mod tests {
                 },
             },
         };
-        assert_eq!(query.eval(&variables, response.clone()).unwrap().unwrap(), Value::String("DQAAAKEaem_vYg".to_string()));
+        assert_eq!(query.eval(&variables, response.clone(), &HashMap::new()).unwrap().unwrap(), Value::String("DQAAAKEaem_vYg".to_string()));
 
 
         // cookie "LSID[Path]"
@@ -611,7 +868,7 @@ pub mod tests {
                 },
             },
         };
-        assert_eq!(query.eval(&variables, response.clone()).unwrap().unwrap(), Value::String("/accounts".to_string()));
+        assert_eq!(query.eval(&variables, response.clone(), &HashMap::new()).unwrap().unwrap(), Value::String("/accounts".to_string()));
 
 
         // cookie "LSID[Secure]"
@@ -638,7 +895,7 @@ pub mod tests {
                 },
             },
         };
-        assert_eq!(query.eval(&variables, response.clone()).unwrap().unwrap(), Value::Bool(true));
+        assert_eq!(query.eval(&variables, response.clone(), &HashMap::new()).unwrap().unwrap(), Value::Bool(true));
 
 
 // cookie "LSID[Domain]"
@@ -662,7 +919,7 @@ pub mod tests {
                 },
             },
         };
-        assert_eq!(query.eval(&variables, response).unwrap(), None);
+        assert_eq!(query.eval(&variables, response, &HashMap::new()).unwrap(), None);
     }
 
     #[test]
@@ -697,13 +954,13 @@ pub mod tests {
             Query {
                 source_info: SourceInfo::init(0, 0, 0, 0),
                 value: QueryValue::Body {},
-            }.eval(&variables, http::response::tests::hello_http_response()).unwrap().unwrap(),
+            }.eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::String(String::from("Hello World!"))
         );
         let error = Query {
             source_info: SourceInfo::init(1, 1, 1, 2),
             value: QueryValue::Body {},
-        }.eval(&variables, http::response::tests::bytes_http_response()).err().unwrap();
+        }.eval(&variables, http::response::tests::bytes_http_response(), &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 2));
         assert_eq!(error.inner, RunnerError::InvalidUtf8 {});
     }
@@ -717,7 +974,7 @@ pub mod tests {
             headers: vec![],
             body: vec![200],
         };
-        let error = xpath_users().eval(&variables, http_response).err().unwrap();
+        let error = xpath_users().eval(&variables, http_response, &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info.start, Pos { line: 1, column: 1 });
         assert_eq!(error.inner, RunnerError::InvalidUtf8);
     }
@@ -745,7 +1002,7 @@ pub mod tests {
                 },
             },
         };
-        let error = query.eval(&variables, http::response::tests::xml_two_users_http_response()).err().unwrap();
+        let error = query.eval(&variables, http::response::tests::xml_two_users_http_response(), &HashMap::new()).err().unwrap();
         assert_eq!(error.inner, RunnerError::QueryInvalidXpathEval);
         assert_eq!(error.source_info.start, Pos { line: 1, column: 7 });
     }
@@ -754,8 +1011,8 @@ pub mod tests {
     fn test_query_xpath() {
         let variables = HashMap::new();
 
-        assert_eq!(xpath_users().eval(&variables, http::response::tests::xml_two_users_http_response()).unwrap().unwrap(), Value::Nodeset(2));
-        assert_eq!(xpath_count_user_query().eval(&variables, http::response::tests::xml_two_users_http_response()).unwrap().unwrap(), Value::Float(2, 0));
+        assert_eq!(xpath_users().eval(&variables, http::response::tests::xml_two_users_http_response(), &HashMap::new()).unwrap().unwrap(), Value::Nodeset(vec![String::from("Bob"), String::from("Bill")]));
+        assert_eq!(xpath_count_user_query().eval(&variables, http::response::tests::xml_two_users_http_response(), &HashMap::new()).unwrap().unwrap(), Value::Float(2, 0));
     }
 
     #[cfg(test)]
@@ -786,7 +1043,37 @@ pub mod tests {
     #[test]
     fn test_query_xpath_with_html() {
         let variables = HashMap::new();
-        assert_eq!(xpath_html_charset().eval(&variables, http::response::tests::html_http_response()).unwrap().unwrap(), Value::String(String::from("UTF-8")));
+        assert_eq!(xpath_html_charset().eval(&variables, http::response::tests::html_http_response(), &HashMap::new()).unwrap().unwrap(), Value::String(String::from("UTF-8")));
+    }
+
+    #[cfg(test)]
+    pub fn css_query(selector: &str) -> Query {
+        Query {
+            source_info: SourceInfo::init(1, 1, 1, 5),
+            value: QueryValue::Css {
+                space0: Whitespace { value: String::from(" "), source_info: SourceInfo::init(1, 4, 1, 5) },
+                expr: Template {
+                    quotes: true,
+                    elements: vec![
+                        TemplateElement::String { value: selector.to_string(), encoded: selector.to_string() }
+                    ],
+                    source_info: SourceInfo::init(1, 5, 1, 7 + selector.len()),
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_query_css() {
+        let variables = HashMap::new();
+        assert_eq!(css_query("head > meta::attr(charset)").eval(&variables, http::response::tests::html_http_response(), &HashMap::new()).unwrap().unwrap(),
+                   Value::Nodeset(vec![String::from("UTF-8")]));
+        assert_eq!(css_query("body br").eval(&variables, http::response::tests::html_http_response(), &HashMap::new()).unwrap().unwrap(),
+                   Value::Nodeset(vec![String::from("")]));
+
+        let error = css_query("meta[charset").eval(&variables, http::response::tests::html_http_response(), &HashMap::new()).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 5, 1, 19));
+        assert_eq!(error.inner, RunnerError::QueryInvalidCssSelector { message: String::from("expecting an attribute operator at offset 12") });
     }
 
     #[test]
@@ -815,7 +1102,7 @@ pub mod tests {
             },
         };
 
-        let error = jsonpath_query.eval(&variables, json_http_response()).err().unwrap();
+        let error = jsonpath_query.eval(&variables, json_http_response(), &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info.start, Pos { line: 1, column: 10 });
         assert_eq!(error.inner, RunnerError::QueryInvalidJsonpathExpression { value: "xxx".to_string() });
     }
@@ -829,7 +1116,7 @@ pub mod tests {
             headers: vec![],
             body: String::into_bytes(String::from("xxx")),
         };
-        let error = jsonpath_success().eval(&variables, http_response).err().unwrap();
+        let error = jsonpath_success().eval(&variables, http_response, &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info.start, Pos { line: 1, column: 1 });
         assert_eq!(error.inner, RunnerError::QueryInvalidJson);
     }
@@ -844,18 +1131,18 @@ pub mod tests {
             body: String::into_bytes(String::from("{}")),
         };
         //assert_eq!(jsonpath_success().eval(http_response).unwrap(), Value::List(vec![]));
-        assert_eq!(jsonpath_success().eval(&variables, http_response).unwrap(), None);
+        assert_eq!(jsonpath_success().eval(&variables, http_response, &HashMap::new()).unwrap(), None);
     }
 
     #[test]
     fn test_query_json() {
         let variables = HashMap::new();
         assert_eq!(
-            jsonpath_success().eval(&variables, json_http_response()).unwrap().unwrap(),
+            jsonpath_success().eval(&variables, json_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::Bool(false)
         );
         assert_eq!(
-            jsonpath_errors().eval(&variables, json_http_response()).unwrap().unwrap(),
+            jsonpath_errors().eval(&variables, json_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::List(vec![
                 Value::Object(vec![(String::from("id"), Value::String(String::from("error1")))]),
                 Value::Object(vec![(String::from("id"), Value::String(String::from("error2")))])
@@ -867,12 +1154,159 @@ pub mod tests {
     fn test_query_regex() {
         let variables = HashMap::new();
         assert_eq!(
-            regex_name().eval(&variables, http::response::tests::hello_http_response()).unwrap().unwrap(),
+            regex_name().eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).unwrap().unwrap(),
             Value::String("World".to_string())
         );
 
-        let error = regex_invalid().eval(&variables, http::response::tests::hello_http_response()).err().unwrap();
+        let error = regex_invalid().eval(&variables, http::response::tests::hello_http_response(), &HashMap::new()).err().unwrap();
         assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 10));
         assert_eq!(error.inner, RunnerError::InvalidRegex());
     }
+
+    fn filter(value: FilterValue) -> Filter {
+        Filter {
+            source_info: SourceInfo::init(1, 1, 1, 20),
+            value,
+        }
+    }
+
+    fn template(s: &str, source_info: SourceInfo) -> Template {
+        Template {
+            quotes: true,
+            elements: vec![
+                TemplateElement::String { value: s.to_string(), encoded: s.to_string() }
+            ],
+            source_info,
+        }
+    }
+
+    #[test]
+    fn test_filter_regex() {
+        // regex "Hello (.*)!"
+        let variables = HashMap::new();
+        let whitespace = Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) };
+        let regex = filter(FilterValue::Regex {
+            space0: whitespace.clone(),
+            expr: RegexValue::Template(template("Hello (.*)!", SourceInfo::init(1, 7, 1, 20))),
+        });
+        assert_eq!(regex.clone().eval(&variables, Value::String("Hello Bob!".to_string())).unwrap().unwrap(),
+                   Value::String("Bob".to_string())
+        );
+        let error = regex.eval(&variables, Value::Bool(true)).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 20));
+        assert_eq!(error.inner, RunnerError::FilterInvalidInput { actual: "boolean".to_string() });
+
+        let regex = filter(FilterValue::Regex {
+            space0: whitespace,
+            expr: RegexValue::Template(template("???", SourceInfo::init(1, 7, 1, 20))),
+        });
+        let error = regex.eval(&variables, Value::String("Hello Bob!".to_string())).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 7, 1, 20));
+        assert_eq!(error.inner, RunnerError::InvalidRegex {});
+    }
+
+    fn regex_literal(value: &str, flags: &str) -> RegexValue {
+        let mut builder = regex::RegexBuilder::new(value);
+        builder.case_insensitive(flags.contains('i'));
+        RegexValue::Literal(RegexLiteral {
+            value: value.to_string(),
+            flags: flags.to_string(),
+            inner: builder.build().unwrap(),
+            source_info: SourceInfo::init(1, 7, 1, 20),
+        })
+    }
+
+    #[test]
+    fn test_filter_regex_literal() {
+        let variables = HashMap::new();
+        let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+
+        // regex /(?P<first>\w+) (?P<last>\w+)?/
+        let regex = filter(FilterValue::Regex {
+            space0: whitespace.clone(),
+            expr: regex_literal(r"(?P<first>\w+) (?P<last>\w+)?", ""),
+        });
+        assert_eq!(regex.eval(&variables, Value::String("John Doe".to_string())).unwrap().unwrap(),
+                   Value::Object(vec![
+                       (String::from("first"), Value::String(String::from("John"))),
+                       (String::from("last"), Value::String(String::from("Doe"))),
+                   ])
+        );
+
+        // regex /id=(\d+)/gi
+        let regex = filter(FilterValue::Regex {
+            space0: whitespace.clone(),
+            expr: regex_literal(r"id=(\d+)", "gi"),
+        });
+        assert_eq!(regex.clone().eval(&variables, Value::String("id=1 ID=2 id=3".to_string())).unwrap().unwrap(),
+                   Value::List(vec![
+                       Value::String(String::from("1")),
+                       Value::String(String::from("2")),
+                       Value::String(String::from("3")),
+                   ])
+        );
+        assert_eq!(regex.eval(&variables, Value::String("none".to_string())).unwrap().unwrap(),
+                   Value::List(vec![])
+        );
+    }
+
+    #[test]
+    fn test_filter_count_nth() {
+        let variables = HashMap::new();
+        let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+        let values = Value::List(vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(filter(FilterValue::Count {}).eval(&variables, values.clone()).unwrap(), Some(Value::Integer(2)));
+        assert_eq!(filter(FilterValue::Count {}).eval(&variables, Value::Nodeset(vec![String::from("a"); 3])).unwrap(), Some(Value::Integer(3)));
+        assert_eq!(filter(FilterValue::Nth { space0: whitespace.clone(), n: 1 }).eval(&variables, values.clone()).unwrap(), Some(Value::Integer(2)));
+        assert_eq!(filter(FilterValue::Nth { space0: whitespace.clone(), n: 2 }).eval(&variables, values).unwrap(), None);
+        let nodeset = Value::Nodeset(vec![String::from("Bob"), String::from("Bill")]);
+        assert_eq!(filter(FilterValue::Nth { space0: whitespace, n: 1 }).eval(&variables, nodeset).unwrap(), Some(Value::String(String::from("Bill"))));
+    }
+
+    #[test]
+    fn test_filter_string() {
+        let variables = HashMap::new();
+        let whitespace = Whitespace { value: String::from(" "), source_info: SourceInfo::init(0, 0, 0, 0) };
+        let source_info = SourceInfo::init(0, 0, 0, 0);
+
+        let split = filter(FilterValue::Split { space0: whitespace.clone(), sep: template(",", source_info.clone()) });
+        assert_eq!(split.eval(&variables, Value::String("a,b".to_string())).unwrap(), Some(Value::List(vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string()),
+        ])));
+
+        let replace = filter(FilterValue::Replace {
+            space0: whitespace.clone(),
+            old_value: template("+", source_info.clone()),
+            space1: whitespace.clone(),
+            new_value: template(" ", source_info.clone()),
+        });
+        assert_eq!(replace.eval(&variables, Value::String("a+b+c".to_string())).unwrap(), Some(Value::String("a b c".to_string())));
+
+        assert_eq!(filter(FilterValue::UrlDecode {}).eval(&variables, Value::String("a%20b%3Dc".to_string())).unwrap(), Some(Value::String("a b=c".to_string())));
+        assert_eq!(filter(FilterValue::UrlEncode {}).eval(&variables, Value::String("a b=c".to_string())).unwrap(), Some(Value::String("a%20b%3Dc".to_string())));
+        assert_eq!(filter(FilterValue::Base64Decode {}).eval(&variables, Value::String("SGVsbG8=".to_string())).unwrap(), Some(Value::Bytes(b"Hello".to_vec())));
+        let error = filter(FilterValue::Base64Decode {}).eval(&variables, Value::String("???".to_string())).err().unwrap();
+        assert_eq!(error.inner, RunnerError::FilterDecode { encoding: "base64".to_string() });
+
+        assert_eq!(filter(FilterValue::HtmlUnescape {}).eval(&variables, Value::String("a &lt;b&gt; &amp; &#39;c&#x27; &unknown;".to_string())).unwrap(),
+                   Some(Value::String("a <b> & 'c' &unknown;".to_string())));
+
+        let jsonpath = filter(FilterValue::Jsonpath { space0: whitespace, expr: template("$.id", source_info) });
+        assert_eq!(jsonpath.clone().eval(&variables, Value::String("{\"id\": 123}".to_string())).unwrap(), Some(Value::Integer(123)));
+        let error = jsonpath.eval(&variables, Value::String("xxx".to_string())).err().unwrap();
+        assert_eq!(error.source_info, SourceInfo::init(1, 1, 1, 20));
+        assert_eq!(error.inner, RunnerError::QueryInvalidJson);
+    }
+
+    #[test]
+    fn test_filter_to_number() {
+        let variables = HashMap::new();
+        assert_eq!(filter(FilterValue::ToInt {}).eval(&variables, Value::String("123".to_string())).unwrap(), Some(Value::Integer(123)));
+        assert_eq!(filter(FilterValue::ToInt {}).eval(&variables, Value::from_f64(1.5)).unwrap(), Some(Value::Integer(1)));
+        let error = filter(FilterValue::ToInt {}).eval(&variables, Value::String("abc".to_string())).err().unwrap();
+        assert_eq!(error.inner, RunnerError::FilterInvalidInput { actual: "abc".to_string() });
+        assert_eq!(filter(FilterValue::ToFloat {}).eval(&variables, Value::String("1.5".to_string())).unwrap(), Some(Value::from_f64(1.5)));
+        assert_eq!(filter(FilterValue::ToFloat {}).eval(&variables, Value::Integer(2)).unwrap(), Some(Value::Float(2, 0)));
+    }
 }
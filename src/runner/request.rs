@@ -45,6 +45,7 @@ impl Request {
                     source_info: self.clone().url.source_info,
                     inner: RunnerError::InvalidURL(url),
                     assert: false,
+                    call_site: None,
                 });
             }
             Ok(u) => {
@@ -146,6 +147,7 @@ impl Request {
                             source_info: filename.source_info,
                             inner: RunnerError::FileReadAccess { value: filename.value.clone() },
                             assert: false,
+                            call_site: None,
                         });
                     }
                     multipart.push(http::request::MultipartParam::FileParam { name, filename: absolute_filename, content_type });
@@ -230,6 +232,51 @@ pub fn split_url(url: String) -> (String, Vec<http::core::Param>) {
     }
 }
 
+impl Request {
+    ///
+    /// Add the default headers and query params of the file header,
+    /// unless the request already defines them (in its url for the query params).
+    ///
+    pub fn with_defaults(self, file_sections: &[Section]) -> Request {
+        let mut request = self;
+        for file_section in file_sections {
+            match file_section.value.clone() {
+                SectionValue::Headers(default_headers) => {
+                    let mut headers: Vec<KeyValue> = default_headers
+                        .into_iter()
+                        .filter(|default| !request.headers.iter().any(|h| h.key.value.eq_ignore_ascii_case(default.key.value.as_str())))
+                        .collect();
+                    headers.append(&mut request.headers);
+                    request.headers = headers;
+                }
+                SectionValue::QueryParams(default_params) => {
+                    let request_params = request.clone().querystring_params();
+                    let url: String = request.url.elements.iter().map(|element| match element {
+                        TemplateElement::String { value, .. } => value.clone(),
+                        TemplateElement::Expression(expr) => expr.to_string(),
+                    }).collect();
+                    let (_, url_params) = split_url(url);
+                    let mut params: Vec<KeyValue> = default_params
+                        .into_iter()
+                        .filter(|default| !request_params.iter().any(|p| p.key.value == default.key.value))
+                        .filter(|default| !url_params.iter().any(|p| p.name == default.key.value))
+                        .collect();
+                    params.extend(request_params);
+                    match request.sections.iter_mut().find(|s| s.name() == "QueryStringParams") {
+                        Some(section) => section.value = SectionValue::QueryParams(params),
+                        None => request.sections.insert(0, Section {
+                            value: SectionValue::QueryParams(params),
+                            ..file_section.clone()
+                        }),
+                    }
+                }
+                _ => {}
+            }
+        }
+        request
+    }
+}
+
 pub fn eval_url(s: String) -> Result<http::core::Url, RunnerError> {
     match url::Url::parse(s.as_str()) {
         Err(_) => Err(RunnerError::InvalidURL(s)),
@@ -275,10 +322,11 @@ mod tests {
                 elements: vec![
                     TemplateElement::Expression(Expr {
                         space0: whitespace.clone(),
-                        variable: Variable {
+                        value: ExprValue::Variable(Variable {
                             name: String::from("base_url"),
                             source_info: SourceInfo::init(1, 7, 1, 15),
-                        },
+                        }),
+                        filters: vec![],
                         space1: whitespace.clone(),
                     }),
                     TemplateElement::String {
@@ -363,10 +411,11 @@ mod tests {
                                 elements: vec![
                                     TemplateElement::Expression(Expr {
                                         space0: whitespace.clone(),
-                                        variable: Variable {
+                                        value: ExprValue::Variable(Variable {
                                             name: String::from("param1"),
                                             source_info: SourceInfo::init(1, 7, 1, 15),
-                                        },
+                                        }),
+                                        filters: vec![],
                                         space1: whitespace.clone(),
                                     })
                                 ],
@@ -448,4 +497,59 @@ mod tests {
         assert_eq!(url.port, Some(8000));
 // assert_eq!(url.querystring.unwrap(), String::from("param1=value1"));
     }
+
+    #[test]
+    pub fn test_with_defaults() {
+        let key = |value: &str| EncodedString {
+            quotes: false,
+            value: value.to_string(),
+            encoded: value.to_string(),
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let value = |value: &str| Template {
+            quotes: false,
+            elements: vec![TemplateElement::String { value: value.to_string(), encoded: value.to_string() }],
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let file_section = |value: SectionValue| Section {
+            line_terminators: vec![],
+            space0: Whitespace { value: String::from(""), source_info: SourceInfo::init(0, 0, 0, 0) },
+            line_terminator0: query_request().line_terminator0,
+            value,
+            source_info: SourceInfo::init(0, 0, 0, 0),
+        };
+        let file_sections = vec![
+            file_section(SectionValue::Headers(vec![
+                simple_key_value(key("Accept"), value("application/json")),
+                simple_key_value(key("user-agent"), value("hurl")),
+            ])),
+            file_section(SectionValue::QueryParams(vec![
+                simple_key_value(key("lang"), value("en")),
+                simple_key_value(key("param2"), value("default")),
+            ])),
+        ];
+
+        let mut request = hello_request();
+        request.headers = vec![simple_key_value(key("User-Agent"), value("curl"))];
+        let request = request.with_defaults(&file_sections);
+        assert_eq!(request.headers, vec![
+            simple_key_value(key("Accept"), value("application/json")),
+            simple_key_value(key("User-Agent"), value("curl")),
+        ]);
+        assert_eq!(request.querystring_params(), vec![
+            simple_key_value(key("lang"), value("en")),
+            simple_key_value(key("param2"), value("default")),
+        ]);
+
+        let params = query_request().with_defaults(&file_sections).querystring_params();
+        let names: Vec<String> = params.iter().map(|p| p.key.value.clone()).collect();
+        assert_eq!(names, vec!["lang", "param1", "param2"]);
+        assert_eq!(params[2].value, value("a b"));
+
+        // a param of the url overrides the default param
+        let mut request = hello_request();
+        request.url.elements.push(TemplateElement::String { value: "?lang=fr".to_string(), encoded: "?lang=fr".to_string() });
+        let params = request.with_defaults(&file_sections).querystring_params();
+        assert_eq!(params, vec![simple_key_value(key("param2"), value("default"))]);
+    }
 }
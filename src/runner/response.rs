@@ -19,6 +19,7 @@ use std::collections::HashMap;
 
 use encoding::{DecoderTrap, Encoding};
 use encoding::all::ISO_8859_1;
+use regex::Regex;
 
 use crate::core::common::{Pos, SourceInfo};
 use crate::core::common::Value;
@@ -27,6 +28,8 @@ use crate::runner::core::RunnerError;
 
 use super::core::*;
 use super::core::Error;
+use super::json;
+use super::xpath;
 use super::super::core::ast::*;
 
 pub fn decode_bytes(bytes: Vec<u8>, encoding: http::core::Encoding) -> Result<String, RunnerError> {
@@ -43,7 +46,7 @@ pub fn decode_bytes(bytes: Vec<u8>, encoding: http::core::Encoding) -> Result<St
 }
 
 impl Response {
-    pub fn eval_asserts(self, variables: &HashMap<String, Value>, http_response: http::response::Response, context_dir: String) -> Vec<AssertResult> {
+    pub fn eval_asserts(self, variables: &HashMap<String, Value>, http_response: http::response::Response, context_dir: String, namespaces: &HashMap<String, String>) -> Vec<AssertResult> {
         let mut asserts = vec![];
 
         let version = self.clone().version;
@@ -78,6 +81,7 @@ impl Response {
                                 source_info: header.key.clone().source_info,
                                 inner: RunnerError::QueryHeaderNotFound {},
                                 assert: false,
+                                call_site: None,
                             }),
                             expected,
                             source_info: header.key.clone().source_info,
@@ -115,44 +119,49 @@ impl Response {
         if let Some(body) = self.clone().body {
             match body.value {
                 Bytes::Json { value } => {
-                    let expected = match value.eval(variables) {
-                        Ok(s) => Ok(Value::String(s)),
-                        Err(e) => Err(e),
-                    };
-                    let actual = match decode_bytes(http_response.body.clone(), http_response.encoding()) {
-                        Ok(s) => Ok(Value::String(s)),
-                        Err(e) => Err(Error {
-                            source_info: SourceInfo {
-                                start: Pos { line: 1, column: 1 },
-                                end: Pos { line: 1, column: 1 },
-                            },
-                            inner: e,
-                            assert: true,
-                        }),
-                    };
-                    asserts.push(AssertResult::Body {
-                        actual,
-                        expected,
-                        source_info: body.space0.source_info.clone(),
+                    // the json body is compared structurally, the differences are displayed line by line
+                    let source_info = body.space0.source_info.clone();
+                    let line = source_info.start.line;
+                    let diffs = self.clone().json_ignore_paths(variables).and_then(|ignored| {
+                        let actual = match decode_bytes(http_response.body.clone(), http_response.encoding()) {
+                            Ok(s) => s,
+                            Err(e) => return Err(Error {
+                                source_info: source_info.clone(),
+                                inner: e,
+                                assert: true,
+                                call_site: None,
+                            }),
+                        };
+                        let actual = match serde_json::from_str::<serde_json::Value>(actual.as_str()) {
+                            Ok(v) => v,
+                            Err(_) => return Err(Error {
+                                source_info: source_info.clone(),
+                                inner: RunnerError::QueryInvalidJson,
+                                assert: true,
+                                call_site: None,
+                            }),
+                        };
+                        value.diff(&actual, variables, &ignored)
+                    });
+                    asserts.push(AssertResult::JsonBody {
+                        diffs,
+                        source_info: SourceInfo::init(line, 0, line, 0),
                     })
                 }
                 Bytes::Xml { value } => {
-                    let expected = Ok(Value::String(value));
-                    let actual = match decode_bytes(http_response.body.clone(), http_response.encoding()) {
-                        Ok(s) => Ok(Value::String(s)),
-                        Err(e) => Err(Error {
-                            source_info: SourceInfo {
-                                start: Pos { line: 1, column: 1 },
-                                end: Pos { line: 1, column: 1 },
-                            },
-                            inner: e,
-                            assert: true,
-                        }),
-                    };
-                    asserts.push(AssertResult::Body {
-                        actual,
-                        expected,
-                        source_info: body.space0.source_info.clone(),
+                    let source_info = body.space0.source_info.clone();
+                    let line = source_info.start.line;
+                    asserts.push(AssertResult::XmlBody {
+                        diff: xml_body_diff(Ok(value), &http_response, source_info),
+                        source_info: SourceInfo::init(line, 0, line, 0),
+                    })
+                }
+                Bytes::TemplateXml { value, .. } => {
+                    let source_info = body.space0.source_info.clone();
+                    let line = source_info.start.line;
+                    asserts.push(AssertResult::XmlBody {
+                        diff: xml_body_diff(value.eval_xml(variables), &http_response, source_info),
+                        source_info: SourceInfo::init(line, 0, line, 0),
                     })
                 }
                 Bytes::RawString { value, .. } => {
@@ -169,6 +178,7 @@ impl Response {
                             },
                             inner: e,
                             assert: true,
+                            call_site: None,
                         }),
                     };
                     asserts.push(AssertResult::Body {
@@ -183,8 +193,8 @@ impl Response {
                         expected: Ok(Value::Bytes(value)),
                         source_info: SourceInfo { start: space0.source_info.end, end: space1.source_info.start },
                     }),
-                Bytes::File { .. } => {
-                    let expected = match body.clone().eval(variables, context_dir) {
+                Bytes::File { .. } | Bytes::TemplateFile { .. } => {
+                    let expected = match body.clone().eval(variables, context_dir.clone()) {
                         Ok(bytes) => Ok(Value::Bytes(bytes)),
                         Err(e) => Err(e),
                     };
@@ -199,22 +209,62 @@ impl Response {
         }
 
         for assert in self.asserts() {
-            let assert_result = assert.eval(http_response.clone(), variables);
+            let assert_result = assert.eval(http_response.clone(), variables, context_dir.clone(), namespaces);
             asserts.push(assert_result);
         }
         asserts
     }
 
-    pub fn eval_captures(self, http_response: http::response::Response, variables: &HashMap<String, Value>) -> Result<Vec<CaptureResult>, Error> {
+    fn json_ignore_paths(self, variables: &HashMap<String, Value>) -> Result<Vec<Regex>, Error> {
+        let mut paths = vec![];
+        for ignored_path in self.json_ignore() {
+            let source_info = ignored_path.path.source_info.clone();
+            let value = ignored_path.path.eval(variables)?;
+            match json::ignored_path(value.as_str()) {
+                Some(re) => paths.push(re),
+                None => return Err(Error {
+                    source_info,
+                    inner: RunnerError::QueryInvalidJsonpathExpression { value },
+                    assert: false,
+                    call_site: None,
+                }),
+            }
+        }
+        Ok(paths)
+    }
+
+    pub fn eval_captures(self, http_response: http::response::Response, variables: &HashMap<String, Value>, namespaces: &HashMap<String, String>) -> Result<Vec<CaptureResult>, Error> {
         let mut captures = vec![];
         for capture in self.captures() {
-            let capture_result = capture.eval(variables, http_response.clone())?;
+            let capture_result = capture.eval(variables, http_response.clone(), namespaces)?;
             captures.push(capture_result);
         }
         Ok(captures)
     }
 }
 
+// the xml body is compared by canonical form, the first difference is reported
+fn xml_body_diff(expected: Result<String, Error>, http_response: &http::response::Response, source_info: SourceInfo) -> Result<Option<xpath::XmlDiff>, Error> {
+    let expected = expected?;
+    match decode_bytes(http_response.body.clone(), http_response.encoding()) {
+        Ok(actual) => match xpath::compare_xml(expected, actual) {
+            Ok(diff) => Ok(diff),
+            Err(_) => Err(Error {
+                source_info,
+                inner: RunnerError::QueryInvalidXml,
+                assert: true,
+                call_site: None,
+            }),
+        },
+        Err(e) => Err(Error {
+            source_info,
+            inner: e,
+            assert: true,
+            call_site: None,
+        }),
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -273,7 +323,7 @@ mod tests {
         let variables = HashMap::new();
         let context_dir = "undefined".to_string();
         assert_eq!(
-            user_response().eval_asserts(&variables, http::response::tests::xml_two_users_http_response(), context_dir),
+            user_response().eval_asserts(&variables, http::response::tests::xml_two_users_http_response(), context_dir, &HashMap::new()),
             vec![
                 AssertResult::Version {
                     actual: String::from("1.0"),
@@ -286,7 +336,7 @@ mod tests {
                     source_info: SourceInfo::init(2, 10, 2, 13),
                 },
                 AssertResult::Explicit {
-                    actual: Ok(Some(Value::Nodeset(2))),
+                    actual: Ok(Some(Value::Nodeset(vec![String::from("Bob"), String::from("Bill")]))),
                     source_info: SourceInfo::init(1, 14, 1, 27),
                     predicate_result: Some(Err(Error {
                         source_info: SourceInfo::init(1, 0, 1, 0),
@@ -296,6 +346,7 @@ mod tests {
                             type_mismatch: false,
                         },
                         assert: true,
+                        call_site: None,
                     })),
                 }
             ]
@@ -306,7 +357,7 @@ mod tests {
     pub fn test_eval_captures() {
         let variables = HashMap::new();
         assert_eq!(
-            user_response().eval_captures(http::response::tests::xml_two_users_http_response(), &variables).unwrap(),
+            user_response().eval_captures(http::response::tests::xml_two_users_http_response(), &variables, &HashMap::new()).unwrap(),
             vec![
                 CaptureResult {
                     name: "UserCount".to_string(),
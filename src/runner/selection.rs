@@ -0,0 +1,233 @@
+/*
+ * hurl (https://hurl.dev)
+ * Copyright (C) 2020 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//!
+//! Selection of the entries to run by name and tags
+//!
+//! The name and tags of an entry are given in its options, those of the file header apply to all its entries.
+//! With dependencies, an entry that is not selected is still run when a selected entry uses one of its captures,
+//! unless it has an excluded tag: the selected entry then fails on the undefined variable.
+//! Only the entries of the file itself are tracked, the captures of the included files are not.
+//!
+
+use std::collections::HashSet;
+
+use crate::core::ast::*;
+use crate::format::token::{Token, Tokenizable};
+
+use super::core::EntryFilter;
+
+///
+/// Return for each entry of the file whether it must be run.
+///
+pub fn select(hurl_file: &HurlFile, filter: &EntryFilter) -> Vec<bool> {
+    let file_options = hurl_file.options();
+    let entries: Vec<Option<Entry>> = hurl_file.entries.iter().map(|file_entry| entry(hurl_file, file_entry)).collect();
+    let options: Vec<Option<Vec<KeyValue>>> = entries
+        .iter()
+        .map(|entry| {
+            entry.as_ref().map(|entry| {
+                let mut options = file_options.clone();
+                options.extend(entry.request.clone().options());
+                options
+            })
+        })
+        .collect();
+    let mut selected: Vec<bool> = options
+        .iter()
+        .map(|options| match options {
+            None => false,
+            Some(options) => is_matching(options, filter),
+        })
+        .collect();
+    if !filter.with_dependencies {
+        return selected;
+    }
+
+    // entries capturing the variables used by the following selected entries
+    let mut needed: HashSet<String> = HashSet::new();
+    for i in (0..entries.len()).rev() {
+        let entry = match &entries[i] {
+            None => continue,
+            Some(entry) => entry,
+        };
+        let captures = captures(entry);
+        let excluded = options[i].as_ref().map(|options| is_excluded(options, filter)).unwrap_or(true);
+        if !selected[i] && !excluded && captures.iter().any(|name| needed.contains(name)) {
+            selected[i] = true;
+        }
+        if selected[i] {
+            for name in captures {
+                needed.remove(&name);
+            }
+            needed.extend(variables(hurl_file, &hurl_file.entries[i], entry));
+        }
+    }
+    selected
+}
+
+// the entry run by a file entry, the entry of the template for a call
+fn entry(hurl_file: &HurlFile, file_entry: &FileEntry) -> Option<Entry> {
+    match file_entry {
+        FileEntry::Entry(entry) => Some(entry.as_ref().clone()),
+        FileEntry::Call(call) => hurl_file.template(call.name.name.as_str()).map(|template| template.entry),
+    }
+}
+
+fn is_matching(options: &[KeyValue], filter: &EntryFilter) -> bool {
+    let names: Vec<String> = options.iter().filter(|option| option.key.value == "name").filter_map(|option| option.text()).collect();
+    let tags: Vec<String> = options.iter().filter(|option| option.key.value == "tags").flat_map(|option| option.tags()).collect();
+    if !filter.names.is_empty() && !filter.names.iter().any(|pattern| names.iter().any(|name| is_matching_pattern(pattern, name))) {
+        return false;
+    }
+    if !filter.tags.is_empty() && !filter.tags.iter().any(|tag| tags.contains(tag)) {
+        return false;
+    }
+    !is_excluded(options, filter)
+}
+
+fn is_excluded(options: &[KeyValue], filter: &EntryFilter) -> bool {
+    let tags: Vec<String> = options.iter().filter(|option| option.key.value == "tags").flat_map(|option| option.tags()).collect();
+    filter.exclude_tags.iter().any(|tag| tags.contains(tag))
+}
+
+// a * in the pattern matches any characters
+fn is_matching_pattern(pattern: &str, name: &str) -> bool {
+    match pattern.find('*') {
+        None => pattern == name,
+        Some(index) => {
+            let (prefix, rest) = (&pattern[..index], &pattern[index + 1..]);
+            match name.strip_prefix(prefix) {
+                None => false,
+                Some(name) => (0..=name.len())
+                    .filter(|i| name.is_char_boundary(*i))
+                    .any(|i| is_matching_pattern(rest, &name[i..])),
+            }
+        }
+    }
+}
+
+fn captures(entry: &Entry) -> Vec<String> {
+    match entry.response.clone() {
+        None => vec![],
+        Some(response) => response.captures().iter().map(|capture| capture.name.value.clone()).collect(),
+    }
+}
+
+// the variables used by the entry, the default headers and query params of the file header included
+fn variables(hurl_file: &HurlFile, file_entry: &FileEntry, entry: &Entry) -> HashSet<String> {
+    let mut tokens = entry.tokenize();
+    for section in hurl_file.sections.iter() {
+        if ["Headers", "QueryStringParams"].contains(&section.name()) {
+            tokens.extend(section.tokenize());
+        }
+    }
+    let mut variables: HashSet<String> = tokens
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::CodeVariable(name) => Some(name),
+            _ => None,
+        })
+        .collect();
+    for option in entry.request.clone().options() {
+        if let Some((_, list)) = option.loop_variables() {
+            variables.insert(list);
+        }
+        if let Some(condition) = option.condition() {
+            variables.insert(condition.variable);
+        }
+    }
+    // the parameters of a template are given by the call
+    if let FileEntry::Call(call) = file_entry {
+        if let Some(template) = hurl_file.template(call.name.name.as_str()) {
+            for param in template.params {
+                variables.remove(&param.1.name);
+            }
+        }
+        for argument in call.arguments.iter() {
+            for token in argument.tokenize() {
+                if let Token::CodeVariable(name) = token {
+                    variables.insert(name);
+                }
+            }
+        }
+    }
+    variables
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser;
+
+    fn filter(names: Vec<&str>, tags: Vec<&str>, exclude_tags: Vec<&str>) -> EntryFilter {
+        EntryFilter {
+            names: names.iter().map(|s| s.to_string()).collect(),
+            tags: tags.iter().map(|s| s.to_string()).collect(),
+            exclude_tags: exclude_tags.iter().map(|s| s.to_string()).collect(),
+            with_dependencies: true,
+        }
+    }
+
+    #[test]
+    fn test_is_matching_pattern() {
+        assert!(is_matching_pattern("login", "login"));
+        assert!(!is_matching_pattern("login", "logout"));
+        assert!(is_matching_pattern("log*", "logout"));
+        assert!(is_matching_pattern("*user*", "create_user_admin"));
+        assert!(!is_matching_pattern("*user", "create_user_admin"));
+        assert!(is_matching_pattern("*", ""));
+    }
+
+    #[test]
+    fn test_select() {
+        let hurl_file = parser::parse_hurl_file(r#"[Options]
+tags: api
+
+POST http://localhost/login
+[Options]
+name: login
+tags: auth, slow
+HTTP/1.0 200
+[Captures]
+token: header "token"
+
+GET http://localhost/users
+Authorization: {{token}}
+[Options]
+name: list_users
+tags: smoke
+
+GET http://localhost/health
+[Options]
+name: health
+tags: smoke, slow
+"#).unwrap();
+        assert_eq!(select(&hurl_file, &EntryFilter { with_dependencies: true, ..EntryFilter::default() }), vec![true, true, true]);
+        assert_eq!(select(&hurl_file, &filter(vec![], vec!["smoke"], vec![])), vec![true, true, true]);
+        assert_eq!(select(&hurl_file, &filter(vec![], vec!["smoke"], vec!["slow"])), vec![false, true, false]);
+        assert_eq!(select(&hurl_file, &filter(vec!["health"], vec![], vec![])), vec![false, false, true]);
+        assert_eq!(select(&hurl_file, &filter(vec!["*user*"], vec![], vec![])), vec![true, true, false]);
+        assert_eq!(select(&hurl_file, &filter(vec![], vec!["api"], vec!["smoke"])), vec![true, false, false]);
+        assert_eq!(select(&hurl_file, &filter(vec![], vec!["unknown"], vec![])), vec![false, false, false]);
+
+        let without_dependencies = |filter: EntryFilter| EntryFilter { with_dependencies: false, ..filter };
+        assert_eq!(select(&hurl_file, &without_dependencies(filter(vec![], vec!["smoke"], vec![]))), vec![false, true, true]);
+        assert_eq!(select(&hurl_file, &without_dependencies(filter(vec![], vec!["smoke"], vec!["slow"]))), vec![false, true, false]);
+        assert_eq!(select(&hurl_file, &without_dependencies(filter(vec!["*user*"], vec![], vec![]))), vec![false, true, false]);
+    }
+}
@@ -36,20 +36,57 @@ impl Template {
             Ok(value)
         }
     }
+
+    ///
+    /// render the template in an xml document
+    /// the values of the variables are escaped, so that they can not break the xml
+    ///
+    pub fn eval_xml(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
+        let mut value = String::from("");
+        for elem in self.elements {
+            match elem {
+                TemplateElement::String { value: s, .. } => value.push_str(s.as_str()),
+                expr => value.push_str(escape_xml(expr.eval(variables)?.as_str()).as_str()),
+            }
+        }
+        Ok(value)
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl RegexValue {
+    pub fn eval(self, variables: &HashMap<String, Value>) -> Result<regex::Regex, Error> {
+        match self {
+            RegexValue::Literal(literal) => Ok(literal.inner),
+            RegexValue::Template(template) => {
+                let source_info = template.source_info.clone();
+                let value = template.eval(variables)?;
+                match regex::Regex::new(value.as_str()) {
+                    Ok(re) => Ok(re),
+                    Err(_) => Err(Error { source_info, inner: RunnerError::InvalidRegex(), assert: false, call_site: None }),
+                }
+            }
+        }
+    }
 }
 
 impl TemplateElement {
     pub fn eval(self, variables: &HashMap<String, Value>) -> Result<String, Error> {
         match self {
             TemplateElement::String { value, .. } => { Ok(value) }
-            TemplateElement::Expression(Expr { variable: Variable { name, source_info }, .. }) => {
-                match variables.get(&name as &str) {
-                    Some(value) => if value.is_renderable() {
-                        Ok(value.clone().to_string())
-                    } else {
-                        Err(Error { source_info, inner: RunnerError::UnrenderableVariable { value: value.to_string() }, assert: false })
-                    },
-                    _ => Err(Error { source_info, inner: RunnerError::TemplateVariableNotDefined { name }, assert: false }),
+            TemplateElement::Expression(expr) => {
+                let source_info = expr.source_info();
+                let value = expr.eval(variables)?;
+                match value.render() {
+                    Some(s) => Ok(s),
+                    None => Err(Error { source_info, inner: RunnerError::UnrenderableVariable { value: value.to_string() }, assert: false, call_site: None }),
                 }
             }
         }
@@ -63,6 +100,15 @@ impl Value {
             _ => false,
         }
     }
+
+    // text of the value in a template, floats being rendered as in json, without trailing zeros
+    pub fn render(&self) -> Option<String> {
+        match self {
+            Value::Float(_, _) => Some(self.to_json().to_string()),
+            value if value.is_renderable() => Some(value.to_string()),
+            _ => None,
+        }
+    }
 }
 
 
@@ -76,7 +122,8 @@ mod tests {
         // {{name}}
         TemplateElement::Expression(Expr {
             space0: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 3, 1, 3) },
-            variable: Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 3, 1, 7) },
+            value: ExprValue::Variable(Variable { name: "name".to_string(), source_info: SourceInfo::init(1, 3, 1, 7) }),
+            filters: vec![],
             space1: Whitespace { value: "".to_string(), source_info: SourceInfo::init(1, 7, 1, 7) },
         })
     }
@@ -92,6 +139,9 @@ mod tests {
         let mut variables = HashMap::new();
         variables.insert("name".to_string(), Value::String("World".to_string()));
         assert_eq!(template_element_expression().eval(&variables).unwrap(), "World".to_string());
+
+        variables.insert("name".to_string(), Value::from_f64(1.5));
+        assert_eq!(template_element_expression().eval(&variables).unwrap(), "1.5".to_string());
     }
 
 
@@ -103,4 +153,22 @@ mod tests {
         assert_eq!(error.source_info, SourceInfo::init(1, 3, 1, 7));
         assert_eq!(error.inner, RunnerError::UnrenderableVariable { value: "[1,2]".to_string() });
     }
+
+    #[test]
+    fn test_template_xml() {
+        // <a>{{name}}</a>
+        let template = Template {
+            quotes: false,
+            elements: vec![
+                TemplateElement::String { value: "<a>".to_string(), encoded: "<a>".to_string() },
+                template_element_expression(),
+                TemplateElement::String { value: "</a>".to_string(), encoded: "</a>".to_string() },
+            ],
+            source_info: SourceInfo::init(1, 1, 1, 16),
+        };
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), Value::String("Tom & \"Jerry\" <3".to_string()));
+        assert_eq!(template.clone().eval(&variables).unwrap(), "<a>Tom & \"Jerry\" <3</a>".to_string());
+        assert_eq!(template.eval_xml(&variables).unwrap(), "<a>Tom &amp; &quot;Jerry&quot; &lt;3</a>".to_string());
+    }
 }
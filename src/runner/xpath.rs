@@ -18,7 +18,12 @@
 // unique entry point to libxml
 extern crate libxml;
 
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
+
+use libxml::bindings;
 
 use super::super::core::common::Value;
 
@@ -30,19 +35,19 @@ pub enum XpathError {
     Unsupported,
 }
 
-pub fn eval_xml(xml: String, expr: String) -> Result<Value, XpathError> {
+pub fn eval_xml(xml: String, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
     let parser = libxml::parser::Parser::default();
     match parser.parse_string(xml) {
         Ok(doc) => if doc.get_root_element() == None {
             Err(XpathError::InvalidXML {})
         } else {
-            eval(doc, expr)
+            eval(doc, expr, namespaces)
         },
         Err(_) => Err(XpathError::InvalidXML {})
     }
 }
 
-pub fn eval_html(html: String, expr: String) -> Result<Value, XpathError> {
+pub fn eval_html(html: String, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
     let parser = libxml::parser::Parser::default_html();
     match parser.parse_string(html) {
         Ok(doc) => {
@@ -51,18 +56,23 @@ pub fn eval_html(html: String, expr: String) -> Result<Value, XpathError> {
             if doc.get_root_element() == None {
                 Err(XpathError::InvalidHtml {})
             } else {
-                eval(doc, expr)
+                eval(doc, expr, namespaces)
             }
         }
         Err(_) => Err(XpathError::InvalidHtml {})
     }
 }
 
-pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathError> {
+pub fn eval(doc: libxml::tree::Document, expr: String, namespaces: &HashMap<String, String>) -> Result<Value, XpathError> {
     let context = match libxml::xpath::Context::new(&doc) {
         Ok(context) => context,
         _ => panic!("error setting context in xpath module")
     };
+    for (prefix, uri) in namespaces {
+        if context.register_namespace(prefix.as_str(), uri.as_str()).is_err() {
+            return Err(XpathError::Eval {});
+        }
+    }
     unsafe {
         libxml::bindings::initGenericErrorDefaultFunc(&mut None);
     }
@@ -87,7 +97,11 @@ pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathErr
 
             Ok(Value::String(s))
         }
-        libxml::bindings::xmlXPathObjectType_XPATH_NODESET => Ok(Value::Nodeset(result.get_number_of_nodes())),
+        libxml::bindings::xmlXPathObjectType_XPATH_NODESET => {
+            // string value of each node (text content for elements, value for attributes)
+            let values = result.get_nodes_as_vec().iter().map(|node| node.get_content()).collect();
+            Ok(Value::Nodeset(values))
+        }
         _ => {
             Err(XpathError::Unsupported {})
         }
@@ -95,6 +109,307 @@ pub fn eval(doc: libxml::tree::Document, expr: String) -> Result<Value, XpathErr
 }
 
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaKind {
+    Xsd,
+    Dtd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SchemaError {
+    InvalidXML,
+    // errors reported while parsing the schema
+    InvalidSchema { errors: Vec<ValidationError> },
+}
+
+// error reported by libxml while validating a document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    pub line: usize,
+    pub message: String,
+}
+
+///
+/// validate an xml document against an XML Schema or a DTD
+/// returns all the validation errors (empty if the document is valid)
+///
+pub fn validate_xml(xml: String, schema: &[u8], kind: SchemaKind) -> Result<Vec<ValidationError>, SchemaError> {
+    let parser = libxml::parser::Parser::default();
+    let doc = match parser.parse_string(xml) {
+        Ok(doc) if doc.get_root_element().is_some() => doc,
+        _ => return Err(SchemaError::InvalidXML {}),
+    };
+    let mut schema_errors: Vec<ValidationError> = vec![];
+    let mut errors: Vec<ValidationError> = vec![];
+    let schema_errors_ptr = &mut schema_errors as *mut Vec<ValidationError> as *mut c_void;
+    let errors_ptr = &mut errors as *mut Vec<ValidationError> as *mut c_void;
+    let buffer = schema.as_ptr() as *const c_char;
+    let size = schema.len() as i32;
+
+    // errors are collected with the global structured error handler
+    // which takes precedence over the generic error function (reset by the xpath evaluation)
+    let rc = unsafe {
+        bindings::xmlSetStructuredErrorFunc(schema_errors_ptr, Some(collect_error));
+        let rc = match kind {
+            SchemaKind::Xsd => {
+                let parser_ctxt = bindings::xmlSchemaNewMemParserCtxt(buffer, size);
+                let xsd = if parser_ctxt.is_null() { ptr::null_mut() } else { bindings::xmlSchemaParse(parser_ctxt) };
+                if !parser_ctxt.is_null() {
+                    bindings::xmlSchemaFreeParserCtxt(parser_ctxt);
+                }
+                if xsd.is_null() {
+                    -1
+                } else {
+                    bindings::xmlSetStructuredErrorFunc(errors_ptr, Some(collect_error));
+                    let ctxt = bindings::xmlSchemaNewValidCtxt(xsd);
+                    let rc = bindings::xmlSchemaValidateDoc(ctxt, doc.doc_ptr());
+                    bindings::xmlSchemaFreeValidCtxt(ctxt);
+                    bindings::xmlSchemaFree(xsd);
+                    rc
+                }
+            }
+            SchemaKind::Dtd => {
+                let input = bindings::xmlParserInputBufferCreateMem(buffer, size, bindings::xmlCharEncoding_XML_CHAR_ENCODING_NONE);
+                let dtd = bindings::xmlIOParseDTD(ptr::null_mut(), input, bindings::xmlCharEncoding_XML_CHAR_ENCODING_NONE);
+                if dtd.is_null() {
+                    -1
+                } else {
+                    bindings::xmlSetStructuredErrorFunc(errors_ptr, Some(collect_error));
+                    let ctxt = bindings::xmlNewValidCtxt();
+                    let valid = bindings::xmlValidateDtd(ctxt, doc.doc_ptr(), dtd);
+                    bindings::xmlFreeValidCtxt(ctxt);
+                    bindings::xmlFreeDtd(dtd);
+                    if valid == 1 { 0 } else { 1 }
+                }
+            }
+        };
+        bindings::xmlSetStructuredErrorFunc(ptr::null_mut(), None);
+        rc
+    };
+    if rc < 0 {
+        return Err(SchemaError::InvalidSchema { errors: schema_errors });
+    }
+    Ok(errors)
+}
+
+// libxml structured error handler, user data is the vector collecting the errors
+unsafe fn collect_error(errors: *mut c_void, error: bindings::xmlErrorPtr) {
+    let errors = &mut *(errors as *mut Vec<ValidationError>);
+    if error.is_null() || (*error).level < bindings::xmlErrorLevel_XML_ERR_ERROR {
+        return;
+    }
+    let message = if (*error).message.is_null() {
+        String::from("")
+    } else {
+        CStr::from_ptr((*error).message).to_string_lossy().trim_end().to_string()
+    };
+    errors.push(ValidationError { line: (*error).line as usize, message });
+}
+
+
+///
+/// First difference between an expected xml document and the actual one
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XmlDiff {
+    pub path: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+///
+/// compare two xml documents by their canonical form (C14N)
+/// attribute order, namespace prefixes, comments and whitespace-only text nodes are not significant
+/// returns the first differing node
+///
+pub fn compare_xml(expected: String, actual: String) -> Result<Option<XmlDiff>, XpathError> {
+    let expected = canonical_doc(expected)?;
+    let actual = canonical_doc(actual)?;
+    let expected_root = expected.get_root_element().unwrap();
+    let actual_root = actual.get_root_element().unwrap();
+    if element_name(&expected_root) != element_name(&actual_root) {
+        return Ok(Some(XmlDiff {
+            path: "/".to_string(),
+            expected: element_display(&expected_root),
+            actual: element_display(&actual_root),
+        }));
+    }
+    let path = format!("/{}", qualified_name(&expected_root));
+    Ok(compare_element(&expected_root, &actual_root, path))
+}
+
+// the document is parsed from its canonical serialization
+// (entities expanded, CDATA sections as text, adjacent text nodes merged, comments removed)
+fn canonical_doc(xml: String) -> Result<libxml::tree::Document, XpathError> {
+    let parser = libxml::parser::Parser::default();
+    let doc = match parser.parse_string(xml) {
+        Ok(doc) if doc.get_root_element().is_some() => doc,
+        _ => return Err(XpathError::InvalidXML {}),
+    };
+    let mut errors: Vec<ValidationError> = vec![];
+    let errors_ptr = &mut errors as *mut Vec<ValidationError> as *mut c_void;
+    let canonical = unsafe {
+        bindings::xmlSetStructuredErrorFunc(errors_ptr, Some(collect_error));
+        let mut buffer: *mut bindings::xmlChar = ptr::null_mut();
+        let size = bindings::xmlC14NDocDumpMemory(doc.doc_ptr(), ptr::null_mut(), bindings::xmlC14NMode_XML_C14N_1_0 as i32, ptr::null_mut(), 0, &mut buffer);
+        bindings::xmlSetStructuredErrorFunc(ptr::null_mut(), None);
+        if size < 0 || buffer.is_null() {
+            None
+        } else {
+            let canonical = CStr::from_ptr(buffer as *const c_char).to_string_lossy().into_owned();
+            if let Some(free) = bindings::xmlFree {
+                free(buffer as *mut c_void);
+            }
+            Some(canonical)
+        }
+    };
+    match canonical {
+        // a document with relative namespace uris can not be canonicalized
+        None => Ok(doc),
+        Some(canonical) => match parser.parse_string(canonical) {
+            Ok(doc) => Ok(doc),
+            Err(_) => Err(XpathError::InvalidXML {}),
+        },
+    }
+}
+
+fn compare_element(expected: &libxml::tree::Node, actual: &libxml::tree::Node, path: String) -> Option<XmlDiff> {
+    let expected_attributes = attributes(expected);
+    let actual_attributes = attributes(actual);
+    for (key, (name, value)) in expected_attributes.iter() {
+        let actual_value = match actual_attributes.get(key) {
+            Some((_, actual_value)) if actual_value == value => continue,
+            Some((_, actual_value)) => format!("\"{}\"", actual_value),
+            None => "none".to_string(),
+        };
+        return Some(XmlDiff { path: format!("{}/@{}", path, name), expected: format!("\"{}\"", value), actual: actual_value });
+    }
+    for (key, (name, value)) in actual_attributes.iter() {
+        if !expected_attributes.contains_key(key) {
+            return Some(XmlDiff { path: format!("{}/@{}", path, name), expected: "none".to_string(), actual: format!("\"{}\"", value) });
+        }
+    }
+
+    let expected_children = significant_children(expected);
+    let actual_children = significant_children(actual);
+    for i in 0..std::cmp::max(expected_children.len(), actual_children.len()) {
+        match (expected_children.get(i), actual_children.get(i)) {
+            (Some(e), Some(a)) if e.is_element_node() && a.is_element_node() => {
+                let child_path = child_path(&path, &expected_children, i);
+                if element_name(e) != element_name(a) {
+                    return Some(XmlDiff { path: child_path, expected: element_display(e), actual: element_display(a) });
+                }
+                if let Some(diff) = compare_element(e, a, child_path) {
+                    return Some(diff);
+                }
+            }
+            (Some(e), Some(a)) => {
+                if node_display(e) != node_display(a) {
+                    return Some(XmlDiff { path: child_path(&path, &expected_children, i), expected: node_display(e), actual: node_display(a) });
+                }
+            }
+            (Some(e), None) => return Some(XmlDiff { path: child_path(&path, &expected_children, i), expected: node_display(e), actual: "none".to_string() }),
+            (None, Some(a)) => return Some(XmlDiff { path: child_path(&path, &actual_children, i), expected: "none".to_string(), actual: node_display(a) }),
+            (None, None) => {}
+        }
+    }
+    None
+}
+
+// elements and text nodes, without whitespace-only text nodes
+fn significant_children(node: &libxml::tree::Node) -> Vec<libxml::tree::Node> {
+    node.get_child_nodes().into_iter().filter(|child| match child.get_type() {
+        Some(libxml::tree::NodeType::ElementNode) => true,
+        Some(libxml::tree::NodeType::TextNode) | Some(libxml::tree::NodeType::CDataSectionNode) => !child.get_content().trim().is_empty(),
+        _ => false,
+    }).collect()
+}
+
+// xpath of a child node, with its position among the siblings of the same name when needed
+fn child_path(parent_path: &str, children: &[libxml::tree::Node], index: usize) -> String {
+    let node = &children[index];
+    if !node.is_element_node() {
+        return format!("{}/text()", parent_path);
+    }
+    let name = element_name(node);
+    let siblings: Vec<usize> = children.iter().enumerate()
+        .filter(|(_, child)| child.is_element_node() && element_name(child) == name)
+        .map(|(i, _)| i)
+        .collect();
+    if siblings.len() > 1 {
+        let position = siblings.iter().position(|i| *i == index).unwrap_or(0) + 1;
+        format!("{}/{}[{}]", parent_path, qualified_name(node), position)
+    } else {
+        format!("{}/{}", parent_path, qualified_name(node))
+    }
+}
+
+// an element is identified by its namespace uri and local name, not by its prefix
+fn element_name(node: &libxml::tree::Node) -> (Option<String>, String) {
+    (node.get_namespace().map(|ns| ns.get_href()), node.get_name())
+}
+
+fn qualified_name(node: &libxml::tree::Node) -> String {
+    match node.get_namespace().map(|ns| ns.get_prefix()) {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, node.get_name()),
+        _ => node.get_name(),
+    }
+}
+
+fn element_display(node: &libxml::tree::Node) -> String {
+    match node.get_namespace() {
+        Some(ns) => format!("<{}> in namespace <{}>", qualified_name(node), ns.get_href()),
+        None => format!("<{}>", qualified_name(node)),
+    }
+}
+
+fn node_display(node: &libxml::tree::Node) -> String {
+    if node.is_element_node() {
+        element_display(node)
+    } else {
+        format!("\"{}\"", node.get_content().trim())
+    }
+}
+
+type AttributeKey = (Option<String>, String);
+
+// attributes indexed by namespace uri and local name, with their qualified name and value
+fn attributes(node: &libxml::tree::Node) -> std::collections::BTreeMap<AttributeKey, (String, String)> {
+    let mut attributes = std::collections::BTreeMap::new();
+    unsafe {
+        let mut attr = (*node.node_ptr()).properties;
+        while !attr.is_null() {
+            let name = CStr::from_ptr((*attr).name as *const c_char).to_string_lossy().into_owned();
+            let (namespace, qualified_name) = if (*attr).ns.is_null() {
+                (None, name.clone())
+            } else {
+                let ns = (*attr).ns;
+                let href = CStr::from_ptr((*ns).href as *const c_char).to_string_lossy().into_owned();
+                let qualified_name = if (*ns).prefix.is_null() {
+                    name.clone()
+                } else {
+                    format!("{}:{}", CStr::from_ptr((*ns).prefix as *const c_char).to_string_lossy(), name)
+                };
+                (Some(href), qualified_name)
+            };
+            let value_ptr = bindings::xmlNodeListGetString((*attr).doc, (*attr).children, 1);
+            let value = if value_ptr.is_null() {
+                String::from("")
+            } else {
+                let value = CStr::from_ptr(value_ptr as *const c_char).to_string_lossy().into_owned();
+                if let Some(free) = bindings::xmlFree {
+                    free(value_ptr as *mut c_void);
+                }
+                value
+            };
+            attributes.insert((namespace, name), (qualified_name, value));
+            attr = (*attr).next;
+        }
+    }
+    attributes
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,23 +424,23 @@ mod tests {
 </food>
 "#);
         let xpath = String::from("count(//food/*)");
-        assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(3.0));
+        assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::from_f64(3.0));
 
         let xpath = String::from("//food/*");
-        assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::Nodeset(3));
+        assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::Nodeset(vec![String::from(""); 3]));
 
         let xpath = String::from("count(//*[@type='fruit'])");
-        assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(2.0));
+        assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::from_f64(2.0));
 
         let xpath = String::from("number(//food/banana/@price)");
-        assert_eq!(eval_xml(xml.clone(), xpath).unwrap(), Value::from_f64(1.1));
+        assert_eq!(eval_xml(xml.clone(), xpath, &HashMap::new()).unwrap(), Value::from_f64(1.1));
     }
 
 
     #[test]
     fn test_error_eval() {
-        assert_eq!(eval_xml(String::from("<a/>"), String::from("^^^")).err().unwrap(), XpathError::Eval {});
-        assert_eq!(eval_xml(String::from("<a/>"), String::from("//")).err().unwrap(), XpathError::Eval {});
+        assert_eq!(eval_xml(String::from("<a/>"), String::from("^^^"), &HashMap::new()).err().unwrap(), XpathError::Eval {});
+        assert_eq!(eval_xml(String::from("<a/>"), String::from("//"), &HashMap::new()).err().unwrap(), XpathError::Eval {});
         // assert_eq!(1,2);
     }
 
@@ -134,13 +449,13 @@ mod tests {
 // Invalid XML not detected at parsing??? => goes into an eval error
     #[test]
     fn test_invalid_xml() {
-        assert_eq!(eval_xml(String::from("??"), String::from("//person")).err().unwrap(), XpathError::InvalidXML);
+        assert_eq!(eval_xml(String::from("??"), String::from("//person"), &HashMap::new()).err().unwrap(), XpathError::InvalidXML);
     }
 
     #[test]
     fn test_cafe() {
         assert_eq!(eval_xml(
-            String::from("<data>café</data>"), String::from("normalize-space(//data)")).unwrap(),
+            String::from("<data>café</data>"), String::from("normalize-space(//data)"), &HashMap::new()).unwrap(),
                    Value::String(String::from("café"))
         );
     }
@@ -157,7 +472,7 @@ mod tests {
   </body>
 </html>"#);
         let xpath = String::from("normalize-space(/html/head/meta/@charset)");
-        assert_eq!(eval_html(html.clone(), xpath).unwrap(), Value::String(String::from("UTF-8")));
+        assert_eq!(eval_html(html.clone(), xpath, &HashMap::new()).unwrap(), Value::String(String::from("UTF-8")));
     }
 
 
@@ -166,6 +481,140 @@ mod tests {
         let html = String::from(r#"<html></html>"#);
         //let xpath = String::from("boolean(count(//a[contains(@href,'xxx')]))");
         let xpath = String::from("boolean(count(//a[contains(@href,'xxx')]))");
-        assert_eq!(eval_html(html.clone(), xpath).unwrap(), Value::Bool(false));
+        assert_eq!(eval_html(html.clone(), xpath, &HashMap::new()).unwrap(), Value::Bool(false));
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <title>Example Feed</title>
+  <entry><dc:creator>John</dc:creator></entry>
+  <entry/>
+</feed>
+"#);
+        // elements in the default namespace can not be selected without a prefix
+        assert_eq!(eval_xml(xml.clone(), String::from("count(//feed/entry)"), &HashMap::new()).unwrap(), Value::from_f64(0.0));
+
+        let mut namespaces = HashMap::new();
+        namespaces.insert(String::from("atom"), String::from("http://www.w3.org/2005/Atom"));
+        namespaces.insert(String::from("dc"), String::from("http://purl.org/dc/elements/1.1/"));
+        assert_eq!(eval_xml(xml.clone(), String::from("count(//atom:feed/atom:entry)"), &namespaces).unwrap(), Value::from_f64(2.0));
+        assert_eq!(eval_xml(xml.clone(), String::from("string(//atom:title)"), &namespaces).unwrap(), Value::String(String::from("Example Feed")));
+        assert_eq!(eval_xml(xml.clone(), String::from("string(//dc:creator)"), &namespaces).unwrap(), Value::String(String::from("John")));
+
+        // undeclared prefix
+        assert_eq!(eval_xml(xml, String::from("//soap:Envelope"), &namespaces).err().unwrap(), XpathError::Eval {});
+
+        let html = String::from("<html><body><p>Hello</p></body></html>");
+        assert_eq!(eval_html(html, String::from("normalize-space(//p)"), &namespaces).unwrap(), Value::String(String::from("Hello")));
+    }
+
+    #[test]
+    fn test_nodeset_values() {
+        let html = String::from(r#"<html><body>
+<ul>
+  <li class="first">Apple</li>
+  <li>Banana <b>split</b></li>
+</ul>
+<a href="/home">Home</a>
+</body></html>"#);
+        assert_eq!(eval_html(html.clone(), String::from("//li"), &HashMap::new()).unwrap(),
+                   Value::Nodeset(vec![String::from("Apple"), String::from("Banana split")]));
+        assert_eq!(eval_html(html.clone(), String::from("//a/@href"), &HashMap::new()).unwrap(),
+                   Value::Nodeset(vec![String::from("/home")]));
+        assert_eq!(eval_html(html, String::from("//li[@class='none']"), &HashMap::new()).unwrap(),
+                   Value::Nodeset(vec![]));
+    }
+
+    #[test]
+    fn test_validate_xsd() {
+        let xsd = r#"<?xml version="1.0"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:element name="order">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element name="id" type="xs:integer"/>
+        <xs:element name="price" type="xs:decimal"/>
+      </xs:sequence>
+    </xs:complexType>
+  </xs:element>
+</xs:schema>"#;
+        let xml = String::from("<order><id>1</id><price>9.99</price></order>");
+        assert_eq!(validate_xml(xml, xsd.as_bytes(), SchemaKind::Xsd).unwrap(), vec![]);
+
+        let xml = String::from("<order>\n  <id>one</id>\n  <price>free</price>\n</order>");
+        let errors = validate_xml(xml, xsd.as_bytes(), SchemaKind::Xsd).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, 2);
+        assert!(errors[0].message.starts_with("Element 'id': 'one' is not a valid value"));
+        assert_eq!(errors[1].line, 3);
+
+        assert_eq!(validate_xml(String::from("xxx"), xsd.as_bytes(), SchemaKind::Xsd).err().unwrap(), SchemaError::InvalidXML {});
+        match validate_xml(String::from("<order/>"), b"<xs:schema", SchemaKind::Xsd).err().unwrap() {
+            SchemaError::InvalidSchema { errors } => {
+                assert!(!errors.is_empty());
+                assert_eq!(errors[0].line, 1);
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn test_validate_dtd() {
+        let dtd = b"<!ELEMENT order (id, price)>\n<!ELEMENT id (#PCDATA)>\n<!ELEMENT price (#PCDATA)>";
+        let xml = String::from("<order><id>1</id><price>9.99</price></order>");
+        assert_eq!(validate_xml(xml, dtd, SchemaKind::Dtd).unwrap(), vec![]);
+
+        let xml = String::from("<order>\n  <id>1</id>\n</order>");
+        let errors = validate_xml(xml, dtd, SchemaKind::Dtd).unwrap();
+        assert_eq!(errors, vec![ValidationError {
+            line: 1,
+            message: String::from("Element order content does not follow the DTD, expecting (id , price), got (id )"),
+        }]);
+    }
+
+    #[test]
+    fn test_compare_xml() {
+        let expected = String::from("<order id=\"1\" status=\"paid\">\n  <item>Pen</item>\n  <item><![CDATA[Ink]]></item>\n</order>");
+        let actual = String::from("<?xml version=\"1.0\"?><order status='paid' id='1'><!-- items --><item>Pen</item><item>Ink</item></order>");
+        assert_eq!(compare_xml(expected.clone(), actual).unwrap(), None);
+
+        let actual = String::from("<order id=\"1\" status=\"paid\"><item>Pen</item><item>Paper</item></order>");
+        assert_eq!(compare_xml(expected.clone(), actual).unwrap(), Some(XmlDiff {
+            path: String::from("/order/item[2]/text()"),
+            expected: String::from("\"Ink\""),
+            actual: String::from("\"Paper\""),
+        }));
+
+        let actual = String::from("<order id=\"2\" status=\"paid\"><item>Pen</item></order>");
+        assert_eq!(compare_xml(expected.clone(), actual).unwrap(), Some(XmlDiff {
+            path: String::from("/order/@id"),
+            expected: String::from("\"1\""),
+            actual: String::from("\"2\""),
+        }));
+
+        let actual = String::from("<order id=\"1\" status=\"paid\"><item>Pen</item></order>");
+        assert_eq!(compare_xml(expected.clone(), actual).unwrap(), Some(XmlDiff {
+            path: String::from("/order/item[2]"),
+            expected: String::from("<item>"),
+            actual: String::from("none"),
+        }));
+
+        assert_eq!(compare_xml(expected, String::from("xxx")).err().unwrap(), XpathError::InvalidXML {});
+    }
+
+    #[test]
+    fn test_compare_xml_namespaces() {
+        let expected = String::from("<a:order xmlns:a=\"http://example.org/order\"><a:id>1</a:id></a:order>");
+        let actual = String::from("<order xmlns=\"http://example.org/order\">\n  <id>1</id>\n</order>");
+        assert_eq!(compare_xml(expected.clone(), actual).unwrap(), None);
+
+        let actual = String::from("<order><id>1</id></order>");
+        assert_eq!(compare_xml(expected, actual).unwrap(), Some(XmlDiff {
+            path: String::from("/"),
+            expected: String::from("<a:order> in namespace <http://example.org/order>"),
+            actual: String::from("<order>"),
+        }));
     }
 }
@@ -27,6 +27,7 @@ fn default_client_options() -> http::client::ClientOptions {
         http_proxy: None,
         https_proxy: None,
         all_proxy: None,
+        timeout: None,
     }
 }
 
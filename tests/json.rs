@@ -25,7 +25,7 @@ use proptest::prelude::prop::test_runner::TestRunner;
 
 use hurl::core::json;
 use hurl::format::token::*;
-use hurl::core::ast::{Template, TemplateElement, Expr, Variable, Whitespace};
+use hurl::core::ast::{Template, TemplateElement, Expr, ExprValue, Variable, Whitespace};
 use hurl::core::common::SourceInfo;
 
 
@@ -90,7 +90,8 @@ fn value_string() -> BoxedStrategy<json::Value> {
                            value: "".to_string(),
                            source_info: source_info.clone()
                        },
-                       variable,
+                       value: ExprValue::Variable(variable),
+                       filters: vec![],
                        space1: Whitespace {
                            value: "".to_string(),
                            source_info: source_info.clone()
@@ -64,3 +64,53 @@ fn test_array() {
     let array = json!([{"name": "Bob"},{"name": "Bill"}]);
     assert_eq!(test_ok("$[0].name", array.clone()), vec![json!("Bob")]);
 }
+
+
+// queries with a consensus of the json-path-comparison project
+// https://cburgmer.github.io/json-path-comparison/
+// results are compared without order where the consensus is unordered
+#[test]
+fn test_comparison_consensus() {
+    let cases = vec![
+        ("$.key", json!({"key": "value"}), json!(["value"]), true),
+        ("$['key']", json!({"key": "value"}), json!(["value"]), true),
+        ("$.key-dash", json!({"key-dash": "value"}), json!(["value"]), true),
+        ("$['missing']", json!({"key": "value"}), json!([]), true),
+        ("$[0]", json!(["first", "second"]), json!(["first"]), true),
+        ("$[-1]", json!(["first", "second", "third"]), json!(["third"]), true),
+        ("$[1:3]", json!(["first", "second", "third", "forth", "fifth"]), json!(["second", "third"]), true),
+        ("$[0:3:2]", json!(["first", "second", "third", "forth", "fifth"]), json!(["first", "third"]), true),
+        ("$[-2:]", json!(["first", "second", "third"]), json!(["second", "third"]), true),
+        ("$[:2]", json!(["first", "second", "third"]), json!(["first", "second"]), true),
+        ("$[1:10]", json!(["first", "second", "third"]), json!(["second", "third"]), true),
+        ("$[2:1]", json!(["first", "second", "third"]), json!([]), true),
+        ("$[0,1]", json!(["first", "second", "third"]), json!(["first", "second"]), true),
+        ("$['key','another']", json!({"key": "value", "another": "entry"}), json!(["value", "entry"]), true),
+        ("$[*]", json!(["string", 42, {"key": "value"}, [0, 1]]), json!(["string", 42, {"key": "value"}, [0, 1]]), true),
+        ("$.*", json!({"some": "string", "int": 42}), json!(["string", 42]), false),
+        ("$..key", json!({"object": {"key": "value", "array": [{"key": "something"}, {"key": {"key": "russian dolls"}}]}, "key": "top"}),
+         json!(["top", "value", "something", {"key": "russian dolls"}, "russian dolls"]), false),
+        ("$..*", json!({"key": "value", "another key": {"complex": ["a", 1]}}),
+         json!(["value", {"complex": ["a", 1]}, ["a", 1], "a", 1]), false),
+        ("$..[*]", json!({"key": "value", "another key": {"complex": ["a", 1]}}),
+         json!(["value", {"complex": ["a", 1]}, ["a", 1], "a", 1]), false),
+        ("$..[0]", json!(["first", {"key": ["first nested", {"more": [{"nested": ["deepest", "second"]}, ["more", "values"]]}]}]),
+         json!(["deepest", "first nested", "first", "more", {"nested": ["deepest", "second"]}]), false),
+        ("$[?(@.key)]", json!([{"some": "some value"}, {"key": "value"}]), json!([{"key": "value"}]), true),
+        ("$[?(@.key==42)]", json!([{"key": 0}, {"key": 42}, {"key": -1}, {"key": 41}, {"key": "42"}]), json!([{"key": 42}]), true),
+        ("$[?(@.key>42)]", json!([{"key": 0}, {"key": 42}, {"key": 43}, {"key": 42.0001}, {"key": "43"}]), json!([{"key": 43}, {"key": 42.0001}]), true),
+    ];
+    for (query, document, expected, ordered) in cases {
+        let actual = test_ok(query, document);
+        let mut expected = expected.as_array().unwrap().clone();
+        if ordered {
+            assert_eq!(actual, expected, "{}", query);
+        } else {
+            let mut actual: Vec<String> = actual.iter().map(|v| v.to_string()).collect();
+            let mut expected: Vec<String> = expected.drain(..).map(|v| v.to_string()).collect();
+            actual.sort();
+            expected.sort();
+            assert_eq!(actual, expected, "{}", query);
+        }
+    }
+}
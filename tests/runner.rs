@@ -44,7 +44,8 @@ fn test_hurl_file() {
         redirect: http::client::Redirect::None,
         http_proxy: None,
         https_proxy: None,
-        all_proxy: None
+        all_proxy: None,
+        timeout: None
     });
     let mut lines: Vec<&str> = regex::Regex::new(r"\n|\r\n")
         .unwrap()
@@ -57,7 +58,10 @@ fn test_hurl_file() {
     let options = RunnerOptions {
         fail_fast: false,
         variables,
+        namespaces: HashMap::new(),
         to_entry: None,
+        seed: None,
+        entry_filter: None,
     };
     let logger = format::logger::Logger {
         filename: Some(filename.to_string()),
@@ -151,7 +155,8 @@ fn test_hello() {
         redirect: http::client::Redirect::None,
         http_proxy: None,
         https_proxy: None,
-        all_proxy: None
+        all_proxy: None,
+        timeout: None
     });
     let source_info = SourceInfo {
         start: Pos { line: 1, column: 1 },
@@ -163,7 +168,9 @@ fn test_hello() {
     };
     let request = hello_request();
     let hurl_file = ast::HurlFile {
-        entries: vec![ast::Entry {
+        sections: vec![],
+        templates: vec![],
+        entries: vec![ast::FileEntry::Entry(Box::new(ast::Entry {
             request,
             response: Some(ast::Response {
                 line_terminators: vec![],
@@ -187,7 +194,7 @@ fn test_hello() {
                 body: None,
                 source_info: source_info.clone(),
             }),
-        }],
+        }))],
         line_terminators: vec![],
     };
     let lines = vec![
@@ -197,7 +204,10 @@ fn test_hello() {
     let options = RunnerOptions {
         fail_fast: true,
         variables,
+        namespaces: HashMap::new(),
         to_entry: None,
+        seed: None,
+        entry_filter: None,
     };
     let logger = format::logger::Logger {
         filename: None,